@@ -1,7 +1,8 @@
 extern crate kernelvex as kv;
+use core::time::Duration;
 use kv::{
-    DifferentialDrive, MotorGroup, OdomChassis, Pose, QAngle, QLength,
-    QTime, Tank, Trajectory, TrajectoryPoint,
+    AngularPid, BackgroundChassis, DifferentialDrive, MotorGroup, OdomChassis, Pid, Pose,
+    QAngle, QLength, QTime, Routine, Step, Tank, Trajectory, TrajectoryPoint,
 };
 use kv::ExpoDrive;
 use vexide::prelude::*;
@@ -10,23 +11,11 @@ use kv::OmniWheel::Omni325;
 struct Robot {
     controller: Controller,
     dt: DifferentialDrive,
-    chassis: OdomChassis,
+    chassis: BackgroundChassis,
 }
 
 impl Compete for Robot {
     async fn autonomous(&mut self) {
-        // Drive straight 2 meters using trapezoidal profile
-        let _ = self
-            .chassis
-            .shoot(QLength::from_meters(2.))
-            .await;
-
-        // Turn to 90 degrees
-        let _ = self
-            .chassis
-            .turn(QAngle::from_degrees(90.))
-            .await;
-
         let traj = Trajectory::from_points(vec![
             TrajectoryPoint::new(
                 Pose::new(Default::default(), QAngle::from_degrees(0.0)),
@@ -42,11 +31,19 @@ impl Compete for Robot {
             ),
         ]);
 
-        // Follow path
-        let _ = self
-            .chassis
-            .trajectory(&traj)
-            .await;
+        let routine = Routine::new()
+            // Drive straight 2 meters using trapezoidal profile
+            .then(Step::shoot(QLength::from_meters(2.)).with_timeout(Duration::from_secs(3)))
+            // Turn to 90 degrees
+            .then(Step::turn(QAngle::from_degrees(90.)).with_timeout(Duration::from_secs(1)))
+            // Follow path
+            .then(Step::trajectory(traj));
+
+        // A failed step stops the routine; the report says which one.
+        let report = routine.run(&mut self.chassis).await;
+        for step in &report.steps {
+            println!("{}: {:?} in {:?}", step.name, step.outcome, step.elapsed);
+        }
     }
 
     async fn driver(&mut self) {
@@ -54,7 +51,8 @@ impl Compete for Robot {
             let state = self.controller.state().unwrap_or_default();
             _ = self
                 .dt
-                .drive_tank(state.left_stick.y(), state.right_stick.x());
+                .drive_tank(state.left_stick.y(), state.right_stick.x())
+                .await;
 
             sleep(Controller::UPDATE_INTERVAL).await;
         }
@@ -77,7 +75,9 @@ async fn main(peripherals: Peripherals) {
     let mut imu = InertialSensor::new(peripherals.port_1);
     let _ = imu.calibrate().await;
 
-    let chassis = OdomChassis::with_config(
+    // Without a tracking rig, the chassis tracks its pose with the drive
+    // motor encoders and the IMU.
+    let chassis = OdomChassis::new(
         DifferentialDrive::new(l_motor.clone(),
                                r_motor.clone(),
                                ExpoDrive::new(1.5, 1.7, None),
@@ -86,7 +86,9 @@ async fn main(peripherals: Peripherals) {
                                1.),
         imu,
         None,
-    );
+    )
+    .with_linear_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+    .with_angular_pid(AngularPid::new().set_gains(10.0, 0.0, 1.5));
 
     let robot = Robot {
         controller: peripherals.primary_controller,
@@ -96,7 +98,7 @@ async fn main(peripherals: Peripherals) {
                                    Omni325,
                                    QLength::from_meters(0.35),
                                    1.),
-        chassis,
+        chassis: BackgroundChassis::new(chassis),
     };
 
     robot.compete().await;
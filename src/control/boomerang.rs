@@ -0,0 +1,205 @@
+//! Boomerang controller for driving to a pose.
+//!
+//! Turning to face a target and driving straight at it reaches the right
+//! position but ignores the heading the robot should arrive with. The
+//! boomerang controller instead steers toward a *carrot* point placed behind
+//! the target along its heading. The carrot's offset shrinks with the
+//! distance left, so the robot sweeps in on a curve and arrives facing the
+//! target heading.
+//!
+//! # Algorithm
+//!
+//! With `d` the distance from the robot to the target and `θ` the target
+//! heading, the carrot is:
+//!
+//! ```text
+//! carrot = target - lead * d * (cos θ, sin θ)
+//! ```
+//!
+//! A `lead` of 0 drives straight at the target; larger values swing wider
+//! and line up with the target heading sooner. Driving backwards flips `θ`,
+//! placing the carrot in front of the target.
+//!
+//! # Example
+//!
+//! ```
+//! use kernelvex::{Boomerang, MoveParams, Pose, QAngle, Vec2};
+//!
+//! let target = Pose::new(Vec2::new(1.0, 0.0), QAngle::from_degrees(90.0));
+//! let controller = Boomerang::new(target, MoveParams::new().with_lead(0.5));
+//!
+//! // 1 m from the target, the carrot sits 0.5 m behind it.
+//! let carrot = controller.carrot(Pose::default());
+//! assert!((carrot.x - 1.0).abs() < 1e-9);
+//! assert!((carrot.y + 0.5).abs() < 1e-9);
+//! ```
+
+use crate::odom::pose::Pose;
+use crate::util::si::{QAngle, Vec2};
+
+/// Which way the robot faces while it drives to a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DriveDirection {
+    /// Drive forwards.
+    Forwards,
+    /// Drive backwards.
+    Backwards,
+    /// Drive whichever way needs less turning.
+    #[default]
+    Auto,
+}
+
+/// Options for the chassis's point and pose motions.
+///
+/// Use the `with_*` builder methods to change the defaults: an automatically
+/// chosen direction, a lead of 0.6, the chassis's maximum velocity and no
+/// minimum speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveParams {
+    /// Which way the robot faces while it drives
+    pub direction: DriveDirection,
+    /// How far behind the target the carrot starts, as a fraction of the
+    /// distance left
+    pub lead: f64,
+    /// Fastest the robot may drive (m/s), or `None` for the chassis's
+    /// maximum velocity
+    pub max_speed: Option<f64>,
+    /// Slowest the robot may drive (m/s), for chaining into the next motion
+    pub min_speed: f64,
+}
+
+impl MoveParams {
+    /// Creates the default options.
+    pub const fn new() -> Self {
+        Self {
+            direction: DriveDirection::Auto,
+            lead: 0.6,
+            max_speed: None,
+            min_speed: 0.0,
+        }
+    }
+
+    /// Sets which way the robot faces while it drives.
+    pub const fn with_direction(mut self, direction: DriveDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Makes the robot drive forwards or backwards to the target, instead of
+    /// choosing.
+    pub const fn with_forwards(mut self, forwards: bool) -> Self {
+        self.direction = if forwards {
+            DriveDirection::Forwards
+        } else {
+            DriveDirection::Backwards
+        };
+        self
+    }
+
+    /// Sets the carrot's lead, from 0 (straight at the target) to about 1.
+    pub const fn with_lead(mut self, lead: f64) -> Self {
+        self.lead = lead;
+        self
+    }
+
+    /// Caps the robot's speed, in m/s.
+    pub const fn with_max_speed(mut self, speed: f64) -> Self {
+        self.max_speed = Some(speed);
+        self
+    }
+
+    /// Keeps the robot moving at least this fast, in m/s.
+    ///
+    /// A robot that never slows down cannot settle, so use this with
+    /// [`ExitConditions::with_chain`](crate::ExitConditions::with_chain).
+    pub const fn with_min_speed(mut self, speed: f64) -> Self {
+        self.min_speed = speed;
+        self
+    }
+}
+
+impl Default for MoveParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Boomerang controller steering toward a carrot point behind a target pose.
+#[derive(Debug, Clone, Copy)]
+pub struct Boomerang {
+    /// The pose to reach.
+    target: Pose,
+    /// Direction, lead and speed limits.
+    params: MoveParams,
+}
+
+impl Boomerang {
+    /// Creates a boomerang controller.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The pose to reach, with the heading to arrive at
+    /// * `params` - Direction, lead and speed limits
+    pub fn new(target: Pose, params: MoveParams) -> Self {
+        Self { target, params }
+    }
+
+    /// Returns the pose to reach.
+    pub fn target(&self) -> Pose {
+        self.target
+    }
+
+    /// Returns the motion's options.
+    pub fn params(&self) -> MoveParams {
+        self.params
+    }
+
+    /// Returns the heading the robot's direction of travel should end at:
+    /// the target heading, or its opposite when driving backwards.
+    ///
+    /// [`DriveDirection::Auto`] counts as forwards; use
+    /// [`facing`](Self::facing) to choose a direction first.
+    pub fn travel_heading(&self) -> QAngle {
+        match self.params.direction {
+            DriveDirection::Backwards => {
+                self.target.heading() + QAngle::from_radians(core::f64::consts::PI)
+            }
+            _ => self.target.heading(),
+        }
+    }
+
+    /// Chooses a direction if the params left it to the controller.
+    ///
+    /// The robot drives forwards if it starts behind the target, measured
+    /// along the target heading, and backwards if it starts in front, so it
+    /// arrives at the target heading without turning around.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's pose at the start of the motion
+    pub fn facing(mut self, pose: Pose) -> Self {
+        if self.params.direction == DriveDirection::Auto {
+            let heading = self.target.heading();
+            let ahead = (self.target.position() - pose.position())
+                .dot(Vec2::new(heading.cos(), heading.sin()));
+            self.params = self.params.with_forwards(ahead >= 0.0);
+        }
+        self
+    }
+
+    /// Computes the carrot point to steer toward.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's current pose
+    ///
+    /// # Returns
+    ///
+    /// The carrot position in field coordinates.
+    pub fn carrot(&self, pose: Pose) -> Vec2<f64> {
+        let heading = self.travel_heading();
+        let distance = pose.position().distance(self.target.position());
+        let direction = Vec2::new(heading.cos(), heading.sin());
+        self.target.position() - direction * (self.params.lead * distance)
+    }
+}
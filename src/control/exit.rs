@@ -0,0 +1,327 @@
+//! Exit conditions for chassis motions.
+//!
+//! A feedback motion never reaches its target exactly, so it needs a rule for
+//! when to stop. [`ExitConditions`] collects the rules common in VEX
+//! libraries, and a motion ends as soon as any enabled one is met:
+//!
+//! | Condition | Met when |
+//! |-----------|----------|
+//! | Small error | The error has stayed within a tight tolerance for a short time |
+//! | Large error | The error has stayed within a loose tolerance for a longer time |
+//! | Velocity | The robot has moved and then stayed below a speed for some time, for example when it is stuck |
+//! | Timeout | The motion has run for too long |
+//! | Chain | The error is within the chain range; the motion ends without stopping |
+//!
+//! Chaining lets consecutive motions flow into each other: a chained motion
+//! aims to still be moving at a set speed when it reaches its exit range,
+//! leaves the drivetrain running, and the next motion starts from the
+//! robot's current velocity.
+//!
+//! [`ExitTracker`] checks the conditions each loop iteration and reports which
+//! one ended the motion as an [`ExitReason`]. Errors and velocities are in the
+//! motion's units: metres and m/s for driving, radians and rad/s for turning.
+//!
+//! # Example
+//!
+//! ```
+//! use core::time::Duration;
+//! use kernelvex::{ExitConditions, ExitReason, ExitTracker};
+//!
+//! let exit = ExitConditions::new()
+//!     .with_small_error(0.01, Duration::from_millis(100))
+//!     .with_timeout(Duration::from_secs(2));
+//! let mut tracker = ExitTracker::new(exit, Duration::ZERO);
+//!
+//! assert_eq!(tracker.update(0.005, 0.0, Duration::from_millis(10)), None);
+//! assert_eq!(
+//!     tracker.update(0.004, 0.0, Duration::from_millis(110)),
+//!     Some(ExitReason::SmallError)
+//! );
+//! ```
+
+use crate::odom::chassis::DriveError;
+use core::time::Duration;
+
+/// Rules for ending a motion.
+///
+/// Every condition starts disabled; use the `with_*` builder methods to
+/// enable them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ExitConditions {
+    /// Tolerance and time for the small error condition
+    pub small_error: Option<(f64, Duration)>,
+    /// Tolerance and time for the large error condition
+    pub large_error: Option<(f64, Duration)>,
+    /// Speed and time for the velocity condition
+    pub velocity: Option<(f64, Duration)>,
+    /// How long a robot that has not yet moved is given to start before the
+    /// velocity condition applies
+    pub velocity_delay: Duration,
+    /// Longest the motion may run
+    pub timeout: Option<Duration>,
+    /// Exit range and speed for chaining into the next motion
+    pub chain: Option<(f64, f64)>,
+}
+
+impl ExitConditions {
+    /// Creates a set of conditions with every condition disabled.
+    pub const fn new() -> Self {
+        Self {
+            small_error: None,
+            large_error: None,
+            velocity: None,
+            velocity_delay: Duration::ZERO,
+            timeout: None,
+            chain: None,
+        }
+    }
+
+    /// Default conditions for driving: within 1 cm for 100 ms, within 5 cm
+    /// for 500 ms, or below 1 cm/s for 250 ms once the robot has moved or
+    /// 1 s has passed.
+    pub const fn linear() -> Self {
+        Self::new()
+            .with_small_error(0.01, Duration::from_millis(100))
+            .with_large_error(0.05, Duration::from_millis(500))
+            .with_velocity(0.01, Duration::from_millis(250))
+            .with_velocity_delay(Duration::from_secs(1))
+    }
+
+    /// Default conditions for turning: within 1° for 100 ms, within 3° for
+    /// 500 ms, or below 3°/s for 250 ms once the robot has turned or 1 s has
+    /// passed.
+    pub const fn angular() -> Self {
+        Self::new()
+            .with_small_error(1.0f64.to_radians(), Duration::from_millis(100))
+            .with_large_error(3.0f64.to_radians(), Duration::from_millis(500))
+            .with_velocity(3.0f64.to_radians(), Duration::from_millis(250))
+            .with_velocity_delay(Duration::from_secs(1))
+    }
+
+    /// Ends the motion once the error stays within `tolerance` for `time`.
+    pub const fn with_small_error(mut self, tolerance: f64, time: Duration) -> Self {
+        self.small_error = Some((tolerance, time));
+        self
+    }
+
+    /// Ends the motion once the error stays within a looser `tolerance` for a
+    /// longer `time`.
+    pub const fn with_large_error(mut self, tolerance: f64, time: Duration) -> Self {
+        self.large_error = Some((tolerance, time));
+        self
+    }
+
+    /// Ends the motion once the robot's speed stays below `speed` for `time`.
+    ///
+    /// The robot starts at rest, so the condition only applies once the robot
+    /// has moved faster than `speed` or the
+    /// [velocity delay](Self::with_velocity_delay) has passed.
+    pub const fn with_velocity(mut self, speed: f64, time: Duration) -> Self {
+        self.velocity = Some((speed, time));
+        self
+    }
+
+    /// Gives a robot that has not moved yet `delay` to start before the
+    /// velocity condition applies.
+    ///
+    /// Motions that turn in place before driving, or that wind up slowly,
+    /// would otherwise end on the velocity condition before they begin. A
+    /// robot that never moves, for example because it is stuck, still ends
+    /// `delay` plus the velocity time after the start.
+    pub const fn with_velocity_delay(mut self, delay: Duration) -> Self {
+        self.velocity_delay = delay;
+        self
+    }
+
+    /// Ends the motion once it has run for `timeout`, reporting
+    /// [`ExitReason::Timeout`].
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Chains the motion into the next one.
+    ///
+    /// The motion ends as soon as the error is within `range`, reporting
+    /// [`ExitReason::Chained`], and leaves the drivetrain moving instead of
+    /// stopping it. Motions that plan their speed aim to be moving at `speed`
+    /// when they get there. Follow a chained motion with another motion, or
+    /// stop the drivetrain yourself. A relative motion such as
+    /// [`OdomChassis::shoot`](crate::OdomChassis::shoot) measures from where
+    /// the chained motion ended, up to `range` short of its target.
+    pub const fn with_chain(mut self, range: f64, speed: f64) -> Self {
+        self.chain = Some((range, speed));
+        self
+    }
+
+    /// Returns the speed to leave the motion at: the chain speed, or zero if
+    /// the motion is not chained.
+    pub fn exit_speed(&self) -> f64 {
+        self.chain.map_or(0.0, |(_, speed)| speed.abs())
+    }
+}
+
+/// Why a motion ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The motion reached the end of its path or profile.
+    Completed,
+    /// The small error condition was met.
+    SmallError,
+    /// The large error condition was met.
+    LargeError,
+    /// The velocity condition was met.
+    Velocity,
+    /// The motion ran out of time before settling.
+    Timeout,
+    /// The motion reached its chain range and left the drivetrain moving.
+    Chained,
+}
+
+impl ExitReason {
+    /// Returns whether the motion was cut short by its timeout.
+    pub const fn is_timeout(self) -> bool {
+        matches!(self, Self::Timeout)
+    }
+
+    /// Returns whether the motion ended without stopping the drivetrain.
+    pub const fn is_chained(self) -> bool {
+        matches!(self, Self::Chained)
+    }
+
+    /// Turns a timeout into [`DriveError::Timeout`], for routines that cannot
+    /// carry on after a motion fails to settle.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// chassis.turn(QAngle::from_degrees(90.0)).await?.ok_or_timeout()?;
+    /// ```
+    pub fn ok_or_timeout(self) -> Result<Self, DriveError> {
+        match self {
+            Self::Timeout => Err(DriveError::Timeout),
+            reason => Ok(reason),
+        }
+    }
+}
+
+/// Checks a motion's [`ExitConditions`] as it runs.
+#[derive(Debug, Clone)]
+pub struct ExitTracker {
+    conditions: ExitConditions,
+    start: Duration,
+    /// When each condition's threshold was last crossed into: small error,
+    /// large error, velocity
+    since: [Option<Duration>; 3],
+    /// Whether the robot has moved faster than the velocity threshold
+    moved: bool,
+}
+
+impl ExitTracker {
+    /// Starts tracking a motion.
+    ///
+    /// # Arguments
+    ///
+    /// * `conditions` - The rules for ending the motion
+    /// * `now` - The time the motion starts
+    pub fn new(conditions: ExitConditions, now: Duration) -> Self {
+        Self {
+            conditions,
+            start: now,
+            since: [None; 3],
+            moved: false,
+        }
+    }
+
+    /// Returns the conditions being checked.
+    pub fn conditions(&self) -> ExitConditions {
+        self.conditions
+    }
+
+    /// Checks the conditions against the latest measurements.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Distance from the target
+    /// * `velocity` - The robot's speed
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    ///
+    /// The condition that was met, or `None` if the motion should continue.
+    /// Chaining takes precedence over settling, and settling over the
+    /// timeout, when several are met at once.
+    pub fn update(&mut self, error: f64, velocity: f64, now: Duration) -> Option<ExitReason> {
+        if let Some(reason) = self.chained(error) {
+            return Some(reason);
+        }
+
+        // A robot that has not started moving is not stalled yet.
+        if let Some((threshold, _)) = self.conditions.velocity {
+            self.moved |= velocity.abs() > threshold;
+        }
+        let armed = self.moved
+            || now.saturating_sub(self.start) >= self.conditions.velocity_delay;
+
+        let checks = [
+            (self.conditions.small_error, error, ExitReason::SmallError),
+            (self.conditions.large_error, error, ExitReason::LargeError),
+            (
+                self.conditions.velocity.filter(|_| armed),
+                velocity,
+                ExitReason::Velocity,
+            ),
+        ];
+
+        let mut exit = None;
+        for ((condition, value, reason), since) in checks.into_iter().zip(self.since.iter_mut()) {
+            let Some((threshold, time)) = condition else {
+                continue;
+            };
+            if value.abs() > threshold {
+                *since = None;
+                continue;
+            }
+            let since = *since.get_or_insert(now);
+            if exit.is_none() && now.saturating_sub(since) >= time {
+                exit = Some(reason);
+            }
+        }
+
+        exit.or_else(|| self.timed_out(now))
+    }
+
+    /// Checks only the timeout, for the part of a motion where the error
+    /// conditions do not apply yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    ///
+    /// [`ExitReason::Timeout`] if the motion has run out of time.
+    pub fn timed_out(&self, now: Duration) -> Option<ExitReason> {
+        self.conditions
+            .timeout
+            .filter(|&timeout| now.saturating_sub(self.start) >= timeout)
+            .map(|_| ExitReason::Timeout)
+    }
+
+    /// Checks only the chain range, for the part of a motion where the
+    /// settling conditions do not apply yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - Distance from the target
+    ///
+    /// # Returns
+    ///
+    /// [`ExitReason::Chained`] if the motion is chained and within range.
+    pub fn chained(&self, error: f64) -> Option<ExitReason> {
+        self.conditions
+            .chain
+            .filter(|&(range, _)| error.abs() <= range)
+            .map(|_| ExitReason::Chained)
+    }
+}
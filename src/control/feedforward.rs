@@ -152,6 +152,12 @@ impl FeedForward {
     }
 }
 
+impl Default for FeedForward {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Feedforward controller for arm mechanisms with gravity compensation.
 ///
 /// Extends [`FeedForward`] with a gravity compensation term that accounts for
@@ -1,3 +1,5 @@
+pub mod boomerang;
+pub mod exit;
 pub mod feedforward;
 pub mod pid;
 pub mod purepursuit;
@@ -24,11 +24,19 @@
 //! pid.calculate(setpoint, current_value);
 //! // Use the PID output to adjust your system
 //! ```
+//!
+//! # Timing
+//!
+//! [`Pid::calculate`] measures the time between calls with a [`Clock`], which
+//! defaults to [`SystemClock`]. Use `with_clock` to substitute a
+//! [`ManualClock`](crate::util::clock::ManualClock) in tests, or
+//! `calculate_with_dt` to supply the time step explicitly.
 
 #![allow(dead_code)]
 
-use crate::QAngle;
-use std::time::Instant;
+use crate::util::clock::{Clock, SystemClock};
+use crate::{QAngle, QTime};
+use core::time::Duration;
 
 /// A PID controller for closed-loop control systems.
 ///
@@ -44,8 +52,9 @@ use std::time::Instant;
 /// * `kd` - Derivative gain constant
 /// * `integral` - Accumulated integral term (sum of errors over time)
 /// * `previous_error` - Error from the last calculation (for derivative term)
+/// * `clock` - Time source used to measure the interval between calculations
 /// * `last_time` - Timestamp of the last calculation
-pub struct Pid {
+pub struct Pid<C: Clock = SystemClock> {
     /// Proportional gain constant
     kp: f64,
     /// Integral gain constant
@@ -56,10 +65,10 @@ pub struct Pid {
     integral: f64,
     /// Previous error value (for calculating derivative)
     previous_error: f64,
-    /// Timestamp of controller since construction
-    time: Instant,
-    /// Timestamp of the last calculation
-    last_time: f64,
+    /// Time source for measuring calculation intervals
+    clock: C,
+    /// Clock time of the last calculation (or reset)
+    last_time: Duration,
     /// Minimum output value
     min: f64,
     /// Maximum output value
@@ -101,14 +110,49 @@ impl Pid {
             kd: 0.,
             integral: 0.0,
             previous_error: 0.0,
-            time: Instant::now(),
-            last_time: 0.,
+            clock: SystemClock::new(),
+            last_time: Duration::ZERO,
             min: f64::NEG_INFINITY,
             max: f64::INFINITY,
             imin: f64::NEG_INFINITY,
             imax: f64::INFINITY,
         }
     }
+}
+
+impl<C: Clock> Pid<C> {
+    /// Replaces the clock used to measure time between calculations.
+    ///
+    /// Gains and limits are kept; the controller state is reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The new time source
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kernelvex::Pid;
+    /// # use kernelvex::util::clock::ManualClock;
+    /// let clock = ManualClock::new();
+    /// let pid = Pid::new().set_gains(1.0, 0.0, 0.0).with_clock(clock.clone());
+    /// ```
+    pub fn with_clock<D: Clock>(self, clock: D) -> Pid<D> {
+        let last_time = clock.now();
+        Pid {
+            kp: self.kp,
+            ki: self.ki,
+            kd: self.kd,
+            integral: 0.0,
+            previous_error: 0.0,
+            clock,
+            last_time,
+            min: self.min,
+            max: self.max,
+            imin: self.imin,
+            imax: self.imax,
+        }
+    }
 
     /// Returns the current PID gain constants.
     ///
@@ -122,8 +166,9 @@ impl Pid {
     /// Calculates the PID output for the given error.
     ///
     /// This method should be called periodically in the control loop (typically
-    /// every iteration or every few milliseconds). It calculates the time delta
-    /// since the last call and updates the integral and derivative terms accordingly.
+    /// every iteration or every few milliseconds). It measures the time delta
+    /// since the last call (or reset) on the controller's clock and passes it
+    /// to [`calculate_with_dt`](Self::calculate_with_dt).
     ///
     /// # PID Formula
     ///
@@ -158,10 +203,43 @@ impl Pid {
     /// // Apply `output` to your motor or actuator
     /// ```
     pub fn calculate(&mut self, setpoint: f64, actual: f64) -> f64 {
+        let now = self.clock.now();
+        let dt = now.saturating_sub(self.last_time);
+        self.last_time = now;
+
+        self.calculate_with_dt(setpoint, actual, QTime::from_sec(dt.as_secs_f64()))
+    }
+
+    /// Calculates the PID output using an explicit time step.
+    ///
+    /// This bypasses the controller's clock entirely, which makes the output a
+    /// pure function of the inputs and the controller state. Time steps of zero
+    /// or less are treated as 1 ms to avoid derivative spikes.
+    ///
+    /// # Arguments
+    ///
+    /// * `setpoint` - The desired value
+    /// * `actual` - The measured value
+    /// * `dt` - Time elapsed since the previous calculation
+    ///
+    /// # Returns
+    ///
+    /// The PID controller output value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use kernelvex::{Pid, QTime};
+    /// let mut pid = Pid::new().set_gains(1.0, 2.0, 0.0);
+    ///
+    /// // error = 1.0, integral = 1.0 * 0.5
+    /// let output = pid.calculate_with_dt(1.0, 0.0, QTime::from_sec(0.5));
+    /// assert_eq!(output, 2.0);
+    /// ```
+    pub fn calculate_with_dt(&mut self, setpoint: f64, actual: f64, dt: QTime) -> f64 {
         let error = setpoint - actual;
 
-        let t = self.time.elapsed().as_secs_f64();
-        let mut dt = t - self.last_time;
+        let mut dt = dt.as_sec();
 
         if dt <= 0.0 {
             dt = 0.001; // 1 ms minimum to avoid spikes
@@ -171,12 +249,10 @@ impl Pid {
 
         self.integral = (self.integral + error * dt).clamp(self.imin, self.imax);
 
-        let derivative = if dt > 0. { de / dt } else { 0. };
+        let derivative = de / dt;
 
         self.previous_error = error;
 
-        self.last_time = t;
-
         ((self.kp * error) + (self.ki * self.integral) + (derivative * self.kd))
             .clamp(self.min, self.max)
     }
@@ -197,8 +273,7 @@ impl Pid {
     pub fn reset(&mut self) {
         self.integral = 0.0;
         self.previous_error = 0.0;
-        self.time = Instant::now();
-        self.last_time = 0.0;
+        self.last_time = self.clock.now();
     }
 
     /// Sets new PID gain constants.
@@ -217,20 +292,11 @@ impl Pid {
     /// // Tune the PID during runtime
     /// pid.set_gains(1.5, 0.02, 0.15);
     /// ```
-    pub const fn set_gains(self, kp: f64, ki: f64, kd: f64) -> Self {
-        Self {
-            kp,
-            ki,
-            kd,
-            integral: self.integral,
-            previous_error: self.previous_error,
-            time: self.time,
-            last_time: self.last_time,
-            min: self.min,
-            max: self.max,
-            imin: self.imin,
-            imax: self.imax,
-        }
+    pub const fn set_gains(mut self, kp: f64, ki: f64, kd: f64) -> Self {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+        self
     }
 
     pub const fn set_kp(&mut self, kp: f64) {
@@ -260,6 +326,12 @@ impl Pid {
     }
 }
 
+impl Default for Pid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A PID controller for angular control with angle-aware error calculation.
 ///
 /// Unlike the regular [`Pid`], this controller:
@@ -277,7 +349,7 @@ impl Pid {
 ///     QAngle::from_degrees(85.0),  // current heading
 /// );
 /// ```
-pub struct AngularPid {
+pub struct AngularPid<C: Clock = SystemClock> {
     /// Proportional gain constant
     kp: f64,
     /// Integral gain constant
@@ -288,10 +360,10 @@ pub struct AngularPid {
     integral: f64,
     /// Previous error value (for calculating derivative) in radians
     previous_error: f64,
-    /// Timestamp of controller since construction
-    time: Instant,
-    /// Timestamp of the last calculation
-    last_time: f64,
+    /// Time source for measuring calculation intervals
+    clock: C,
+    /// Clock time of the last calculation (or reset)
+    last_time: Duration,
     /// Minimum output value (volts)
     min: f64,
     /// Maximum output value (volts)
@@ -333,14 +405,40 @@ impl AngularPid {
             kd: 0.,
             integral: 0.0,
             previous_error: 0.0,
-            time: Instant::now(),
-            last_time: 0.,
+            clock: SystemClock::new(),
+            last_time: Duration::ZERO,
             min: f64::NEG_INFINITY,
             max: f64::INFINITY,
             imin: f64::NEG_INFINITY,
             imax: f64::INFINITY,
         }
     }
+}
+
+impl<C: Clock> AngularPid<C> {
+    /// Replaces the clock used to measure time between calculations.
+    ///
+    /// Gains and limits are kept; the controller state is reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The new time source
+    pub fn with_clock<D: Clock>(self, clock: D) -> AngularPid<D> {
+        let last_time = clock.now();
+        AngularPid {
+            kp: self.kp,
+            ki: self.ki,
+            kd: self.kd,
+            integral: 0.0,
+            previous_error: 0.0,
+            clock,
+            last_time,
+            min: self.min,
+            max: self.max,
+            imin: self.imin,
+            imax: self.imax,
+        }
+    }
 
     /// Returns the current PID gain constants.
     ///
@@ -354,8 +452,9 @@ impl AngularPid {
     /// Calculates the PID output voltage for the given angular setpoint and measurement.
     ///
     /// This method should be called periodically in the control loop (typically
-    /// every iteration or every few milliseconds). It calculates the time delta
-    /// since the last call and updates the integral and derivative terms accordingly.
+    /// every iteration or every few milliseconds). It measures the time delta
+    /// since the last call (or reset) on the controller's clock and passes it
+    /// to [`calculate_with_dt`](Self::calculate_with_dt).
     ///
     /// The error is wrapped to [-π, π] to ensure the shortest rotation path is taken.
     ///
@@ -393,11 +492,32 @@ impl AngularPid {
     /// // Apply `output_volts` to your motor or actuator
     /// ```
     pub fn calculate(&mut self, setpoint: QAngle, actual: QAngle) -> f64 {
+        let now = self.clock.now();
+        let dt = now.saturating_sub(self.last_time);
+        self.last_time = now;
+
+        self.calculate_with_dt(setpoint, actual, QTime::from_sec(dt.as_secs_f64()))
+    }
+
+    /// Calculates the PID output using an explicit time step.
+    ///
+    /// This bypasses the controller's clock entirely. Time steps of zero or
+    /// less are treated as 1 ms to avoid derivative spikes.
+    ///
+    /// # Arguments
+    ///
+    /// * `setpoint` - The target heading angle
+    /// * `actual` - The current heading angle
+    /// * `dt` - Time elapsed since the previous calculation
+    ///
+    /// # Returns
+    ///
+    /// The PID controller output voltage.
+    pub fn calculate_with_dt(&mut self, setpoint: QAngle, actual: QAngle, dt: QTime) -> f64 {
         // Wrap error to [-π, π] for shortest path
         let error = (setpoint - actual).remainder(QAngle::TAU).as_radians();
 
-        let t = self.time.elapsed().as_secs_f64();
-        let mut dt = t - self.last_time;
+        let mut dt = dt.as_sec();
 
         if dt <= 0.0 {
             dt = 0.001; // 1 ms minimum to avoid spikes
@@ -407,12 +527,10 @@ impl AngularPid {
 
         self.integral = (self.integral + error * dt).clamp(self.imin, self.imax);
 
-        let derivative = if dt > 0. { de / dt } else { 0.0 };
+        let derivative = de / dt;
 
         self.previous_error = error;
 
-        self.last_time = t;
-
         ((self.kp * error) + (self.ki * self.integral) + (derivative * self.kd))
             .clamp(self.min, self.max)
     }
@@ -433,8 +551,7 @@ impl AngularPid {
     pub fn reset(&mut self) {
         self.integral = 0.0;
         self.previous_error = 0.0;
-        self.time = Instant::now();
-        self.last_time = 0.0;
+        self.last_time = self.clock.now();
     }
 
     /// Sets new PID gain constants.
@@ -453,20 +570,11 @@ impl AngularPid {
     /// // Tune the PID during runtime
     /// pid.set_gains(1.5, 0.02, 0.15);
     /// ```
-    pub const fn set_gains(self, kp: f64, ki: f64, kd: f64) -> Self {
-        Self {
-            kp,
-            ki,
-            kd,
-            integral: self.integral,
-            previous_error: self.previous_error,
-            time: self.time,
-            last_time: self.last_time,
-            min: self.min,
-            max: self.max,
-            imin: self.imin,
-            imax: self.imax,
-        }
+    pub const fn set_gains(mut self, kp: f64, ki: f64, kd: f64) -> Self {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+        self
     }
 
     /// Set output saturation limits (in volts).
@@ -505,3 +613,9 @@ impl AngularPid {
         self.kd = kd
     }
 }
+
+impl Default for AngularPid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
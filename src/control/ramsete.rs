@@ -68,8 +68,10 @@ pub struct RamseteController {
     /// Small-angle epsilon for sinc calculation.
     epsilon: f64,
     /// Maximum linear velocity (None = unlimited).
+    #[allow(dead_code)]
     max_v: Option<f64>,
     /// Maximum angular velocity (None = unlimited).
+    #[allow(dead_code)]
     max_w: Option<f64>,
 }
 
@@ -218,6 +220,12 @@ impl RamseteController {
     }
 }
 
+impl Default for RamseteController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Computes sinc(theta) = sin(theta)/theta with small-angle handling.
 ///
 /// For |theta| < epsilon, returns 1.0 to avoid division by near-zero.
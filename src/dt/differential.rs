@@ -51,8 +51,10 @@
 //! ```
 
 use crate::dt::model::{Arcade, CurvatureDrive, Drivetrain, Tank};
+use crate::hal::motor::MotorOutput;
 use crate::util::utils::GroupErrors;
 use crate::{MotorGroup, OmniWheel, QLength, Vec2};
+use vexide::smart::motor::Motor;
 
 /// A differential (tank-style) drivetrain with left and right motor groups.
 ///
@@ -68,6 +70,10 @@ use crate::{MotorGroup, OmniWheel, QLength, Vec2};
 /// - `ratio`: Gear ratio (motor rotations per wheel rotation)
 /// - `expo`: Exponential drive scaling for smoother control
 ///
+/// The motor type defaults to the V5 smart [`Motor`] but can be any
+/// [`MotorOutput`], which allows the drivetrain to be driven against
+/// simulated motors.
+///
 /// # Velocity Estimation
 ///
 /// The drivetrain can estimate linear and angular velocity from motor RPM:
@@ -77,11 +83,11 @@ use crate::{MotorGroup, OmniWheel, QLength, Vec2};
 /// linear_vel = (left_wheel_vel + right_wheel_vel) / 2
 /// angular_vel = (right_wheel_vel - left_wheel_vel) / track_width
 /// ```
-pub struct DifferentialDrive {
+pub struct DifferentialDrive<M: MotorOutput = Motor> {
     /// Left motor group.
-    left: MotorGroup,
+    left: MotorGroup<M>,
     /// Right motor group.
-    right: MotorGroup,
+    right: MotorGroup<M>,
     /// Wheel type for size calculations.
     wheel: OmniWheel,
     /// Track width (distance between wheel centers).
@@ -92,6 +98,40 @@ pub struct DifferentialDrive {
     expo: ExpoDrive
 }
 
+/// One side of a [`DifferentialDrive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The left side.
+    Left,
+    /// The right side.
+    Right,
+}
+
+impl Side {
+    /// Returns the other side.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// Cloning shares the motors, as [`MotorGroup`] does, so a clone can read the
+/// encoders while the original drives.
+impl<M: MotorOutput> Clone for DifferentialDrive<M> {
+    fn clone(&self) -> Self {
+        Self {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            wheel: self.wheel,
+            width: self.width,
+            ratio: self.ratio,
+            expo: self.expo,
+        }
+    }
+}
+
 /// Exponential drive scaling for smoother joystick control.
 ///
 /// `ExpoDrive` applies an exponential curve to joystick inputs, providing
@@ -118,6 +158,7 @@ pub struct DifferentialDrive {
 /// let expo = ExpoDrive::new(2.0, 1.0);
 /// let (x, y) = expo.calculate(0.5, 0.5).as_tuple();
 /// ```
+#[derive(Debug, Clone, Copy)]
 pub struct ExpoDrive {
     /// Curve shape parameter (higher = more aggressive near center).
     n: f64,
@@ -155,11 +196,11 @@ impl ExpoDrive {
     /// Panics if inputs are outside [-1, 1] range.
     pub fn calculate(&self, x: f64, y: f64) -> Vec2<f64> {
         {
-            assert!(-1.0 <= x && x <= 1.0, "x must be between [-1, 1]");
+            assert!((-1.0..=1.0).contains(&x), "x must be between [-1, 1]");
         }
 
         {
-            assert!(-1.0 <= y && y <= 1.0, "y must be between [-1, 1]");
+            assert!((-1.0..=1.0).contains(&y), "y must be between [-1, 1]");
         }
 
         if x == 0. && y == 0. {
@@ -172,11 +213,11 @@ impl ExpoDrive {
         let mut fx = f*x;
         let mut fy = f*y;
 
-        if fx <= self.eps {
+        if libm::fabs(fx) <= self.eps {
             fx = 0.;
         }
 
-        if fy <= self.eps {
+        if libm::fabs(fy) <= self.eps {
             fy = 0.;
         }
 
@@ -184,7 +225,7 @@ impl ExpoDrive {
     }
 }
 
-impl DifferentialDrive {
+impl<M: MotorOutput> DifferentialDrive<M> {
     /// Creates a new differential drivetrain.
     ///
     /// # Arguments
@@ -209,7 +250,7 @@ impl DifferentialDrive {
     /// );
     /// ```
     #[inline]
-    pub fn new(left: MotorGroup, right: MotorGroup, expo: ExpoDrive, wheel: OmniWheel, width: QLength, ratio: f64) -> Self {
+    pub fn new(left: MotorGroup<M>, right: MotorGroup<M>, expo: ExpoDrive, wheel: OmniWheel, width: QLength, ratio: f64) -> Self {
         Self { left, right, expo, wheel, width, ratio }
     }
 
@@ -222,9 +263,47 @@ impl DifferentialDrive {
     pub fn wheel(&self) -> &OmniWheel {
         &self.wheel
     }
+
+    /// Returns the gear ratio (wheel rotations per motor rotation).
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Returns the motor group on one side, for example to brake it while
+    /// the other side drives.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - Which side's motors to return
+    pub fn side_mut(&mut self, side: Side) -> &mut MotorGroup<M> {
+        match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        }
+    }
+
+    /// Returns the distance each side's wheels have rolled, from the motor encoders.
+    ///
+    /// # Formula
+    ///
+    /// ```text
+    /// travel = motor_turns * ratio * PI * wheel_diameter
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((left, right))` - Distance rolled by each side since the encoders were zeroed
+    /// * `Err(GroupErrors)` - Motor encoder read error
+    pub async fn travel(&self) -> Result<(QLength, QLength), GroupErrors> {
+        let circumference = self.wheel.size() * std::f64::consts::PI;
+        let left = self.left.position().await?.as_turns() * self.ratio;
+        let right = self.right.position().await?.as_turns() * self.ratio;
+
+        Ok((circumference * left, circumference * right))
+    }
 }
 
-impl Arcade for DifferentialDrive {
+impl<M: MotorOutput + Send> Arcade for DifferentialDrive<M> {
     /// Drives using arcade control with exponential scaling.
     ///
     /// Arcade drive combines forward/backward and left/right inputs into
@@ -265,7 +344,7 @@ impl Arcade for DifferentialDrive {
     }
 }
 
-impl Tank for DifferentialDrive {
+impl<M: MotorOutput + Send> Tank for DifferentialDrive<M> {
     /// Drives using tank control with exponential scaling.
     ///
     /// Tank drive controls each side independently. The inputs are scaled
@@ -289,7 +368,7 @@ impl Tank for DifferentialDrive {
     }
 }
 
-impl CurvatureDrive for DifferentialDrive {
+impl<M: MotorOutput + Send> CurvatureDrive for DifferentialDrive<M> {
     /// Drives using curvature control.
     ///
     /// Curvature drive decouples throttle from turn rate, allowing sharp turns
@@ -332,7 +411,7 @@ impl CurvatureDrive for DifferentialDrive {
     }
 }
 
-impl Drivetrain for DifferentialDrive {
+impl<M: MotorOutput> Drivetrain for DifferentialDrive<M> {
     /// Estimates linear velocity from motor encoders (IME fallback).
     ///
     /// Computes the average wheel velocity from left and right motor RPM,
@@ -341,7 +420,7 @@ impl Drivetrain for DifferentialDrive {
     /// # Formula
     ///
     /// ```text
-    /// wheel_vel = motor_rpm * ratio * (2*PI / 60) * wheel_diameter
+    /// wheel_vel = motor_rpm * ratio * (2*PI / 60) * wheel_diameter / 2
     /// linear_vel = (left_wheel_vel + right_wheel_vel) / 2
     /// ```
     ///
@@ -354,7 +433,7 @@ impl Drivetrain for DifferentialDrive {
         let right = self.right.velocity().await? as f64;
         let avg = (left+right)/2.;
 
-        let vel = avg * self.ratio * (std::f64::consts::TAU / 60.) * self.wheel.size().as_meters();
+        let vel = avg * self.ratio * (std::f64::consts::TAU / 60.) * self.wheel.size().as_meters() / 2.;
 
         Ok(vel)
 
@@ -383,7 +462,7 @@ impl Drivetrain for DifferentialDrive {
         let vel = |rpm: f64| -> f64 {
             let wheel_rpm = rpm * self.ratio;
             let angular_vel = wheel_rpm * (2.0 * core::f64::consts::PI / 60.0);
-            angular_vel * self.wheel.size().as_meters()
+            angular_vel * self.wheel.size().as_meters() / 2.
         };
 
         let left = vel(left_rpm);
@@ -15,6 +15,12 @@
 //! of individual motor port errors. If any motor fails, the errors are collected
 //! but the operation continues for the remaining motors.
 //!
+//! # Motor Types
+//!
+//! `MotorGroup` is generic over any [`MotorOutput`] and defaults to the V5 smart
+//! [`Motor`]. Operations that only exist on the V5 motor (velocity and position
+//! targets, direction) are available when the group holds [`Motor`]s.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -32,8 +38,10 @@
 
 #![allow(dead_code)]
 
+use crate::hal::motor::MotorOutput;
 use crate::util::si::QAngle;
 use crate::util::utils::GroupErrors;
+use std::borrow::BorrowMut;
 use std::sync::Arc;
 use vexide::math::Direction;
 use vexide::smart::motor::{BrakeMode, Motor, MotorControl};
@@ -56,7 +64,7 @@ use heapless::Vec;
 ///
 /// ```
 /// use vexide::prelude::*;
-/// use vexide_devices::smart::SmartPort;
+/// use vexide::smart::SmartPort;
 /// use kernelvex::MotorGroup;
 ///
 ///
@@ -75,13 +83,20 @@ use heapless::Vec;
 /// let avg_rpm = group.velocity().await;
 /// }
 /// ```
-#[derive(Clone)]
-pub struct MotorGroup {
+pub struct MotorGroup<M: MotorOutput = Motor> {
     /// Thread-safe storage for up to 8 motors.
-    motors: Arc<Mutex<Vec<Motor, 8>>>,
+    motors: Arc<Mutex<Vec<M, 8>>>,
 }
 
-impl MotorGroup {
+impl<M: MotorOutput> Clone for MotorGroup<M> {
+    fn clone(&self) -> Self {
+        Self {
+            motors: Arc::clone(&self.motors),
+        }
+    }
+}
+
+impl<M: MotorOutput> MotorGroup<M> {
     /// Runs a function on a specific motor by index.
     ///
     /// # Arguments
@@ -94,7 +109,7 @@ impl MotorGroup {
     /// Panics if `index` is out of bounds.
     pub async fn use_at<F, R>(&self, index: usize, f: F) -> R
     where
-        F: FnOnce(&mut Motor) -> R,
+        F: FnOnce(&mut M) -> R,
     {
         let mut guard = self.motors.lock().await;
         f(&mut guard[index])
@@ -120,7 +135,7 @@ impl MotorGroup {
     /// ```ignore
     /// let group = MotorGroup::new([motor1, motor2]);
     /// ```
-    pub fn new<const N: usize>(motors: [Motor; N]) -> Self {
+    pub fn new<const N: usize>(motors: [M; N]) -> Self {
         MotorGroup {
             motors: Arc::new(Mutex::new(Vec::from(motors))),
         }
@@ -157,11 +172,14 @@ impl MotorGroup {
     ///
     /// * `Ok(())` - All motors set successfully
     /// * `Err(GroupErrors)` - One or more motors failed
-    pub async fn set_velocity(&mut self, rpm: i32) -> Result<(), GroupErrors> {
+    pub async fn set_velocity(&mut self, rpm: i32) -> Result<(), GroupErrors>
+    where
+        M: BorrowMut<Motor>,
+    {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_velocity(rpm).err())
+            .filter_map(|motor| motor.borrow_mut().set_velocity(rpm).err())
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -195,11 +213,14 @@ impl MotorGroup {
     ///
     /// * `Ok(())` - All motors set successfully
     /// * `Err(GroupErrors)` - One or more motors failed
-    pub async fn set_direction(&mut self, direction: Direction) -> Result<(), GroupErrors> {
+    pub async fn set_direction(&mut self, direction: Direction) -> Result<(), GroupErrors>
+    where
+        M: BorrowMut<Motor>,
+    {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_direction(direction).err())
+            .filter_map(|motor| motor.borrow_mut().set_direction(direction).err())
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -221,11 +242,19 @@ impl MotorGroup {
         &mut self,
         position: QAngle,
         velocity: i32,
-    ) -> Result<(), GroupErrors> {
+    ) -> Result<(), GroupErrors>
+    where
+        M: BorrowMut<Motor>,
+    {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_position_target(position.into(), velocity).err())
+            .filter_map(|motor| {
+                motor
+                    .borrow_mut()
+                    .set_position_target(position.into(), velocity)
+                    .err()
+            })
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -242,11 +271,14 @@ impl MotorGroup {
     ///
     /// * `Ok(())` - All motors set successfully
     /// * `Err(GroupErrors)` - One or more motors failed
-    pub async fn set_profiled_velocity(&mut self, velocity: i32) -> Result<(), GroupErrors> {
+    pub async fn set_profiled_velocity(&mut self, velocity: i32) -> Result<(), GroupErrors>
+    where
+        M: BorrowMut<Motor>,
+    {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_profiled_velocity(velocity).err())
+            .filter_map(|motor| motor.borrow_mut().set_profiled_velocity(velocity).err())
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -261,11 +293,14 @@ impl MotorGroup {
     ///
     /// * `Ok(())` - All motors set successfully
     /// * `Err(GroupErrors)` - One or more motors failed
-    pub async fn set_target(&mut self, target: MotorControl) -> Result<(), GroupErrors> {
+    pub async fn set_target(&mut self, target: MotorControl) -> Result<(), GroupErrors>
+    where
+        M: BorrowMut<Motor>,
+    {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_target(target).err())
+            .filter_map(|motor| motor.borrow_mut().set_target(target).err())
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -284,7 +319,7 @@ impl MotorGroup {
         let mut guard = self.motors.lock().await;
         let ret: GroupErrors = guard
             .iter_mut()
-            .filter_map(|motor| motor.set_position(position.into()).err())
+            .filter_map(|motor| motor.set_position(position).err())
             .collect();
         if ret.is_empty() { Ok(()) } else { Err(ret) }
     }
@@ -329,4 +364,29 @@ impl MotorGroup {
             Err(errors)
         }
     }
+
+    /// Returns the average encoder position of all motors in the group.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(QAngle)` - Average motor shaft position
+    /// * `Err(GroupErrors)` - One or more motors failed to read
+    pub async fn position(&self) -> Result<QAngle, GroupErrors> {
+        let guard = self.motors.lock().await;
+        let mut errors = GroupErrors::new();
+        let mut total = 0.0;
+
+        for motor in guard.iter() {
+            match motor.position() {
+                Ok(position) => total += position.as_radians(),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(QAngle::from_radians(total / guard.len() as f64))
+        } else {
+            Err(errors)
+        }
+    }
 }
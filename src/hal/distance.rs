@@ -0,0 +1,24 @@
+//! Distance sensor abstraction.
+//!
+//! This module defines the [`RangeFinder`] trait used by the localization
+//! code in [`odom`](crate::odom) to read how far a sensor's beam travels
+//! before hitting an object.
+//!
+//! The V5 [`DistanceSensor`] is the default implementation.
+
+use crate::util::si::QLength;
+use vexide::smart::distance::{DistanceObjectError, DistanceSensor};
+
+/// A sensor that measures the distance to the nearest object along its beam.
+pub trait RangeFinder {
+    /// Returns the distance to the detected object, or `None` if nothing is in range.
+    fn distance(&self) -> Result<Option<QLength>, DistanceObjectError>;
+}
+
+impl RangeFinder for DistanceSensor {
+    fn distance(&self) -> Result<Option<QLength>, DistanceObjectError> {
+        self.object().map(|object| {
+            object.map(|object| QLength::from_meters(object.distance as f64 / 1000.0))
+        })
+    }
+}
@@ -0,0 +1,57 @@
+//! Rotary encoder abstraction.
+//!
+//! This module defines the [`RotaryEncoder`] trait used by
+//! [`TrackingWheel`](crate::TrackingWheel) to measure wheel rotation.
+//!
+//! Implementations are provided for the V5 [`RotationSensor`] and the ADI
+//! quadrature [`AdiEncoder`]. The [`Encoder`](crate::odom::wheel::Encoder) enum
+//! also implements the trait so either sensor can be mixed on the same rig.
+
+use crate::util::si::QAngle;
+use vexide::adi::encoder::AdiEncoder;
+use vexide::smart::PortError;
+use vexide::smart::rotation::RotationSensor;
+
+/// A sensor that measures the total rotation of a shaft.
+pub trait RotaryEncoder {
+    /// Returns the total rotation measured since the last reset.
+    fn position(&self) -> Result<QAngle, PortError>;
+
+    /// Overwrites the measured position with the given angle.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The angle to set the encoder position to
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError>;
+
+    /// Resets the measured position to zero.
+    fn reset_position(&mut self) -> Result<(), PortError>;
+}
+
+impl RotaryEncoder for RotationSensor {
+    fn position(&self) -> Result<QAngle, PortError> {
+        RotationSensor::position(self).map(QAngle::from)
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        RotationSensor::set_position(self, position.into())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        RotationSensor::reset_position(self)
+    }
+}
+
+impl<const TPR: u32> RotaryEncoder for AdiEncoder<TPR> {
+    fn position(&self) -> Result<QAngle, PortError> {
+        AdiEncoder::position(self).map(QAngle::from)
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        AdiEncoder::set_position(self, position.into())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        AdiEncoder::reset_position(self)
+    }
+}
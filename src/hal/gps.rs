@@ -0,0 +1,45 @@
+//! Absolute position sensor abstraction.
+//!
+//! This module defines the [`Gps`] trait used by
+//! [`GpsRig`](crate::odom::gps::GpsRig) to read the robot's position and
+//! heading on the field.
+//!
+//! The V5 [`GpsSensor`] is the default implementation.
+//!
+//! # Conventions
+//!
+//! Values follow the V5 GPS sensor: positions are in metres with the origin at
+//! the centre of the field, and the heading is measured clockwise in degrees
+//! with zero facing the positive y-axis. Readings describe the sensor itself;
+//! configure the `GpsSensor` with a zero offset and let `GpsRig` apply the
+//! mounting offset.
+
+use crate::util::si::{QAngle, QLength, Vec2};
+use vexide::smart::PortError;
+use vexide::smart::gps::GpsSensor;
+
+/// A sensor that reports its absolute position and heading on the field.
+pub trait Gps {
+    /// Returns the sensor position in metres, relative to the field centre.
+    fn position(&self) -> Result<Vec2<f64>, PortError>;
+
+    /// Returns the clockwise heading, with zero facing the positive y-axis.
+    fn heading(&self) -> Result<QAngle, PortError>;
+
+    /// Returns the sensor's estimate of its own position error.
+    fn error(&self) -> Result<QLength, PortError>;
+}
+
+impl Gps for GpsSensor {
+    fn position(&self) -> Result<Vec2<f64>, PortError> {
+        GpsSensor::position(self).map(|position| Vec2::new(position.x, position.y))
+    }
+
+    fn heading(&self) -> Result<QAngle, PortError> {
+        GpsSensor::heading(self).map(QAngle::from)
+    }
+
+    fn error(&self) -> Result<QLength, PortError> {
+        GpsSensor::error(self).map(QLength::from_meters)
+    }
+}
@@ -0,0 +1,70 @@
+//! Heading source abstraction.
+//!
+//! This module defines the [`Gyro`] trait used by [`TrackingRig`](crate::TrackingRig)
+//! and [`OdomChassis`](crate::OdomChassis) to read the robot's heading.
+//!
+//! The V5 [`InertialSensor`] is the default implementation.
+//!
+//! # Conventions
+//!
+//! Values follow the V5 inertial sensor: heading is measured clockwise in
+//! degrees and wraps to `[0, 360)`, and the yaw rate is the raw gyroscope
+//! z-axis rate in degrees per second. The z-axis points up out of the sensor,
+//! so a positive rate is a counter-clockwise rotation. Acceleration is the
+//! magnitude in the sensor's x-y plane, which is horizontal when the sensor is
+//! mounted flat.
+
+use crate::util::si::QAngle;
+use std::rc::Rc;
+use vexide::smart::PortError;
+use vexide::smart::imu::{InertialError, InertialSensor};
+
+/// A sensor that reports the robot's heading and yaw rate.
+pub trait Gyro {
+    /// Returns the clockwise heading, wrapped to `[0, 360)` degrees.
+    fn heading(&self) -> Result<QAngle, InertialError>;
+
+    /// Returns the gyroscope z-axis rate in degrees per second (counter-clockwise positive).
+    fn gyro_rate(&self) -> Result<f64, PortError>;
+
+    /// Returns the magnitude of the horizontal acceleration in m/s².
+    ///
+    /// Sensors without an accelerometer report zero.
+    fn acceleration(&self) -> Result<f64, PortError> {
+        Ok(0.0)
+    }
+}
+
+/// Standard gravity, the V5 inertial sensor's acceleration unit (m/s²).
+const STANDARD_GRAVITY: f64 = 9.80665;
+
+impl Gyro for InertialSensor {
+    fn heading(&self) -> Result<QAngle, InertialError> {
+        InertialSensor::heading(self).map(QAngle::from)
+    }
+
+    fn gyro_rate(&self) -> Result<f64, PortError> {
+        InertialSensor::gyro_rate(self).map(|rate| rate.z)
+    }
+
+    fn acceleration(&self) -> Result<f64, PortError> {
+        InertialSensor::acceleration(self)
+            .map(|accel| libm::hypot(accel.x, accel.y) * STANDARD_GRAVITY)
+    }
+}
+
+/// Shares one sensor, for example an [`ImuFusion`](crate::odom::heading::ImuFusion)
+/// whose status is read while a [`TrackingRig`](crate::TrackingRig) uses it.
+impl<G: Gyro + ?Sized> Gyro for Rc<G> {
+    fn heading(&self) -> Result<QAngle, InertialError> {
+        G::heading(self)
+    }
+
+    fn gyro_rate(&self) -> Result<f64, PortError> {
+        G::gyro_rate(self)
+    }
+
+    fn acceleration(&self) -> Result<f64, PortError> {
+        G::acceleration(self)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod distance;
+pub mod encoder;
+pub mod gps;
+pub mod imu;
+pub mod motor;
@@ -0,0 +1,94 @@
+//! Motor output abstraction.
+//!
+//! This module defines the [`MotorOutput`] trait, the interface that
+//! [`MotorGroup`](crate::MotorGroup) and [`DifferentialDrive`](crate::DifferentialDrive)
+//! use to command motors and read back their integrated encoders.
+//!
+//! The V5 smart [`Motor`] is the default implementation. Any other type that
+//! implements the trait (for example a simulated or mock motor) can be used in
+//! its place, which allows drivetrain and chassis code to run off-robot.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use kernelvex::MotorOutput;
+//!
+//! struct MockMotor {
+//!     volts: f64,
+//! }
+//!
+//! impl MotorOutput for MockMotor {
+//!     fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+//!         self.volts = volts;
+//!         Ok(())
+//!     }
+//!     // ...
+//! }
+//! ```
+
+use crate::util::si::QAngle;
+use vexide::smart::PortError;
+use vexide::smart::motor::{BrakeMode, Motor};
+
+/// A device that accepts voltage commands and reports its shaft state.
+///
+/// All methods mirror the V5 smart motor API. Velocities are reported in RPM
+/// and positions as the total rotation of the motor shaft (after the internal
+/// cartridge gearing).
+pub trait MotorOutput {
+    /// Sets the voltage applied to the motor.
+    ///
+    /// # Arguments
+    ///
+    /// * `volts` - Voltage to apply (typically -12.0 to 12.0)
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError>;
+
+    /// Stops the motor using the given brake mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The brake mode (Coast, Brake, or Hold)
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError>;
+
+    /// Returns the current velocity in RPM.
+    fn velocity(&self) -> Result<f64, PortError>;
+
+    /// Returns the total rotation of the motor shaft.
+    fn position(&self) -> Result<QAngle, PortError>;
+
+    /// Overwrites the encoder position with the given angle.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The angle to set the encoder position to
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError>;
+
+    /// Resets the encoder position to zero.
+    fn reset_position(&mut self) -> Result<(), PortError>;
+}
+
+impl MotorOutput for Motor {
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+        Motor::set_voltage(self, volts)
+    }
+
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError> {
+        Motor::brake(self, mode)
+    }
+
+    fn velocity(&self) -> Result<f64, PortError> {
+        Motor::velocity(self)
+    }
+
+    fn position(&self) -> Result<QAngle, PortError> {
+        Motor::position(self).map(QAngle::from)
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        Motor::set_position(self, position.into())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        Motor::reset_position(self)
+    }
+}
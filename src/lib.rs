@@ -9,6 +9,9 @@
 //! ## Features
 //!
 //! - **Odometry**: Track robot position using [`TrackingRig`] with wheel encoders and IMU
+//! - **Sensor Fusion**: [`EkfRig`] fuses odometry with GPS and distance sensor corrections
+//! - **Localization**: [`Mcl`] corrects odometry drift with distance sensors against a field map,
+//!   and [`GpsRig`] uses the V5 GPS sensor as an absolute pose source
 //! - **Drivetrains**: [`DifferentialDrive`] with tank, arcade, and curvature control
 //! - **Motion Profiles**: [`TrapezoidalConstraints`] for smooth acceleration
 //! - **Trajectory Following**: [`PurePursuit`] and [`RamseteController`] for path tracking
@@ -45,24 +48,46 @@
 //!
 //! | Module | Description |
 //! |--------|-------------|
-//! | [`control`] | PID controllers, feedforward, RAMSETE, pure pursuit |
+//! | [`control`] | PID controllers, feedforward, RAMSETE, pure pursuit, boomerang, motion exit conditions |
 //! | [`dt`] | Drivetrain models and motor groups |
-//! | [`motion`] | Motion profiles and trajectories |
-//! | [`odom`] | Odometry, pose estimation, tracking wheels |
+//! | [`hal`] | Device traits for motors, encoders, heading, distance and GPS sensors |
+//! | [`motion`] | Motion profiles, trajectories, field frames and alliance mirroring |
+//! | [`odom`] | Odometry, calibration, pose history, IMU fusion, contact detection, tracking wheel health, Kalman filter, particle filter and GPS localization, velocity filtering, tracking wheels, motor encoder odometry, background motions, autonomous routines |
+//! | `sim` | Drivetrain physics simulator and simulated devices, behind the `sim` feature |
 //! | [`util`] | Type-safe units, logging, solenoid groups |
 
-pub use odom::chassis::{DriveError, OdomChassis};
+pub use odom::calibrate::Calibrator;
+pub use odom::chassis::{DriveError, OdomChassis, TurnDirection};
+pub use odom::contact::{ContactConfig, ContactEvent, ContactKind, ContactState};
 pub use odom::wheel::{OmniWheel, TrackingRig, TrackingWheel};
+pub use odom::ekf::{Ekf, EkfConfig, EkfRig, Measurement};
+pub use odom::gps::{GpsConfig, GpsReader, GpsRig};
+pub use odom::health::{TravelSource, WheelHealth, WheelHealthConfig, WheelStatus};
+pub use odom::heading::{ImuFusion, ImuFusionConfig, ImuStatus};
+pub use odom::history::{PoseHistory, PoseSample};
+pub use odom::ime::ImeRig;
+pub use odom::mcl::{Mcl, MclConfig, ParticleFilter};
+pub use odom::motion::{BackgroundChassis, MotionHandle};
+pub use odom::routine::{
+    ErrorPolicy, Routine, RoutineReport, Step, StepError, StepOutcome, StepReport,
+};
+pub use odom::source::PoseSource;
+pub use odom::velocity::{VelocityEstimator, VelocityFilter};
 
 pub use control::ramsete::{RamseteController, RamseteReference};
+pub use motion::field::{Alliance, Axis, FieldFrame, FieldSymmetry, FieldTransform, FieldUnits};
 pub use motion::profile::TrapezoidalConstraints;
 pub use motion::trajectory::{Trajectory, TrajectoryPoint};
-pub use odom::{pose::Pose, wheel::*};
+pub use odom::{pose::{Pose, Twist}, wheel::*};
 pub use util::controller::*;
 
 pub use util::si::{QAngle, QLength, QTime};
 
 pub use dt::model::*;
+pub use hal::{
+    distance::RangeFinder, encoder::RotaryEncoder, gps::Gps, imu::Gyro, motor::MotorOutput,
+};
+pub use util::clock::{Clock, LoopTiming, ManualClock, SystemClock};
 pub use util::solenoidgroup::SolenoidGroup;
 
 pub use dt::differential::DifferentialDrive;
@@ -70,6 +95,7 @@ pub use dt::motorgroup::MotorGroup;
 
 pub mod control;
 pub mod dt;
+pub mod hal;
 
 pub use util::logger::Logger;
 pub use util::{si::*, utils::*};
@@ -78,6 +104,11 @@ pub mod odom;
 
 pub mod motion;
 
+#[cfg(feature = "sim")]
+pub mod sim;
+
+pub use control::boomerang::{Boomerang, DriveDirection, MoveParams};
+pub use control::exit::{ExitConditions, ExitReason, ExitTracker};
 pub use control::pid::{AngularPid, Pid};
 
 pub use control::purepursuit::PurePursuit;
@@ -0,0 +1,294 @@
+//! Field frames and alliance mirroring.
+//!
+//! Poses in this crate are in metres from the centre of the field, with +x
+//! towards the right-hand wall and +y away from the driver. [`FieldFrame`]
+//! converts points between that frame and the conventions autonomous routes
+//! are often written in:
+//!
+//! | Units | Origin | Scale |
+//! |-------|--------|-------|
+//! | [`Centered`](FieldUnits::Centered) | Field centre | Metres |
+//! | [`CornerInches`](FieldUnits::CornerInches) | Bottom-left corner | Inches |
+//! | [`Tiles`](FieldUnits::Tiles) | Bottom-left corner | Foam tiles |
+//!
+//! The same route usually runs on both alliances, reflected or rotated across
+//! the field. [`FieldTransform`] mirrors a [`Pose`], [`TrajectoryPoint`],
+//! [`Trajectory`] or [`PurePursuit`] path, flipping headings and angular
+//! velocities so the result can be driven as is, and
+//! [`FieldFrame::for_alliance`] applies the field's symmetry when the robot is
+//! on the other alliance.
+//!
+//! # Example
+//!
+//! ```
+//! use kernelvex::{Alliance, FieldFrame, FieldUnits, Pose, QAngle, Vec2};
+//!
+//! let field = FieldFrame::v5();
+//!
+//! // A route written for red, starting one tile in from the left wall.
+//! let start = field.pose(Vec2::new(1.0, 3.0), QAngle::from_degrees(0.0), FieldUnits::Tiles);
+//! assert!((start.position().x + 1.2192).abs() < 1e-9);
+//!
+//! // On blue, the robot starts on the other side facing the other way.
+//! let blue = field.for_alliance(&start, Alliance::Red, Alliance::Blue);
+//! assert!((blue.position().x - 1.2192).abs() < 1e-9);
+//! assert!((blue.heading().as_degrees() - 180.0).abs() < 1e-9);
+//! ```
+
+use crate::control::purepursuit::PurePursuit;
+use crate::motion::trajectory::{Trajectory, TrajectoryPoint};
+use crate::odom::pose::Pose;
+use crate::util::si::{QAngle, QLength, Vec2};
+
+/// A line through the field centre to mirror across.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// The x axis (`y = 0`): y and headings change sign.
+    X,
+    /// The y axis (`x = 0`): x changes sign and headings point the other way
+    /// along x.
+    Y,
+}
+
+/// How one alliance's half of the field maps onto the other's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSymmetry {
+    /// The halves are reflections across an axis.
+    Mirror(Axis),
+    /// The halves are a half turn about the field centre apart.
+    Rotate,
+}
+
+/// An alliance colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alliance {
+    /// The red alliance
+    Red,
+    /// The blue alliance
+    Blue,
+}
+
+/// A convention for writing field coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldUnits {
+    /// Metres from the field centre, the frame poses use.
+    Centered,
+    /// Inches from the bottom-left corner.
+    CornerInches,
+    /// Foam tiles from the bottom-left corner.
+    Tiles,
+}
+
+/// Field dimensions and symmetry.
+///
+/// Use the `with_*` builder methods to describe a non-standard field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldFrame {
+    /// Extent along x
+    pub width: QLength,
+    /// Extent along y
+    pub height: QLength,
+    /// Side length of one foam tile
+    pub tile: QLength,
+    /// How the alliances' halves map onto each other
+    pub symmetry: FieldSymmetry,
+}
+
+impl FieldFrame {
+    /// Creates a V5 competition field: 12 ft square, 2 ft tiles, with the
+    /// alliances mirrored across the y axis.
+    pub const fn v5() -> Self {
+        Self {
+            width: QLength::from_inches(144.0),
+            height: QLength::from_inches(144.0),
+            tile: QLength::from_inches(24.0),
+            symmetry: FieldSymmetry::Mirror(Axis::Y),
+        }
+    }
+
+    /// Sets the field's extent.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Extent along x
+    /// * `height` - Extent along y
+    pub const fn with_size(mut self, width: QLength, height: QLength) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the side length of one tile.
+    pub const fn with_tile(mut self, tile: QLength) -> Self {
+        self.tile = tile;
+        self
+    }
+
+    /// Sets how the alliances' halves map onto each other.
+    pub const fn with_symmetry(mut self, symmetry: FieldSymmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Returns where a convention's origin lies and the length of its unit,
+    /// both in metres.
+    fn origin_and_scale(&self, units: FieldUnits) -> (Vec2<f64>, f64) {
+        let corner = Vec2::new(
+            -self.width.as_meters() / 2.0,
+            -self.height.as_meters() / 2.0,
+        );
+        match units {
+            FieldUnits::Centered => (Vec2::new(0.0, 0.0), 1.0),
+            FieldUnits::CornerInches => (corner, QLength::from_inches(1.0).as_meters()),
+            FieldUnits::Tiles => (corner, self.tile.as_meters()),
+        }
+    }
+
+    /// Converts a point to metres from the field centre.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point, written in `units`
+    /// * `units` - The convention `point` is written in
+    pub fn to_centered(&self, point: Vec2<f64>, units: FieldUnits) -> Vec2<f64> {
+        let (origin, scale) = self.origin_and_scale(units);
+        origin + point * scale
+    }
+
+    /// Converts a point from metres from the field centre.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point in metres from the field centre
+    /// * `units` - The convention to write it in
+    pub fn from_centered(&self, point: Vec2<f64>, units: FieldUnits) -> Vec2<f64> {
+        let (origin, scale) = self.origin_and_scale(units);
+        (point - origin) / scale
+    }
+
+    /// Converts a point between two conventions.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point, written in `from`
+    /// * `from` - The convention `point` is written in
+    /// * `to` - The convention to write it in
+    pub fn convert(&self, point: Vec2<f64>, from: FieldUnits, to: FieldUnits) -> Vec2<f64> {
+        self.from_centered(self.to_centered(point, from), to)
+    }
+
+    /// Creates a pose from a position written in any convention.
+    ///
+    /// Every convention shares the same axes, so the heading is used as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position, written in `units`
+    /// * `heading` - Counter-clockwise heading from +x
+    /// * `units` - The convention `position` is written in
+    pub fn pose(&self, position: Vec2<f64>, heading: QAngle, units: FieldUnits) -> Pose {
+        Pose::new(self.to_centered(position, units), heading)
+    }
+
+    /// Moves a value written for one alliance onto the other alliance's half
+    /// of the field, using the field's symmetry.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The pose, trajectory or path
+    /// * `written_for` - The alliance `value` was written for
+    /// * `alliance` - The alliance the robot is on
+    ///
+    /// # Returns
+    ///
+    /// `value` unchanged if the alliances match, otherwise its mirror image.
+    pub fn for_alliance<T: FieldTransform + Clone>(
+        &self,
+        value: &T,
+        written_for: Alliance,
+        alliance: Alliance,
+    ) -> T {
+        if written_for == alliance {
+            return value.clone();
+        }
+        match self.symmetry {
+            FieldSymmetry::Mirror(axis) => value.mirror(axis),
+            FieldSymmetry::Rotate => value.rotate_half(),
+        }
+    }
+}
+
+impl Default for FieldFrame {
+    fn default() -> Self {
+        Self::v5()
+    }
+}
+
+/// Values that can be reflected or turned about the field centre.
+///
+/// The result is a value the robot can drive: headings are flipped along
+/// with positions, and a reflection reverses the direction of every turn.
+pub trait FieldTransform: Sized {
+    /// Reflects the value across an axis through the field centre.
+    fn mirror(&self, axis: Axis) -> Self;
+
+    /// Turns the value a half turn about the field centre.
+    fn rotate_half(&self) -> Self;
+}
+
+impl FieldTransform for Pose {
+    fn mirror(&self, axis: Axis) -> Self {
+        let position = self.position();
+        match axis {
+            Axis::X => Pose::new(Vec2::new(position.x, -position.y), -self.heading()),
+            Axis::Y => Pose::new(
+                Vec2::new(-position.x, position.y),
+                QAngle::PI - self.heading(),
+            ),
+        }
+    }
+
+    fn rotate_half(&self) -> Self {
+        Pose::new(-self.position(), self.heading() + QAngle::PI)
+    }
+}
+
+impl FieldTransform for TrajectoryPoint {
+    fn mirror(&self, axis: Axis) -> Self {
+        TrajectoryPoint::new(
+            self.pose.mirror(axis),
+            self.linear_velocity,
+            -self.angular_velocity,
+            self.time,
+        )
+    }
+
+    fn rotate_half(&self) -> Self {
+        TrajectoryPoint::new(
+            self.pose.rotate_half(),
+            self.linear_velocity,
+            self.angular_velocity,
+            self.time,
+        )
+    }
+}
+
+impl FieldTransform for Trajectory {
+    fn mirror(&self, axis: Axis) -> Self {
+        Trajectory::from_points(self.points().iter().map(|p| p.mirror(axis)).collect())
+    }
+
+    fn rotate_half(&self) -> Self {
+        Trajectory::from_points(self.points().iter().map(|p| p.rotate_half()).collect())
+    }
+}
+
+impl FieldTransform for PurePursuit {
+    fn mirror(&self, axis: Axis) -> Self {
+        PurePursuit::new(self.trajectory().mirror(axis), self.lookahead())
+    }
+
+    fn rotate_half(&self) -> Self {
+        PurePursuit::new(self.trajectory().rotate_half(), self.lookahead())
+    }
+}
@@ -11,6 +11,10 @@
 //! 2. **Cruise**: Maintain max velocity (may be skipped for short distances)
 //! 3. **Deceleration**: Ramp down from max velocity to zero
 //!
+//! [`TrapezoidalConstraints::generate_profile_between`] starts and ends the
+//! profile at given velocities instead, so chained motions need not stop
+//! between moves.
+//!
 //! ```text
 //! Velocity
 //!    ^
@@ -116,6 +120,16 @@ impl TrapezoidalConstraints {
         self
     }
 
+    /// Returns whether the constraints can produce a profile.
+    ///
+    /// Both limits must be positive and finite; with a zero limit the robot
+    /// could never reach its target.
+    pub fn is_valid(&self) -> bool {
+        [self.max_velocity, self.max_acceleration]
+            .iter()
+            .all(|limit| limit.is_finite() && *limit > 0.0)
+    }
+
     /// Generates a trapezoidal motion profile for a given distance.
     ///
     /// Returns a sequence of 100 [`MotionState`] points representing the
@@ -145,83 +159,105 @@ impl TrapezoidalConstraints {
     /// }
     /// ```
     pub fn generate_profile(&self, total_distance: QLength) -> Vec<MotionState> {
-        let distance = total_distance.as_meters();
+        self.generate_profile_between(total_distance, 0.0, 0.0)
+    }
+
+    /// Generates a trapezoidal motion profile that starts and ends moving.
+    ///
+    /// Chained motions use this to carry speed from one motion into the
+    /// next instead of stopping in between. Velocities are signed like
+    /// `total_distance`, and only their component in the direction of travel
+    /// counts: a robot moving backwards at the start of a forward profile
+    /// starts it from rest. Both are capped at the maximum velocity.
+    ///
+    /// If the distance is too short to reach `end_velocity` from
+    /// `start_velocity`, the profile accelerates or decelerates the whole way
+    /// and ends at the closest velocity it can reach.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_distance` - The total distance to travel (positive = forward, negative = backward)
+    /// * `start_velocity` - The velocity at the start of the profile (m/s)
+    /// * `end_velocity` - The velocity at the end of the profile (m/s)
+    ///
+    /// # Returns
+    ///
+    /// A vector of 100 motion states sampled evenly across the profile
+    /// duration.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Enter a 1 m drive at 0.5 m/s and leave it at 0.8 m/s.
+    /// let profile = constraints.generate_profile_between(QLength::from_meters(1.0), 0.5, 0.8);
+    /// ```
+    pub fn generate_profile_between(
+        &self,
+        total_distance: QLength,
+        start_velocity: f64,
+        end_velocity: f64,
+    ) -> Vec<MotionState> {
+        let sign = if total_distance.as_meters() < 0.0 { -1.0 } else { 1.0 };
+        let distance = total_distance.as_meters().abs();
         let max_v = self.max_velocity;
         let max_a = self.max_acceleration;
-        let t_accel = max_v / max_a;
-        let d_accel = 0.5 * max_a * t_accel.powi(2); // or max_v^2 / (2*max_a)
-        let d_cruise = distance - 2.0 * d_accel;
-        // no cruise
-        if d_cruise < 0.0 {
-            let v_peak_tri = sqrt(distance * max_a);
-            let t_accel_only = v_peak_tri / max_a;
-            let t_total = 2.0 * t_accel_only;
-            let d_half = 0.5 * distance;
-            let samples = 100;
-            let dt = t_total / (samples as f64 - 1.0);
-            let mut states = Vec::with_capacity(samples);
-            for i in 0..samples {
-                let t = dt * i as f64;
-                let (velocity, acceleration, position) = if t <= t_accel_only {
-                    let v = max_a * t;
-                    let a = max_a;
-                    let p = 0.5 * max_a * t.powi(2);
-                    (v, a, p)
-                } else {
-                    // Decelerating phase
-                    let t_dec = t - t_accel_only;
-                    let v = v_peak_tri - max_a * t_dec;
-                    let a = -max_a;
-                    let p_dec = v_peak_tri * t_dec - 0.5 * max_a * t_dec.powi(2);
-                    (v, a, d_half + p_dec)
-                };
-                states.push(MotionState {
-                    time: QTime::from_sec(t),
-                    position: QLength::from_meters(position),
-                    velocity,
-                    acceleration,
-                });
-            }
-            states
-        } else {
-            // Case 2: Full Trapezoidal profile (with cruise phase)
-            let t_cruise = d_cruise / max_v;
-            let t_total = 2.0 * t_accel + t_cruise;
-            let samples = 100; // Fixed sampling rate
-            let dt = t_total / (samples as f64 - 1.0);
-            let mut states = Vec::with_capacity(samples);
-            for i in 0..samples {
-                let t = dt * i as f64;
-                let (velocity, acceleration, position) = if t < t_accel {
-                    // Accelerating
-                    let v = max_a * t;
-                    let a = max_a;
-                    let p = 0.5 * max_a * t.powi(2);
-                    (v, a, p)
-                } else if t < t_accel + t_cruise {
-                    // Cruising
-                    let t_cruise_elapsed = t - t_accel;
-                    let v = max_v;
-                    let a = 0.0;
-                    let p = d_accel + max_v * t_cruise_elapsed;
-                    (v, a, p)
-                } else {
-                    // Decelerating
-                    let t_dec_elapsed = t - (t_accel + t_cruise);
-                    let v = max_v - max_a * t_dec_elapsed;
-                    let a = -max_a;
-                    let p_accel_cruise = d_accel + max_v * t_cruise;
-                    let p_dec = max_v * t_dec_elapsed - 0.5 * max_a * t_dec_elapsed.powi(2);
-                    (v, a, p_accel_cruise + p_dec)
-                };
-                states.push(MotionState {
-                    time: QTime::from_sec(t),
-                    position: QLength::from_meters(position),
-                    velocity,
-                    acceleration,
-                });
-            }
-            states
+
+        let v_start = (start_velocity * sign).clamp(0.0, max_v);
+        // Fastest and slowest speeds reachable by the end of the distance
+        let v_reachable_hi = sqrt(v_start.powi(2) + 2.0 * max_a * distance);
+        let v_reachable_lo = sqrt((v_start.powi(2) - 2.0 * max_a * distance).max(0.0));
+        let v_end = (end_velocity * sign)
+            .clamp(0.0, max_v)
+            .clamp(v_reachable_lo, v_reachable_hi);
+
+        // Peak velocity where the acceleration and deceleration ramps meet,
+        // capped at the cruise velocity.
+        let v_peak = sqrt(max_a * distance + 0.5 * (v_start.powi(2) + v_end.powi(2)))
+            .min(max_v)
+            .max(v_start.max(v_end));
+        let t_accel = (v_peak - v_start) / max_a;
+        let d_accel = (v_peak.powi(2) - v_start.powi(2)) / (2.0 * max_a);
+        let t_decel = (v_peak - v_end) / max_a;
+        let d_decel = (v_peak.powi(2) - v_end.powi(2)) / (2.0 * max_a);
+        let d_cruise = (distance - d_accel - d_decel).max(0.0);
+        let t_cruise = if v_peak > 0.0 { d_cruise / v_peak } else { 0.0 };
+        let t_total = t_accel + t_cruise + t_decel;
+
+        let samples = 100; // Fixed sampling rate
+        let dt = t_total / (samples as f64 - 1.0);
+        let mut states = Vec::with_capacity(samples);
+        for i in 0..samples {
+            let t = dt * i as f64;
+            let (velocity, acceleration, position) = if t < t_accel {
+                // Accelerating
+                let v = v_start + max_a * t;
+                let p = v_start * t + 0.5 * max_a * t.powi(2);
+                (v, max_a, p)
+            } else if t < t_accel + t_cruise {
+                // Cruising
+                let t_cruise_elapsed = t - t_accel;
+                (v_peak, 0.0, d_accel + v_peak * t_cruise_elapsed)
+            } else {
+                // Decelerating
+                let t_dec_elapsed = (t - (t_accel + t_cruise)).min(t_decel);
+                let v = v_peak - max_a * t_dec_elapsed;
+                let p_dec = v_peak * t_dec_elapsed - 0.5 * max_a * t_dec_elapsed.powi(2);
+                let a = if t_decel > 0.0 { -max_a } else { 0.0 };
+                (v, a, d_accel + d_cruise + p_dec)
+            };
+            states.push(MotionState {
+                time: QTime::from_sec(t),
+                position: QLength::from_meters(sign * position),
+                velocity: sign * velocity,
+                acceleration: sign * acceleration,
+            });
         }
+        states
+    }
+}
+
+impl Default for TrapezoidalConstraints {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -257,7 +257,7 @@ impl Default for TrajectoryPoint {
     }
 }
 /// Linear interpolation between two values.
-fn lerp(a: f64, b: f64, t: f64) -> f64 {
+pub(crate) fn lerp(a: f64, b: f64, t: f64) -> f64 {
     a + (b - a) * t
 }
 
@@ -268,7 +268,7 @@ fn lerp_angle(a: QAngle, b: QAngle, t: f64) -> QAngle {
 }
 
 /// Interpolates between two poses.
-fn interpolate_pose(a: Pose, b: Pose, t: f64) -> Pose {
+pub(crate) fn interpolate_pose(a: Pose, b: Pose, t: f64) -> Pose {
     let (ax, ay) = (a.position().x, a.position().y);
     let (bx, by) = (b.position().x, b.position().y);
     let heading = lerp_angle(a.heading(), b.heading(), t);
@@ -453,9 +453,7 @@ impl Bezier {
         let q1 = (self.control2 - self.control1) * 3.0;
         let q2 = (self.end - self.control2) * 3.0;
 
-        let velocity = q0 * (u * u) + q1 * (2.0 * u * t) + q2 * (t * t);
-
-        velocity
+        q0 * (u * u) + q1 * (2.0 * u * t) + q2 * (t * t)
     }
 
     /// Converts the Bézier curve to a trajectory.
@@ -0,0 +1,378 @@
+//! Calibration routines for tracking wheels and the drivetrain.
+//!
+//! Odometry is only as good as the geometry it is given. Measuring tracking
+//! wheel offsets and the track width with a tape measure is slow and never
+//! quite matches the robot's effective geometry: tread compression, wheel scrub
+//! and flex all shift it. These routines measure the effective values instead.
+//!
+//! - [`Calibrator::turns`] spins the robot in place with the IMU as ground
+//!   truth and computes each wheel's effective offset and the drivetrain's
+//!   effective track width.
+//! - [`Calibrator::start_distance`] and [`DistanceRun::finish`] compare wheel
+//!   readings over a known straight distance and compute each wheel's
+//!   effective diameter. Field tiles are 24 inches, so driving from one tile
+//!   seam to another gives a known distance without measuring.
+//!
+//! Results are plain values: [`TrackingWheelOrientation`]s and
+//! [`OmniWheel::Custom`] sizes to pass to [`TrackingWheel::new`], and a track
+//! width and wheel size to pass to [`DifferentialDrive::new`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! let calibrator = Calibrator::new();
+//! let wheels = [&left_wheel, &right_wheel, &back_wheel];
+//!
+//! let turns = calibrator.turns(&mut dt, &imu, &wheels, 5).await?;
+//! println!("track width: {} in", turns.track_width.as_inches());
+//!
+//! let run = calibrator.start_distance(&dt, &wheels).await?;
+//! // Drive two tiles forward, seam to seam.
+//! let distance = run.finish(&dt, &wheels, QLength::from_inches(48.0)).await?;
+//!
+//! let left = TrackingWheel::new(encoder, distance.wheels[0], turns.wheels[0], None);
+//! ```
+
+use crate::control::exit::{ExitConditions, ExitTracker};
+use crate::dt::differential::DifferentialDrive;
+use crate::dt::model::Tank;
+use crate::hal::encoder::RotaryEncoder;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::odom::wheel::{OmniWheel, TrackingWheel};
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, QLength};
+use crate::util::utils::{GroupErrors, TrackingWheelOrientation};
+use core::f64::consts::TAU;
+use core::time::Duration;
+use vexide::smart::PortError;
+
+/// Smallest rotation or travel a calibration will divide by.
+const MIN_MOTION: f64 = 1e-3;
+
+/// Reasons a calibration could not produce a result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalibrationError {
+    /// A drive motor could not be commanded or read.
+    Drive(GroupErrors),
+    /// The IMU could not be read.
+    Imu,
+    /// A tracking wheel's encoder could not be read.
+    Wheel(PortError),
+    /// The robot did not turn or travel far enough to measure anything.
+    NoMotion,
+    /// The robot stopped turning, or ran out of time, before completing the
+    /// requested turns.
+    Stalled,
+}
+
+impl From<GroupErrors> for CalibrationError {
+    fn from(errors: GroupErrors) -> Self {
+        Self::Drive(errors)
+    }
+}
+
+impl From<PortError> for CalibrationError {
+    fn from(error: PortError) -> Self {
+        Self::Wheel(error)
+    }
+}
+
+/// Effective geometry measured by spinning in place.
+#[derive(Debug, Clone)]
+pub struct TurnCalibration {
+    /// Total rotation measured by the IMU, counter-clockwise positive
+    pub turned: QAngle,
+    /// Effective mounting of each wheel, in the order the wheels were given
+    pub wheels: Vec<TrackingWheelOrientation>,
+    /// Effective drivetrain track width for the drivetrain's configured wheel
+    pub track_width: QLength,
+}
+
+/// Effective wheel sizes measured over a known distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceCalibration {
+    /// Effective size of each tracking wheel, in the order the wheels were given
+    pub wheels: Vec<OmniWheel>,
+    /// Effective drive wheel size for the drivetrain's configured ratio
+    pub drive_wheel: OmniWheel,
+}
+
+/// Wheel readings at the start of a known-distance run.
+///
+/// Created by [`Calibrator::start_distance`].
+#[derive(Debug, Clone)]
+pub struct DistanceRun {
+    wheels: Vec<f64>,
+    drive: (f64, f64),
+}
+
+impl DistanceRun {
+    /// Computes effective wheel sizes once the robot has travelled the
+    /// known distance.
+    ///
+    /// The robot should travel in a straight line; the wheels must be the
+    /// same, in the same order, as those given to
+    /// [`start_distance`](Calibrator::start_distance).
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The drivetrain
+    /// * `wheels` - Tracking wheels to calibrate
+    /// * `distance` - Distance the robot actually travelled
+    ///
+    /// # Returns
+    ///
+    /// Effective sizes, or [`CalibrationError::NoMotion`] if the robot or any
+    /// wheel did not move.
+    pub async fn finish<M: MotorOutput, E: RotaryEncoder>(
+        self,
+        dt: &DifferentialDrive<M>,
+        wheels: &[&TrackingWheel<E>],
+        distance: QLength,
+    ) -> Result<DistanceCalibration, CalibrationError> {
+        let distance = distance.as_meters().abs();
+        if distance < MIN_MOTION {
+            return Err(CalibrationError::NoMotion);
+        }
+
+        let resize = |wheel: &OmniWheel, travel: f64| {
+            if travel.abs() < MIN_MOTION {
+                Err(CalibrationError::NoMotion)
+            } else {
+                Ok(OmniWheel::Custom(wheel.size() * (distance / travel.abs())))
+            }
+        };
+
+        let wheel_sizes = wheels
+            .iter()
+            .zip(self.wheels.iter())
+            .map(|(wheel, start)| resize(wheel.wheel(), wheel.distance()?.as_meters() - start))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (left, right) = dt.travel().await?;
+        let drive_travel =
+            (left.as_meters() - self.drive.0 + right.as_meters() - self.drive.1) / 2.0;
+
+        Ok(DistanceCalibration {
+            wheels: wheel_sizes,
+            drive_wheel: resize(dt.wheel(), drive_travel)?,
+        })
+    }
+}
+
+/// Runs calibration routines.
+///
+/// Use the `with_*` builder methods to adjust how the routines drive.
+#[derive(Debug, Clone, Copy)]
+pub struct Calibrator<C: Clock = SystemClock> {
+    power: f64,
+    settle: Duration,
+    stall: ExitConditions,
+    clock: C,
+}
+
+impl Calibrator {
+    /// Creates a calibrator that turns at 40% power, gives up once the robot
+    /// turns slower than 0.1 rad/s for half a second, and waits half a second
+    /// for the robot to stop.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock::new())
+    }
+}
+
+impl Default for Calibrator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock> Calibrator<C> {
+    /// Creates a calibrator whose routines wait on the given clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for the sampling loop and settle time
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            power: 0.4,
+            settle: Duration::from_millis(500),
+            stall: ExitConditions::new()
+                .with_velocity(0.1, Duration::from_millis(500))
+                .with_velocity_delay(Duration::from_secs(1)),
+            clock,
+        }
+    }
+
+    /// Sets the tank power used to turn, from 0.0 to 1.0.
+    ///
+    /// Slower turns scrub less and give more repeatable results.
+    pub fn with_power(mut self, power: f64) -> Self {
+        self.power = power.abs().min(1.0);
+        self
+    }
+
+    /// Sets how long to keep measuring after the motors stop.
+    pub fn with_settle_time(mut self, settle: Duration) -> Self {
+        self.settle = settle;
+        self
+    }
+
+    /// Sets when [`turns`](Self::turns) gives up on a robot that is not
+    /// turning.
+    ///
+    /// Only the velocity condition, in rad/s, and the timeout apply; meeting
+    /// either fails the calibration with [`CalibrationError::Stalled`].
+    pub fn with_stall(mut self, stall: ExitConditions) -> Self {
+        self.stall = stall;
+        self
+    }
+
+    /// Spins the robot counter-clockwise in place and measures its geometry.
+    ///
+    /// The IMU heading is the ground truth. Each wheel's effective offset is
+    /// the distance it rolled divided by the rotation, and the track width is
+    /// the difference between the drive sides divided by the rotation.
+    /// Several full turns keep errors at the start and end small compared to
+    /// the total rotation.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The drivetrain to turn with
+    /// * `imu` - Heading sensor
+    /// * `wheels` - Tracking wheels to calibrate
+    /// * `turns` - Number of full turns
+    ///
+    /// # Returns
+    ///
+    /// Effective geometry, or an error if a device could not be used or the
+    /// robot did not turn. The drivetrain is stopped before an error is
+    /// returned.
+    pub async fn turns<M, G, E>(
+        &self,
+        dt: &mut DifferentialDrive<M>,
+        imu: &G,
+        wheels: &[&TrackingWheel<E>],
+        turns: u32,
+    ) -> Result<TurnCalibration, CalibrationError>
+    where
+        M: MotorOutput + Send,
+        G: Gyro,
+        E: RotaryEncoder,
+    {
+        let target = TAU * turns as f64;
+        let start_wheels = wheel_travel(wheels)?;
+        let (start_left, start_right) = dt.travel().await?;
+
+        let mut previous = ccw_heading(imu)?;
+        let mut turned = 0.0;
+
+        dt.drive_tank(-self.power, self.power).await?;
+        let mut spin = Ok(());
+        let mut last = self.clock.now();
+        let mut stall = ExitTracker::new(self.stall, last);
+        while turned < target {
+            self.clock.sleep(Duration::from_millis(10)).await;
+            let heading = match ccw_heading(imu) {
+                Ok(heading) => heading,
+                Err(err) => {
+                    spin = Err(err);
+                    break;
+                }
+            };
+            let delta = libm::remainder(heading - previous, TAU);
+            turned += delta;
+            previous = heading;
+
+            // There is no target error to settle on, only progress.
+            let now = self.clock.now();
+            let elapsed = now.saturating_sub(last).as_secs_f64();
+            last = now;
+            if elapsed > 0.0 && stall.update(f64::INFINITY, delta / elapsed, now).is_some() {
+                spin = Err(CalibrationError::Stalled);
+                break;
+            }
+        }
+        // Stop before reporting a failed read or a stall, so the robot is not
+        // left spinning.
+        dt.drive_tank(0.0, 0.0).await?;
+        spin?;
+
+        // Keep integrating while the robot coasts to a stop.
+        let settle_start = self.clock.now();
+        while self.clock.now().saturating_sub(settle_start) < self.settle {
+            self.clock.sleep(Duration::from_millis(10)).await;
+            let heading = ccw_heading(imu)?;
+            turned += libm::remainder(heading - previous, TAU);
+            previous = heading;
+        }
+
+        if turned.abs() < MIN_MOTION {
+            return Err(CalibrationError::NoMotion);
+        }
+
+        let wheel_orientations = wheels
+            .iter()
+            .zip(start_wheels.iter())
+            .map(|(wheel, start)| {
+                let offset = QLength::from_meters((wheel.distance()?.as_meters() - start) / turned);
+                Ok(match wheel.direction() {
+                    TrackingWheelOrientation::Vertical(_) => {
+                        TrackingWheelOrientation::Vertical(offset)
+                    }
+                    TrackingWheelOrientation::Horizontal(_) => {
+                        TrackingWheelOrientation::Horizontal(offset)
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, CalibrationError>>()?;
+
+        let (left, right) = dt.travel().await?;
+        let spread = (right - start_right) - (left - start_left);
+
+        Ok(TurnCalibration {
+            turned: QAngle::from_radians(turned),
+            wheels: wheel_orientations,
+            track_width: QLength::from_meters(spread.as_meters() / turned),
+        })
+    }
+
+    /// Records wheel readings at the start of a known-distance run.
+    ///
+    /// Drive or push the robot straight a known distance, then call
+    /// [`DistanceRun::finish`] with that distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The drivetrain
+    /// * `wheels` - Tracking wheels to calibrate
+    pub async fn start_distance<M: MotorOutput, E: RotaryEncoder>(
+        &self,
+        dt: &DifferentialDrive<M>,
+        wheels: &[&TrackingWheel<E>],
+    ) -> Result<DistanceRun, CalibrationError> {
+        let (left, right) = dt.travel().await?;
+
+        Ok(DistanceRun {
+            wheels: wheel_travel(wheels)?,
+            drive: (left.as_meters(), right.as_meters()),
+        })
+    }
+}
+
+/// Reads the IMU heading as a counter-clockwise angle in radians.
+fn ccw_heading<G: Gyro>(imu: &G) -> Result<f64, CalibrationError> {
+    imu.heading()
+        .map(|heading| -heading.as_radians())
+        .map_err(|_| CalibrationError::Imu)
+}
+
+/// Reads the travel of each wheel in meters.
+fn wheel_travel<E: RotaryEncoder>(
+    wheels: &[&TrackingWheel<E>],
+) -> Result<Vec<f64>, CalibrationError> {
+    wheels
+        .iter()
+        .map(|wheel| Ok(wheel.distance()?.as_meters()))
+        .collect()
+}
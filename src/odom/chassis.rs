@@ -8,7 +8,8 @@
 //!
 //! `OdomChassis` unifies all the components needed for precise autonomous robot control:
 //! - **Drivetrain**: Controls motor outputs via [`DifferentialDrive`]
-//! - **Odometry**: Tracks robot position using either a [`TrackingRig`] or IME fallback
+//! - **Odometry**: Tracks robot position using a [`TrackingRig`], another
+//!   [`PoseSource`], or the drive motor encoders through an [`ImeRig`]
 //! - **Motion Profiles**: Generates smooth velocity profiles via [`TrapezoidalConstraints`]
 //! - **Trajectory Following**: Uses RAMSETE controller for curved path tracking
 //!
@@ -32,32 +33,139 @@
 //!
 //! // Drive to a specific pose (turn + shoot)
 //! chassis.shoot_to_pose(target_pose).await?;
+//!
+//! // Give up on a turn after a second
+//! let exit = ExitConditions::angular().with_timeout(Duration::from_secs(1));
+//! if chassis.turn_with(QAngle::from_degrees(180.0), exit).await?.is_timeout() {
+//!     println!("turn timed out");
+//! }
 //! ```
 
 use crate::FeedForward;
+use crate::control::boomerang::{Boomerang, DriveDirection, MoveParams};
+use crate::control::exit::{ExitConditions, ExitReason, ExitTracker};
 use crate::GroupErrors;
+use crate::hal::distance::RangeFinder;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
 use crate::PurePursuit;
 use crate::Tank;
 use crate::{AngularPid, Pid};
-use crate::{DifferentialDrive, Drivetrain, Pose, TrackingRig};
-use crate::{QAngle, QLength, QTime};
+use crate::odom::health::{TravelSource, WheelStatus};
+use crate::odom::ime::ImeRig;
+use crate::odom::motion::MotionState;
+use crate::odom::dist::{MountedSensor, Wall, WallReading, WallResetError, relocalize};
+use crate::odom::source::PoseSource;
+use crate::odom::wheel::HeadingSource;
+use crate::{DifferentialDrive, Drivetrain, Pose, Side, TrackingRig};
+use crate::{QAngle, QLength, QTime, Vec2};
 use crate::{RamseteController, RamseteReference};
+use crate::util::clock::{Clock, SystemClock};
 use crate::{Trajectory, TrapezoidalConstraints};
 use core::time::Duration;
-use vexide::smart::{imu::InertialSensor, motor::Motor};
-use vexide_async::time::sleep;
+use std::rc::Rc;
+use vexide::smart::imu::{InertialError, InertialSensor};
+use vexide::smart::motor::{BrakeMode, Motor};
+
+/// Speed (m/s) per metre of remaining distance while [`OdomChassis::shoot`]
+/// settles after its profile.
+const SETTLE_GAIN: f64 = 4.0;
+
+/// Distance (m) from the target within which [`OdomChassis::move_to_pose`]
+/// stops chasing the carrot and holds the target heading.
+const SETTLE_RADIUS: f64 = 0.15;
+
+/// How long the pose source may go without measuring the heading or forward
+/// travel before a motion fails.
+const SENSOR_GRACE: Duration = Duration::from_millis(100);
+
+/// Returns the speed (m/s) to approach a point `along` metres ahead, or
+/// behind if negative, within a motion's speed limits.
+fn approach_speed(along: f64, max_speed: f64, min_speed: f64) -> f64 {
+    let speed = (SETTLE_GAIN * along).clamp(-max_speed, max_speed);
+    if speed.abs() < min_speed {
+        min_speed.copysign(along)
+    } else {
+        speed
+    }
+}
+
+/// Moves `speed` toward `target`, limiting only speeding up to `step` so the
+/// robot can still slow down in time.
+fn ramp(speed: f64, target: f64, step: f64) -> f64 {
+    if target.abs() > speed.abs() {
+        target.clamp(speed - step, speed + step)
+    } else {
+        target
+    }
+}
+
+/// Returns `done / total` clamped to `[0, 1]`, or 1 for an empty motion.
+fn fraction(done: f64, total: f64) -> f64 {
+    if total == 0.0 {
+        1.0
+    } else {
+        (done / total).clamp(0.0, 1.0)
+    }
+}
 
 /// Unified error type for drive operations.
 ///
-/// Wraps motor-related errors that can occur during chassis movement commands.
+/// Chassis motions report every failure through this type instead of
+/// panicking, and stop the drivetrain before returning one.
+///
+/// A motion that runs out of time is not an error by default: it ends with
+/// `Ok(ExitReason::Timeout)`. `Timeout` is only returned when the caller opts
+/// in with [`ExitReason::ok_or_timeout`].
 ///
 /// # Variants
 ///
 /// * `Motor` - Contains a collection of motor port errors from the drivetrain
+/// * `Timeout` - A motion ran out of time before settling
+/// * `Imu` - The heading could not be measured
+/// * `TrackingWheel` - The robot's forward travel could not be measured
+/// * `DriveEncoders` - The robot's forward travel could not be measured by a
+///   chassis without tracking wheels
+/// * `InvalidConstraints` - The motion constraints cannot produce a profile
+/// * `Cancelled` - The motion was cancelled before it finished
 #[derive(Debug)]
 pub enum DriveError {
     /// Motor group encountered one or more port errors.
     Motor(GroupErrors),
+    /// The motion ran out of time before settling.
+    ///
+    /// Motions report timeouts as [`ExitReason::Timeout`] so a routine can
+    /// carry on; [`ExitReason::ok_or_timeout`] turns them into this error.
+    Timeout,
+    /// Neither the heading sensor nor a pair of parallel wheels could measure
+    /// the heading. Holds the heading sensor's error.
+    Imu(InertialError),
+    /// Neither the vertical tracking wheels nor the drive encoders could
+    /// measure the robot's forward travel. Holds the health of each tracking
+    /// wheel.
+    TrackingWheel(Vec<WheelStatus>),
+    /// The pose source has no tracking wheels, and the drive motor encoders
+    /// it measures travel with could not be read.
+    ///
+    /// This is how a chassis built without a tracking rig, which falls back
+    /// to [`ImeRig`], loses its odometry.
+    DriveEncoders,
+    /// The maximum velocity or acceleration is not positive.
+    InvalidConstraints,
+    /// The motion was cancelled before it finished.
+    Cancelled,
+}
+
+/// Which way a turn rotates toward its target heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TurnDirection {
+    /// Rotate whichever way is shorter.
+    #[default]
+    Shortest,
+    /// Rotate clockwise, even if that is the long way round.
+    Clockwise,
+    /// Rotate counter-clockwise, even if that is the long way round.
+    CounterClockwise,
 }
 
 /// A unified chassis controller with odometry, PID, feedforward, and trajectory support.
@@ -65,7 +173,7 @@ pub enum DriveError {
 /// `OdomChassis` provides a high-level API for autonomous robot movement, combining:
 /// - Differential drivetrain control
 /// - IMU-based heading measurement
-/// - Optional tracking rig for full pose estimation
+/// - Full pose estimation from a tracking rig or the drive motor encoders
 /// - Linear and angular PID controllers
 /// - Feedforward for velocity/acceleration compensation
 /// - RAMSETE controller for curved trajectory following
@@ -73,9 +181,81 @@ pub enum DriveError {
 ///
 /// # Velocity Feedback
 ///
-/// When a [`TrackingRig`] is provided, velocity feedback comes from the tracking wheels.
-/// Otherwise, the chassis falls back to Integrated Motor Encoder (IME) velocity estimation
-/// from the drivetrain motors.
+/// When a [`TrackingRig`] is provided, pose and velocity feedback come from the tracking
+/// wheels. Otherwise, the chassis runs an [`ImeRig`] that estimates them from the
+/// drivetrain's Integrated Motor Encoders (IMEs) and the IMU, so every motion, including
+/// [`pursuit`](Self::pursuit) and [`turn_to_pose`](Self::turn_to_pose), works without
+/// tracking wheels.
+///
+/// # Pose Sources
+///
+/// Any [`PoseSource`] can stand in for the tracking rig, for example an
+/// [`EkfRig`](crate::odom::ekf::EkfRig) that also fuses absolute measurements.
+/// Attach one with [`with_pose_source`](Self::with_pose_source).
+///
+/// # Devices
+///
+/// The chassis is generic over its motors (`M`) and heading sensor (`G`), which
+/// default to the V5 smart [`Motor`] and [`InertialSensor`]. Substituting other
+/// [`MotorOutput`] and [`Gyro`] implementations lets autonomous routines run
+/// against simulated hardware.
+///
+/// # Timing
+///
+/// Every control loop waits and measures time through a [`Clock`] (`C`),
+/// which defaults to [`SystemClock`]. [`with_clock`](Self::with_clock)
+/// substitutes another time source and moves the chassis PID controllers onto
+/// it, so a simulated autonomous routine can run on a
+/// [`ManualClock`](crate::util::clock::ManualClock).
+///
+/// # Exit Conditions
+///
+/// [`shoot`](Self::shoot) and [`turn`](Self::turn) end when the chassis's
+/// [`ExitConditions`] are met, by default [`ExitConditions::linear`] and
+/// [`ExitConditions::angular`]. Set other defaults with
+/// [`with_linear_exit`](Self::with_linear_exit) and
+/// [`with_angular_exit`](Self::with_angular_exit), or pass conditions for a
+/// single motion to the `*_with` variants. Every motion returns the
+/// [`ExitReason`] it ended with, so a motion cut short by its timeout can be
+/// told apart from one that settled.
+///
+/// # Swing and Arc Turns
+///
+/// Besides turning in place with [`turn`](Self::turn), the chassis can turn
+/// about one side with [`swing`](Self::swing), holding that side still with
+/// the brake mode set by [`with_swing_brake`](Self::with_swing_brake), or
+/// drive a circle of fixed radius with [`arc`](Self::arc). Both take the side
+/// the robot turns about and a [`TurnDirection`]; the direction decides
+/// whether the robot moves forwards or backwards around that side.
+///
+/// ```ignore
+/// // Pivot on the left wheels to face 90 degrees.
+/// chassis.swing(QAngle::from_degrees(90.0), Side::Left, TurnDirection::Shortest).await?;
+/// // Back around a 0.5 m circle on the right to face 0 degrees.
+/// let radius = QLength::from_meters(0.5);
+/// chassis.arc(QAngle::default(), radius, Side::Right, TurnDirection::CounterClockwise).await?;
+/// ```
+///
+/// # Chaining
+///
+/// Every motion stops the drivetrain when it ends, unless its exit conditions
+/// chain it into the next with [`ExitConditions::with_chain`]. A chained
+/// motion ends early, still moving, and reports [`ExitReason::Chained`]; the
+/// next motion starts from the robot's current velocity.
+///
+/// ```ignore
+/// let chain = ExitConditions::linear().with_chain(0.05, 0.6);
+/// chassis.shoot_with(QLength::from_meters(1.0), chain).await?;
+/// chassis.shoot(QLength::from_meters(0.5)).await?;
+/// ```
+///
+/// # Background Motions
+///
+/// Every motion runs to completion when awaited. To do something else while
+/// the robot moves, such as starting an intake partway to a goal, hand the
+/// chassis to a [`BackgroundChassis`](crate::odom::motion::BackgroundChassis),
+/// whose motions return a [`MotionHandle`](crate::odom::motion::MotionHandle)
+/// at once.
 ///
 /// # Builder Pattern
 ///
@@ -88,32 +268,43 @@ pub enum DriveError {
 ///     .with_ff(FeedForward::new().set_gains(0.1, 0.5, 0.01))
 ///     .with_constraints(TrapezoidalConstraints::new().set_gains(1.0, 2.0));
 /// ```
-pub struct OdomChassis {
+pub struct OdomChassis<M: MotorOutput = Motor, G: Gyro = InertialSensor, C: Clock = SystemClock> {
     /// The differential drivetrain for motor control.
-    dt: DifferentialDrive,
-    /// Inertial sensor for heading measurement.
-    imu: InertialSensor,
-    /// Optional tracking rig for full pose estimation.
-    tracking: Option<TrackingRig>,
-    /// Current pose estimate (used when no tracking rig is present).
-    pose: Pose,
+    dt: DifferentialDrive<M>,
+    /// Inertial sensor for heading measurement, shared with the built-in IME odometry.
+    imu: Rc<G>,
+    /// Pose source (a tracking rig, another estimator, or IME odometry).
+    tracking: Rc<dyn PoseSource>,
+    /// Whether `tracking` is the built-in IME odometry.
+    ime: bool,
     /// PID controller for linear (forward/backward) motion.
-    linear_pid: Pid,
+    linear_pid: Pid<C>,
     /// PID controller for left-side velocity during trajectory following.
-    left_pid: Pid,
+    left_pid: Pid<C>,
     /// PID controller for right-side velocity during trajectory following.
-    right_pid: Pid,
+    right_pid: Pid<C>,
     /// PID controller for angular (turning) motion.
-    angular_pid: AngularPid,
+    angular_pid: AngularPid<C>,
     /// Feedforward controller for velocity/acceleration compensation.
     ff: FeedForward,
     /// RAMSETE controller for curved trajectory following.
     ramsete: RamseteController,
     /// Motion profile constraints (max velocity and acceleration).
     constraints: TrapezoidalConstraints,
+    /// Default exit conditions for driving straight.
+    linear_exit: ExitConditions,
+    /// Default exit conditions for turning.
+    angular_exit: ExitConditions,
+    /// How the locked side holds still during a swing turn.
+    swing_brake: BrakeMode,
+    /// Progress and cancellation of the motion running in the background, if
+    /// any.
+    motion: Option<Rc<MotionState>>,
+    /// Time source for control loops and controller timing.
+    clock: C,
 }
 
-impl OdomChassis {
+impl<M: MotorOutput + Send + 'static, G: Gyro + 'static> OdomChassis<M, G> {
     /// Creates an `OdomChassis` with default configuration.
     ///
     /// All PID gains and feedforward constants are initialized to zero.
@@ -124,14 +315,15 @@ impl OdomChassis {
     /// * `dt` - The differential drivetrain to control
     /// * `imu` - Inertial sensor for heading measurement
     /// * `tracking` - Optional tracking rig for full pose estimation. If `None`,
-    ///   velocity feedback falls back to motor encoders (IME).
+    ///   pose is tracked from the drive motor encoders and the IMU with an
+    ///   [`ImeRig`], starting at the origin facing the IMU's heading.
     ///
     /// # Example
     ///
     /// ```ignore
     /// let chassis = OdomChassis::new(drivetrain, imu, Some(tracking_rig));
     /// ```
-    pub fn new(dt: DifferentialDrive, imu: InertialSensor, tracking: Option<TrackingRig>) -> Self {
+    pub fn new(dt: DifferentialDrive<M>, imu: G, tracking: Option<TrackingRig>) -> Self {
         Self::with_config(dt, imu, tracking)
     }
 
@@ -145,11 +337,20 @@ impl OdomChassis {
     /// * `dt` - The differential drivetrain to control
     /// * `imu` - Inertial sensor for heading measurement
     /// * `tracking` - Optional tracking rig for pose estimation
-    pub fn with_config(
-        dt: DifferentialDrive,
-        imu: InertialSensor,
-        tracking: Option<TrackingRig>,
-    ) -> Self {
+    pub fn with_config(dt: DifferentialDrive<M>, imu: G, tracking: Option<TrackingRig>) -> Self {
+        let imu = Rc::new(imu);
+        let ime = tracking.is_none();
+        let tracking: Rc<dyn PoseSource> = match tracking {
+            Some(rig) => Rc::new(rig),
+            None => {
+                let heading = imu
+                    .heading()
+                    .map(|heading| QAngle::from_radians(-heading.as_radians()))
+                    .unwrap_or_default();
+                let origin = Pose::new(Default::default(), heading);
+                Rc::new(ImeRig::new(origin, &dt, Some(Rc::clone(&imu))))
+            }
+        };
         let linear_pid =
             Pid::new().with_output_limits(-Motor::V5_MAX_VOLTAGE, Motor::V5_MAX_VOLTAGE);
         let left_pid = Pid::new().with_output_limits(-Motor::V5_MAX_VOLTAGE, Motor::V5_MAX_VOLTAGE);
@@ -164,7 +365,7 @@ impl OdomChassis {
             dt,
             imu,
             tracking,
-            pose: Default::default(),
+            ime,
             linear_pid,
             left_pid,
             right_pid,
@@ -172,8 +373,86 @@ impl OdomChassis {
             ff,
             ramsete,
             constraints: TrapezoidalConstraints::new(),
+            linear_exit: ExitConditions::linear(),
+            angular_exit: ExitConditions::angular(),
+            swing_brake: BrakeMode::Hold,
+            motion: None,
+            clock: SystemClock::new(),
         }
     }
+}
+
+impl<M: MotorOutput + Send, G: Gyro, C: Clock> OdomChassis<M, G, C> {
+    /// Sets the clock used by every control loop.
+    ///
+    /// The chassis PID controllers are moved onto the new clock, keeping their
+    /// gains and limits. Controllers set afterwards with the `with_*_pid`
+    /// methods are moved onto it as well. The built-in IME odometry, if in
+    /// use, is restarted on the new clock at its current pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The time source for sleeps and controller timing
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let clock = ManualClock::new();
+    /// let chassis = OdomChassis::new(dt, imu, tracking).with_clock(clock.clone());
+    /// ```
+    pub fn with_clock<D: Clock + 'static>(self, clock: D) -> OdomChassis<M, G, D>
+    where
+        M: 'static,
+        G: 'static,
+    {
+        let tracking: Rc<dyn PoseSource> = if self.ime {
+            Rc::new(ImeRig::with_clock(
+                self.tracking.pose(),
+                &self.dt,
+                Some(Rc::clone(&self.imu)),
+                clock.clone(),
+            ))
+        } else {
+            self.tracking
+        };
+
+        OdomChassis {
+            dt: self.dt,
+            imu: self.imu,
+            tracking,
+            ime: self.ime,
+            linear_pid: self.linear_pid.with_clock(clock.clone()),
+            left_pid: self.left_pid.with_clock(clock.clone()),
+            right_pid: self.right_pid.with_clock(clock.clone()),
+            angular_pid: self.angular_pid.with_clock(clock.clone()),
+            ff: self.ff,
+            ramsete: self.ramsete,
+            constraints: self.constraints,
+            linear_exit: self.linear_exit,
+            angular_exit: self.angular_exit,
+            swing_brake: self.swing_brake,
+            motion: self.motion,
+            clock,
+        }
+    }
+
+    /// Sets the pose source used for feedback, replacing any tracking rig.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The pose estimator to use, such as an
+    ///   [`EkfRig`](crate::odom::ekf::EkfRig)
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let chassis = OdomChassis::new(dt, imu, None).with_pose_source(ekf_rig);
+    /// ```
+    pub fn with_pose_source<P: PoseSource + 'static>(mut self, source: P) -> Self {
+        self.tracking = Rc::new(source);
+        self.ime = false;
+        self
+    }
 
     /// Sets the linear PID controller for straight-line motion.
     ///
@@ -183,8 +462,8 @@ impl OdomChassis {
     /// # Arguments
     ///
     /// * `pid` - The PID controller to use for linear motion
-    pub fn with_linear_pid(mut self, pid: Pid) -> Self {
-        self.linear_pid = pid;
+    pub fn with_linear_pid<D: Clock>(mut self, pid: Pid<D>) -> Self {
+        self.linear_pid = pid.with_clock(self.clock.clone());
         self
     }
 
@@ -196,8 +475,8 @@ impl OdomChassis {
     /// # Arguments
     ///
     /// * `pid` - The angular PID controller to use
-    pub fn with_angular_pid(mut self, pid: AngularPid) -> Self {
-        self.angular_pid = pid;
+    pub fn with_angular_pid<D: Clock>(mut self, pid: AngularPid<D>) -> Self {
+        self.angular_pid = pid.with_clock(self.clock.clone());
         self
     }
 
@@ -222,8 +501,8 @@ impl OdomChassis {
     /// # Arguments
     ///
     /// * `pid` - The PID controller for left-side velocity
-    pub fn with_left_pid(mut self, pid: Pid) -> Self {
-        self.left_pid = pid;
+    pub fn with_left_pid<D: Clock>(mut self, pid: Pid<D>) -> Self {
+        self.left_pid = pid.with_clock(self.clock.clone());
         self
     }
 
@@ -235,8 +514,8 @@ impl OdomChassis {
     /// # Arguments
     ///
     /// * `pid` - The PID controller for right-side velocity
-    pub fn with_right_pid(mut self, pid: Pid) -> Self {
-        self.right_pid = pid;
+    pub fn with_right_pid<D: Clock>(mut self, pid: Pid<D>) -> Self {
+        self.right_pid = pid.with_clock(self.clock.clone());
         self
     }
 
@@ -266,25 +545,65 @@ impl OdomChassis {
         self
     }
 
-    /// Returns the current heading from the IMU.
+    /// Sets the default exit conditions for [`shoot`](Self::shoot).
+    ///
+    /// Errors are in metres and velocities in m/s.
+    ///
+    /// # Arguments
+    ///
+    /// * `exit` - The conditions for ending a straight drive
+    pub fn with_linear_exit(mut self, exit: ExitConditions) -> Self {
+        self.linear_exit = exit;
+        self
+    }
+
+    /// Sets the default exit conditions for [`turn`](Self::turn).
     ///
-    /// If the IMU read fails, returns 0 radians.
+    /// Errors are in radians and velocities in rad/s.
+    ///
+    /// # Arguments
+    ///
+    /// * `exit` - The conditions for ending a turn
+    pub fn with_angular_exit(mut self, exit: ExitConditions) -> Self {
+        self.angular_exit = exit;
+        self
+    }
+
+    /// Sets how the locked side holds still during a
+    /// [`swing`](Self::swing), by default [`BrakeMode::Hold`].
+    ///
+    /// # Arguments
+    ///
+    /// * `brake` - The brake mode for the locked side's motors
+    pub fn with_swing_brake(mut self, brake: BrakeMode) -> Self {
+        self.swing_brake = brake;
+        self
+    }
+
+    /// Returns the current heading.
+    ///
+    /// Headings follow the [`Pose`] convention: counter-clockwise positive,
+    /// with zero facing the positive x-axis. The heading is taken from the pose
+    /// source.
     ///
     /// # Returns
     ///
     /// The current heading as a [`QAngle`]
     pub fn heading(&self) -> QAngle {
-        self.imu
-            .heading()
-            .map(|a| QAngle::from_radians(a.as_radians()))
-            .unwrap_or(QAngle::from_radians(0.0))
+        self.tracking.pose().heading()
     }
 
     /// Drives the robot straight for a specified distance using a trapezoidal motion profile.
     ///
     /// This method generates a trapezoidal velocity profile and executes it using
-    /// feedforward + PID control. Velocity feedback comes from the tracking rig if
-    /// available, otherwise from motor encoders (IME fallback).
+    /// feedforward + PID control. Velocity feedback comes from the pose source.
+    /// Once the profile ends, the chassis closes the remaining distance, measured
+    /// along the starting heading, until the default linear exit conditions are
+    /// met.
+    ///
+    /// The profile starts from the robot's current velocity, so a drive that
+    /// follows a chained motion carries on without stopping first. See
+    /// [`ExitConditions::with_chain`].
     ///
     /// # Arguments
     ///
@@ -292,8 +611,13 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Movement completed successfully
+    /// * `Ok(reason)` - Why the movement ended
     /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
     ///
     /// # Example
     ///
@@ -304,16 +628,64 @@ impl OdomChassis {
     /// // Drive backward 50 centimeters
     /// chassis.shoot(QLength::from_meters(-0.5)).await?;
     /// ```
-    pub async fn shoot(&mut self, distance: QLength) -> Result<(), DriveError> {
-        let constraints = TrapezoidalConstraints {
-            max_velocity: self.constraints.max_velocity,
-            max_acceleration: self.constraints.max_acceleration,
-        };
-        let profile = constraints.generate_profile(distance);
+    pub async fn shoot(&mut self, distance: QLength) -> Result<ExitReason, DriveError> {
+        self.shoot_with(distance, self.linear_exit).await
+    }
+
+    /// Drives the robot straight with exit conditions for this motion only.
+    ///
+    /// Only the timeout and chain range apply while the profile runs; the
+    /// other conditions are checked once it has ended. A chained drive plans
+    /// its profile to end at the chain speed and keeps at least that speed
+    /// until it is in range. See [`shoot`](Self::shoot).
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance to travel (positive = forward, negative = backward)
+    /// * `exit` - When to end the motion, with errors in metres
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the movement ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
+    pub async fn shoot_with(
+        &mut self,
+        distance: QLength,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        if !self.constraints.is_valid() {
+            return Err(DriveError::InvalidConstraints);
+        }
+        let exit_v = exit.exit_speed().copysign(distance.as_meters());
+        let profile = self.constraints.generate_profile_between(
+            distance,
+            self.tracking.linear_velocity(),
+            exit_v,
+        );
+        let start = self.tracking.pose();
+        let direction = Vec2::new(start.heading().cos(), start.heading().sin());
+        let travelled = |pose: Pose| (pose.position() - start.position()).dot(direction);
+        let mut tracker = ExitTracker::new(exit, self.clock.now());
+        let mut fault = None;
 
         self.linear_pid.reset();
 
         for window in profile.windows(2) {
+            let travelled = travelled(self.tracking.pose());
+            let chained = tracker.chained(distance.as_meters() - travelled);
+            if let Some(reason) = chained.or_else(|| tracker.timed_out(self.clock.now())) {
+                return self.finish(reason).await;
+            }
+            let progress = fraction(travelled, distance.as_meters());
+            if let Err(err) = self.checkpoint(progress, &mut fault) {
+                return self.abort(err).await;
+            }
+
             let current = &window[0];
             let next = &window[1];
 
@@ -321,37 +693,195 @@ impl OdomChassis {
             let dt = (next.time - current.time).as_sec().max(1e-3);
             let target_a = (next.velocity - current.velocity) / dt;
 
-            let measured_v = if let Some(tracking) = self.tracking.as_ref() {
-                tracking.linear_velocity()
-            } else {
-                self.dt.linear_velocity().await.unwrap_or(0.0)
-            };
+            self.drive_velocity(target_v, target_a).await?;
+            self.clock.sleep(Duration::from_secs_f64(dt)).await;
+        }
 
-            let volts_pid = self.linear_pid.calculate(target_v, measured_v);
-            let volts_ff = self.ff.calculate(target_v, target_a);
-            let volts = (volts_pid + volts_ff).clamp(-Motor::V5_MAX_VOLTAGE, Motor::V5_MAX_VOLTAGE);
+        // Close the remaining distance once the profile has ended.
+        let reason = loop {
+            let travelled = travelled(self.tracking.pose());
+            let error = distance.as_meters() - travelled;
+            let measured_v = self.tracking.linear_velocity();
+            if let Some(reason) = tracker.update(error, measured_v, self.clock.now()) {
+                break reason;
+            }
+            let progress = fraction(travelled, distance.as_meters());
+            if let Err(err) = self.checkpoint(progress, &mut fault) {
+                return self.abort(err).await;
+            }
+
+            let mut target_v = (SETTLE_GAIN * error).clamp(
+                -self.constraints.max_velocity,
+                self.constraints.max_velocity,
+            );
+            // A chained drive keeps its exit speed until it is in range.
+            if target_v.abs() < exit_v.abs() {
+                target_v = exit_v.abs().copysign(error);
+            }
+            self.drive_velocity(target_v, 0.0).await?;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
+
+        self.finish(reason).await
+    }
 
-            let fraction = volts / Motor::V5_MAX_VOLTAGE;
-            self.dt
-                .drive_tank(fraction, fraction)
-                .await
-                .map_err(DriveError::Motor)?;
+    /// Returns the voltage that tracks a forward velocity, from the linear
+    /// PID and feedforward.
+    fn linear_volts(&mut self, target_v: f64, target_a: f64) -> f64 {
+        let measured_v = self.tracking.linear_velocity();
 
-            sleep(Duration::from_secs_f64(dt)).await;
+        let volts_pid = self.linear_pid.calculate(target_v, measured_v);
+        let volts_ff = self.ff.calculate(target_v, target_a);
+        (volts_pid + volts_ff).clamp(-Motor::V5_MAX_VOLTAGE, Motor::V5_MAX_VOLTAGE)
+    }
+
+    /// Commands a forward velocity with the linear PID and feedforward while
+    /// the angular PID's output steers.
+    ///
+    /// Steering takes priority when the motors saturate.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_v` - Forward velocity in m/s
+    /// * `turn` - Angular PID output in volts, counter-clockwise positive
+    ///
+    /// A motor error stops the drivetrain before it is returned.
+    async fn drive_steered(&mut self, target_v: f64, turn: f64) -> Result<(), DriveError> {
+        let max = Motor::V5_MAX_VOLTAGE;
+        let turn = turn.clamp(-max, max);
+        let headroom = max - turn.abs();
+        let drive = self
+            .linear_volts(target_v, 0.0)
+            .clamp(-headroom, headroom);
+
+        if let Err(err) = self.dt.drive_tank((drive - turn) / max, (drive + turn) / max).await {
+            return self.abort(DriveError::Motor(err)).await;
         }
+        self.observe_drive().await;
+        Ok(())
+    }
 
-        self.dt
-            .drive_tank(0.0, 0.0)
-            .await
-            .map_err(DriveError::Motor)?;
+    /// Returns the fastest and slowest speeds a point or pose motion may
+    /// drive at, in m/s.
+    fn speed_limits(&self, params: MoveParams) -> (f64, f64) {
+        let max_speed = params
+            .max_speed
+            .map_or(self.constraints.max_velocity, f64::abs)
+            .min(self.constraints.max_velocity);
+        (max_speed, params.min_speed.abs().min(max_speed))
+    }
+
+    /// Commands a forward velocity with the linear PID and feedforward.
+    ///
+    /// A motor error stops the drivetrain before it is returned.
+    async fn drive_velocity(&mut self, target_v: f64, target_a: f64) -> Result<(), DriveError> {
+        let volts = self.linear_volts(target_v, target_a);
+
+        let fraction = volts / Motor::V5_MAX_VOLTAGE;
+        if let Err(err) = self.dt.drive_tank(fraction, fraction).await {
+            return self.abort(DriveError::Motor(err)).await;
+        }
+
+        self.observe_drive().await;
         Ok(())
     }
 
+    /// Stops the drivetrain.
+    ///
+    /// Both sides are stopped even if one of them fails, so a dead motor
+    /// never leaves the other side driving.
+    pub(crate) async fn stop(&mut self) -> Result<(), DriveError> {
+        let left = self.dt.side_mut(Side::Left).set_voltage(0.0).await;
+        let right = self.dt.side_mut(Side::Right).set_voltage(0.0).await;
+        let errors: GroupErrors = left.err().into_iter().chain(right.err()).flatten().collect();
+        if errors.is_empty() { Ok(()) } else { Err(DriveError::Motor(errors)) }
+    }
+
+    /// Returns the measured left and right wheel speeds in m/s, so a motion
+    /// that starts while the robot moves ramps from its current speed.
+    fn wheel_speeds(&self) -> (f64, f64) {
+        let half_width = self.dt.width.as_meters() * 0.5;
+        let v = self.tracking.linear_velocity();
+        let w = self.tracking.angular_velocity();
+        (v - w * half_width, v + w * half_width)
+    }
+
+    /// Ends a motion, stopping the drivetrain unless the motion chained into
+    /// the next one.
+    async fn finish(&mut self, reason: ExitReason) -> Result<ExitReason, DriveError> {
+        if !reason.is_chained() {
+            self.stop().await?;
+        }
+        Ok(reason)
+    }
+
+    /// Stops the drivetrain and fails the motion with `err`.
+    ///
+    /// The stop is best-effort: `err` is reported even if the motors cannot
+    /// all be stopped.
+    async fn abort<T>(&mut self, err: DriveError) -> Result<T, DriveError> {
+        let _ = self.stop().await;
+        Err(err)
+    }
+
+    /// Reports a motion's progress to its
+    /// [`MotionHandle`](crate::odom::motion::MotionHandle) and checks that it
+    /// may carry on.
+    ///
+    /// The motion fails if it has been cancelled or if the pose source can no
+    /// longer measure the robot's motion. Short sensor dropouts are
+    /// tolerated; a measurement missing for longer than [`SENSOR_GRACE`]
+    /// fails the motion.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress` - Fraction of the motion completed, from 0 to 1
+    /// * `fault` - When the current dropout started, kept by the motion loop
+    fn checkpoint(&self, progress: f64, fault: &mut Option<Duration>) -> Result<(), DriveError> {
+        if let Some(motion) = &self.motion {
+            motion.report(self.tracking.pose().position(), progress);
+            if motion.is_cancelled() {
+                return Err(DriveError::Cancelled);
+            }
+        }
+
+        let Some(err) = self.pose_fault() else {
+            *fault = None;
+            return Ok(());
+        };
+
+        let now = self.clock.now();
+        let since = *fault.get_or_insert(now);
+        if now.saturating_sub(since) < SENSOR_GRACE {
+            return Ok(());
+        }
+        Err(err)
+    }
+
+    /// Returns the error for a pose source that cannot measure the heading or
+    /// the robot's travel, or `None` if it can.
+    fn pose_fault(&self) -> Option<DriveError> {
+        let heading_lost = self.tracking.heading_source() == Some(HeadingSource::Unavailable);
+        let travel_lost = self.tracking.travel_source() == Some(TravelSource::Unavailable);
+        if !heading_lost && !travel_lost {
+            return None;
+        }
+        // Without a heading, blame the heading sensor if it cannot be read;
+        // otherwise the wheels that should have stood in for it.
+        let wheels = self.tracking.wheel_status();
+        Some(match self.imu.heading() {
+            Err(err) if heading_lost => DriveError::Imu(err),
+            _ if wheels.is_empty() => DriveError::DriveEncoders,
+            _ => DriveError::TrackingWheel(wheels),
+        })
+    }
+
     /// Turns the robot in place to the specified absolute heading.
     ///
-    /// Uses the angular PID controller to turn until the heading error is within
-    /// a 2-degree tolerance. The turn direction is automatically chosen to take
-    /// the shortest path.
+    /// Uses the angular PID controller to turn until the default angular exit
+    /// conditions are met. The turn direction is automatically chosen to take
+    /// the shortest path. Headings are counter-clockwise positive, as returned
+    /// by [`heading`](Self::heading).
     ///
     /// # Arguments
     ///
@@ -359,8 +889,11 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Turn completed successfully
+    /// * `Ok(reason)` - Why the turn ended
     /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
     ///
     /// # Example
     ///
@@ -371,34 +904,319 @@ impl OdomChassis {
     /// // Turn to face north (0 degrees)
     /// chassis.turn(QAngle::from_degrees(0.0)).await?;
     /// ```
-    pub async fn turn(&mut self, target: QAngle) -> Result<(), DriveError> {
-        let angle_tolerance = QAngle::from_degrees(2.0);
+    pub async fn turn(&mut self, target: QAngle) -> Result<ExitReason, DriveError> {
+        self.turn_with(target, self.angular_exit).await
+    }
+
+    /// Turns the robot in place with exit conditions for this motion only.
+    ///
+    /// A chained turn ends as soon as it is within its chain range, still
+    /// turning. See [`turn`](Self::turn).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `exit` - When to end the turn, with errors in radians
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the turn ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    pub async fn turn_with(
+        &mut self,
+        target: QAngle,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        let mut tracker = ExitTracker::new(exit, self.clock.now());
+        let mut fault = None;
+        let initial = (target - self.heading()).remainder(QAngle::TAU).as_radians();
 
         self.angular_pid.reset();
 
-        loop {
+        let reason = loop {
             let current_heading = self.heading();
             let error = (target - current_heading).remainder(QAngle::TAU);
+            let velocity = self.tracking.angular_velocity();
 
-            if error.abs().as_radians() <= angle_tolerance.as_radians() {
-                self.dt
-                    .drive_tank(0.0, 0.0)
-                    .await
-                    .map_err(DriveError::Motor)?;
-                break;
+            if let Some(reason) = tracker.update(error.as_radians(), velocity, self.clock.now()) {
+                break reason;
+            }
+            let progress = fraction(initial - error.as_radians(), initial);
+            if let Err(err) = self.checkpoint(progress, &mut fault) {
+                return self.abort(err).await;
             }
 
             let output = self.angular_pid.calculate(target, current_heading);
             let turn = (output / Motor::V5_MAX_VOLTAGE).clamp(-1.0, 1.0);
 
-            self.dt
-                .drive_tank(turn, -turn)
-                .await
-                .map_err(DriveError::Motor)?;
-            sleep(Duration::from_millis(10)).await;
+            if let Err(err) = self.dt.drive_tank(-turn, turn).await {
+                return self.abort(DriveError::Motor(err)).await;
+            }
+            self.observe_drive().await;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
+
+        self.finish(reason).await
+    }
+
+    /// Swings the robot to an absolute heading, turning about one side.
+    ///
+    /// The locked side is braked with the chassis's swing brake mode, by
+    /// default [`BrakeMode::Hold`], while the angular PID drives the other
+    /// side. Turning counter-clockwise about the left side, or clockwise about
+    /// the right, moves the robot forwards; the other direction backs it up.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `locked` - The side that holds still
+    /// * `direction` - Which way to rotate to the target
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the swing ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Pivot on the right wheels, clockwise, to face -90 degrees
+    /// chassis.swing(QAngle::from_degrees(-90.0), Side::Right, TurnDirection::Clockwise).await?;
+    /// ```
+    pub async fn swing(
+        &mut self,
+        target: QAngle,
+        locked: Side,
+        direction: TurnDirection,
+    ) -> Result<ExitReason, DriveError> {
+        self.swing_with(target, locked, direction, self.angular_exit)
+            .await
+    }
+
+    /// Swings the robot about one side with exit conditions for this motion
+    /// only. See [`swing`](Self::swing).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `locked` - The side that holds still
+    /// * `direction` - Which way to rotate to the target
+    /// * `exit` - When to end the swing, with errors in radians
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the swing ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    pub async fn swing_with(
+        &mut self,
+        target: QAngle,
+        locked: Side,
+        direction: TurnDirection,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        let half_width = self.dt.width.as_meters() * 0.5;
+        if let Err(err) = self.dt.side_mut(locked).brake(self.swing_brake).await {
+            return self.abort(DriveError::Motor(err)).await;
         }
+        self.turn_about(target, locked, half_width, direction, exit, true)
+            .await
+    }
 
-        Ok(())
+    /// Drives the robot around a circle of fixed radius until it faces an
+    /// absolute heading.
+    ///
+    /// The radius is measured from the centre of the robot to the centre of
+    /// the circle, which lies on the given side. The angular PID sets how
+    /// fast the robot turns, and the two sides share its output in the ratio
+    /// that keeps the robot on the circle, the outer side getting the full
+    /// output. As with [`swing`](Self::swing), the direction decides whether
+    /// the robot drives forwards or backwards around the circle.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `radius` - The radius of the circle
+    /// * `centre` - The side the circle's centre lies on
+    /// * `direction` - Which way to rotate to the target
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the arc ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Curve forwards and left around a 0.6 m circle to face 90 degrees
+    /// let radius = QLength::from_meters(0.6);
+    /// chassis.arc(QAngle::from_degrees(90.0), radius, Side::Left, TurnDirection::Shortest).await?;
+    /// ```
+    pub async fn arc(
+        &mut self,
+        target: QAngle,
+        radius: QLength,
+        centre: Side,
+        direction: TurnDirection,
+    ) -> Result<ExitReason, DriveError> {
+        self.arc_with(target, radius, centre, direction, self.angular_exit)
+            .await
+    }
+
+    /// Drives the robot around a circle with exit conditions for this motion
+    /// only. See [`arc`](Self::arc).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `radius` - The radius of the circle
+    /// * `centre` - The side the circle's centre lies on
+    /// * `direction` - Which way to rotate to the target
+    /// * `exit` - When to end the arc, with errors in radians
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the arc ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    pub async fn arc_with(
+        &mut self,
+        target: QAngle,
+        radius: QLength,
+        centre: Side,
+        direction: TurnDirection,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        let radius = radius.as_meters().abs();
+        self.turn_about(target, centre, radius, direction, exit, false)
+            .await
+    }
+
+    /// Turns to a heading about a point `radius` metres to one side of the
+    /// robot, shared by [`swing_with`](Self::swing_with) and
+    /// [`arc_with`](Self::arc_with).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `centre` - The side the centre of rotation lies on
+    /// * `radius` - Distance from the robot's centre to the centre of rotation
+    /// * `direction` - Which way to rotate to the target
+    /// * `exit` - When to end the turn, with errors in radians
+    /// * `swing` - Whether the `centre` side is braked and left alone
+    async fn turn_about(
+        &mut self,
+        target: QAngle,
+        centre: Side,
+        radius: f64,
+        direction: TurnDirection,
+        exit: ExitConditions,
+        swing: bool,
+    ) -> Result<ExitReason, DriveError> {
+        use core::f64::consts::{PI, TAU};
+
+        let max = Motor::V5_MAX_VOLTAGE;
+        let half_width = self.dt.width.as_meters() * 0.5;
+
+        // Each side's share of the angular PID output. A side's speed is
+        // ω * (r - w/2) on the left and ω * (r + w/2) on the right, with the
+        // centre at signed lateral offset r.
+        let offset = match centre {
+            Side::Left => radius,
+            Side::Right => -radius,
+        };
+        let (left, right) = (offset - half_width, offset + half_width);
+        let outer = left.abs().max(right.abs());
+        let (left, right) = (left / outer, right / outer);
+
+        // Forced directions rotate the long way round at full output until
+        // the target is less than half a turn away, then settle like any turn.
+        let mut committed = direction == TurnDirection::Shortest;
+        let remaining = |heading: QAngle, committed: bool| {
+            let shortest = (target - heading).remainder(QAngle::TAU).as_radians();
+            let ccw = (target - heading).as_radians().rem_euclid(TAU);
+            match direction {
+                _ if committed => shortest,
+                TurnDirection::CounterClockwise => ccw,
+                TurnDirection::Clockwise => ccw - TAU,
+                TurnDirection::Shortest => shortest,
+            }
+        };
+
+        let mut tracker = ExitTracker::new(exit, self.clock.now());
+        let mut fault = None;
+        let initial = remaining(self.heading(), committed);
+
+        self.angular_pid.reset();
+        self.linear_pid.reset();
+
+        let reason = loop {
+            let current_heading = self.heading();
+            let mut error = remaining(current_heading, committed);
+            if !committed && error.abs() <= PI {
+                committed = true;
+                error = remaining(current_heading, committed);
+            }
+            let velocity = self.tracking.angular_velocity();
+
+            if let Some(reason) = tracker.update(error, velocity, self.clock.now()) {
+                break reason;
+            }
+            let progress = fraction(initial - error, initial);
+            if let Err(err) = self.checkpoint(progress, &mut fault) {
+                return self.abort(err).await;
+            }
+
+            let output = self.angular_pid.calculate(target, current_heading);
+            let output = if committed {
+                output.clamp(-max, max)
+            } else {
+                max.copysign(error)
+            };
+
+            if swing {
+                let share = match centre {
+                    Side::Left => right,
+                    Side::Right => left,
+                };
+                if let Err(err) = self
+                    .dt
+                    .side_mut(centre.opposite())
+                    .set_voltage(output * share)
+                    .await
+                {
+                    return self.abort(DriveError::Motor(err)).await;
+                }
+            } else {
+                // Shares alone only hold the radius at steady speed, so the
+                // linear PID keeps the forward speed at ω * r as well.
+                let correction = self
+                    .linear_pid
+                    .calculate(velocity * offset, self.tracking.linear_velocity());
+                let (l, r) = (output * left + correction, output * right + correction);
+                let scale = max / l.abs().max(r.abs()).max(max);
+                if let Err(err) = self.dt.drive_tank(l * scale / max, r * scale / max).await {
+                    return self.abort(DriveError::Motor(err)).await;
+                }
+            }
+            self.observe_drive().await;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
+
+        self.finish(reason).await
     }
 
     /// Follows a pre-generated trajectory using RAMSETE control.
@@ -414,13 +1232,11 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Trajectory completed successfully
+    /// * `Ok(ExitReason::Completed)` - Trajectory completed successfully
     /// * `Err(DriveError::Motor)` - Motor communication error
-    ///
-    /// # Panics
-    ///
-    /// This method requires a tracking rig for pose feedback. If no tracking rig
-    /// is present, pose estimation defaults to heading-only (position at origin).
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
     ///
     /// # Example
     ///
@@ -436,34 +1252,70 @@ impl OdomChassis {
     /// );
     /// chassis.trajectory(&trajectory).await?;
     /// ```
-    pub async fn trajectory(&mut self, traj: &Trajectory) -> Result<(), DriveError> {
+    pub async fn trajectory(&mut self, traj: &Trajectory) -> Result<ExitReason, DriveError> {
+        self.trajectory_with(traj, ExitConditions::new()).await
+    }
+
+    /// Follows a trajectory with exit conditions for this motion only.
+    ///
+    /// The trajectory's own timing ends the motion, so only the timeout
+    /// applies. A chained trajectory reports [`ExitReason::Chained`] when it
+    /// completes and leaves the robot moving at the trajectory's final
+    /// velocity. See [`trajectory`](Self::trajectory).
+    ///
+    /// # Arguments
+    ///
+    /// * `traj` - The trajectory to follow
+    /// * `exit` - When to give up on the trajectory
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - [`ExitReason::Completed`] or [`ExitReason::Timeout`]
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    pub async fn trajectory_with(
+        &mut self,
+        traj: &Trajectory,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
         let track_width_m = self.dt.width.as_meters();
         let total_time = traj.total_time().unwrap_or(QTime::from_sec(0.0)).as_sec();
-        let mut last_left_target = 0.0;
-        let mut last_right_target = 0.0;
+        let (mut last_left_target, mut last_right_target) = self.wheel_speeds();
         let mut last_time = 0.0;
-        let start = std::time::Instant::now();
+        let completed = if exit.chain.is_some() {
+            ExitReason::Chained
+        } else {
+            ExitReason::Completed
+        };
+        let start = self.clock.now();
+
+        let tracker = ExitTracker::new(exit, start);
+        let mut fault = None;
 
         self.left_pid.reset();
         self.right_pid.reset();
 
-        loop {
-            let t = QTime::from_sec(start.elapsed().as_secs_f64());
+        let reason = loop {
+            let t = QTime::from_sec(self.clock.now().saturating_sub(start).as_secs_f64());
             if t.as_sec() > total_time + 0.05 {
-                break;
+                break completed;
+            }
+            if let Some(reason) = tracker.timed_out(self.clock.now()) {
+                break reason;
+            }
+
+            if let Err(err) = self.checkpoint(fraction(t.as_sec(), total_time), &mut fault) {
+                return self.abort(err).await;
             }
 
             let point = match traj.sample(t) {
                 Some(p) => p,
-                None => break,
+                None => break completed,
             };
 
-            let heading = self.heading();
-            let pose = if let Some(tracking) = self.tracking.as_ref() {
-                Pose::new(tracking.pose().position(), heading)
-            } else {
-                Pose::new(Default::default(), heading)
-            };
+            let pose = self.tracking.pose();
 
             let reference = RamseteReference::from(point);
             let (v, w) = self.ramsete.calculate(pose, reference);
@@ -478,13 +1330,10 @@ impl OdomChassis {
             last_right_target = right_target;
             last_time = t.as_sec();
 
-            let (meas_v, meas_w) = if let Some(tracking) = self.tracking.as_ref() {
-                (tracking.linear_velocity(), tracking.angular_velocity())
-            } else {
-                let v = self.dt.linear_velocity().await.unwrap_or(0.0);
-                let w = self.dt.angular_velocity().await.unwrap_or(0.0);
-                (v, w)
-            };
+            let (meas_v, meas_w) = (
+                self.tracking.linear_velocity(),
+                self.tracking.angular_velocity(),
+            );
             let left_meas = meas_v - meas_w * (track_width_m * 0.5);
             let right_meas = meas_v + meas_w * (track_width_m * 0.5);
 
@@ -498,18 +1347,14 @@ impl OdomChassis {
             let left = left_volts / Motor::V5_MAX_VOLTAGE;
             let right = right_volts / Motor::V5_MAX_VOLTAGE;
 
-            self.dt
-                .drive_tank(left, right)
-                .await
-                .map_err(DriveError::Motor)?;
-            sleep(Duration::from_millis(10)).await;
-        }
+            if let Err(err) = self.dt.drive_tank(left, right).await {
+                return self.abort(DriveError::Motor(err)).await;
+            }
+            self.observe_drive().await;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
 
-        self.dt
-            .drive_tank(0.0, 0.0)
-            .await
-            .map_err(DriveError::Motor)?;
-        Ok(())
+        self.finish(reason).await
     }
 
     /// Follows a trajectory using pure pursuit control.
@@ -533,12 +1378,11 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Path completed successfully
+    /// * `Ok(ExitReason::Completed)` - Path completed successfully
     /// * `Err(DriveError::Motor)` - Motor communication error
-    ///
-    /// # Panics
-    ///
-    /// Panics if no tracking rig is present (required for position feedback).
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
     ///
     /// # Example
     ///
@@ -547,44 +1391,87 @@ impl OdomChassis {
     /// let pursuit = PurePursuit::new(trajectory, 0.3); // 30cm lookahead
     /// chassis.pursuit(&pursuit).await;
     /// ```
-    pub async fn pursuit(&mut self, path: &PurePursuit) -> Result<(), DriveError> {
-        const EXIT_TOLERANCE: f64 = 0.05;
+    pub async fn pursuit(&mut self, path: &PurePursuit) -> Result<ExitReason, DriveError> {
+        self.pursuit_with(path, ExitConditions::new()).await
+    }
 
-        assert!(
-            self.tracking.is_some(),
-            "pure pursuit requires tracking rig"
-        );
+    /// Follows a path with pure pursuit and exit conditions for this motion
+    /// only.
+    ///
+    /// Reaching the end of the path ends the motion, so only the timeout and
+    /// chain range apply. A chained path ends once the robot is within the
+    /// chain range of its last point, still moving. See
+    /// [`pursuit`](Self::pursuit).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The pure pursuit controller
+    /// * `exit` - When to give up on the path
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - [`ExitReason::Completed`] or [`ExitReason::Timeout`]
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    pub async fn pursuit_with(
+        &mut self,
+        path: &PurePursuit,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        const EXIT_TOLERANCE: f64 = 0.05;
 
         let track_width = self.dt.width.as_meters();
         let trajectory = path.trajectory();
         let final_point = match trajectory.points().last() {
             Some(p) => p.pose.position(),
-            None => return Ok(()), // Empty trajectory
+            None => return Ok(ExitReason::Completed), // Empty trajectory
         };
+        let tracker = ExitTracker::new(exit, self.clock.now());
+        let mut fault = None;
+        let length: f64 = trajectory
+            .points()
+            .windows(2)
+            .map(|pair| pair[0].pose.position().distance(pair[1].pose.position()))
+            .sum();
+        let mut travelled = 0.0;
+        let mut last_position = self.tracking.pose().position();
 
         self.left_pid.reset();
         self.right_pid.reset();
 
-        let mut last_left_target = 0.0;
-        let mut last_right_target = 0.0;
-        let mut last_time = std::time::Instant::now();
+        let (mut last_left_target, mut last_right_target) = self.wheel_speeds();
+        let mut last_time = self.clock.now();
+
+        let reason = loop {
+            let position = self.tracking.pose().position();
+            travelled += position.distance(last_position);
+            last_position = position;
+            if let Err(err) = self.checkpoint(fraction(travelled, length), &mut fault) {
+                return self.abort(err).await;
+            }
 
-        loop {
-            let heading = self.heading();
-            let tracking = self.tracking.as_ref().unwrap();
-            let position = tracking.pose().position();
-            let pose = Pose::new(position, heading);
+            let tracking = &self.tracking;
+            let pose = tracking.pose();
+            let position = pose.position();
 
             let dx = final_point.x - position.x;
             let dy = final_point.y - position.y;
             let dist_to_end = libm::sqrt(dx * dx + dy * dy);
+            if let Some(reason) = tracker.chained(dist_to_end) {
+                break reason;
+            }
             if dist_to_end < EXIT_TOLERANCE {
-                break;
+                break ExitReason::Completed;
+            }
+            if let Some(reason) = tracker.timed_out(self.clock.now()) {
+                break reason;
             }
 
             let target_point = match path.intersect(pose) {
                 Some(p) => p,
-                None => break,
+                None => break ExitReason::Completed,
             };
 
             let curvature = path.curvature(pose, target_point.pose.position());
@@ -595,8 +1482,8 @@ impl OdomChassis {
             let left_target = target_v - w * (track_width * 0.5);
             let right_target = target_v + w * (track_width * 0.5);
 
-            let now = std::time::Instant::now();
-            let dt = now.duration_since(last_time).as_secs_f64().max(1e-3);
+            let now = self.clock.now();
+            let dt = now.saturating_sub(last_time).as_secs_f64().max(1e-3);
             let left_accel = (left_target - last_left_target) / dt;
             let right_accel = (right_target - last_right_target) / dt;
             last_left_target = left_target;
@@ -617,48 +1504,142 @@ impl OdomChassis {
             let left = left_volts / Motor::V5_MAX_VOLTAGE;
             let right = right_volts / Motor::V5_MAX_VOLTAGE;
 
-            self.dt
-                .drive_tank(left, right)
-                .await
-                .map_err(DriveError::Motor)?;
-            sleep(Duration::from_millis(10)).await;
-        }
+            if let Err(err) = self.dt.drive_tank(left, right).await {
+                return self.abort(DriveError::Motor(err)).await;
+            }
+            self.observe_drive().await;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
 
-        self.dt
-            .drive_tank(0.0, 0.0)
-            .await
-            .map_err(DriveError::Motor)?;
-        Ok(())
+        self.finish(reason).await
     }
 
     /// Sets the robot's current pose estimate.
     ///
     /// Use this to initialize the pose at the start of autonomous or to
-    /// correct drift during operation.
+    /// correct drift during operation. The pose source is moved to the new
+    /// pose.
     ///
     /// # Arguments
     ///
     /// * `pose` - The new pose to set
     pub fn set_pose(&mut self, pose: &Pose) {
-        self.pose = pose.clone();
+        self.tracking.set_pose(*pose);
     }
 
     /// Returns the current pose estimate.
     ///
-    /// If a tracking rig is present, prefer using `tracking.pose()` directly
-    /// for the most up-to-date estimate.
+    /// This is the pose source's latest estimate.
     ///
     /// # Returns
     ///
     /// The current pose estimate
     pub fn get_pose(&self) -> Pose {
-        self.pose
+        self.tracking.pose()
+    }
+
+    /// Returns a handle to the pose source.
+    pub(crate) fn pose_source(&self) -> Rc<dyn PoseSource> {
+        Rc::clone(&self.tracking)
+    }
+
+    /// Returns the clock the control loops run on.
+    pub(crate) fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Attaches the state motions report their progress to, or detaches it.
+    pub(crate) fn set_motion(&mut self, motion: Option<Rc<MotionState>>) {
+        self.motion = motion;
+    }
+
+    /// Returns the health of the pose source's tracking wheels.
+    ///
+    /// With a [`TrackingRig`], there is one entry per wheel, vertical wheels
+    /// first. Pose sources that do not monitor tracking wheels, including the
+    /// built-in IME odometry, report none.
+    ///
+    /// # Returns
+    ///
+    /// The status of each tracking wheel
+    pub fn wheel_status(&self) -> Vec<WheelStatus> {
+        self.tracking.wheel_status()
+    }
+
+    /// Reports the drivetrain's velocity to the pose source for slip detection.
+    ///
+    /// Read errors are ignored; the pose source then treats the report as
+    /// missing.
+    async fn observe_drive(&self) {
+        if let (Ok(linear), Ok(angular)) = (
+            self.dt.linear_velocity().await,
+            self.dt.angular_velocity().await,
+        ) {
+            self.tracking.observe_drive(linear, angular);
+        }
+    }
+
+    /// Corrects the pose estimate using distance sensors facing known walls.
+    ///
+    /// Each sensor is paired with the wall its beam hits. The robot's distance
+    /// from each wall is recomputed from the readings and written back into
+    /// the tracking rig, so a sensor facing a side wall corrects one
+    /// coordinate and sensors facing perpendicular walls correct both. With
+    /// `heading` set, two sensors facing the same wall (usually a parallel
+    /// pair) also correct the heading. See [`relocalize`] for details.
+    ///
+    /// The robot should be stationary and square enough to the walls that the
+    /// beams hit them, which is typical at the start of a skills route or
+    /// after driving into a corner.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensors` - Distance sensors and the walls they face
+    /// * `heading` - Whether to also recompute the heading
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(pose)` - The corrected pose, now used by the tracking rig
+    /// * `Err(WallResetError)` - A sensor detected nothing or the readings
+    ///   could not be used; the pose is unchanged
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Robot facing +x in the bottom-left corner of a field centred on the origin
+    /// let half = Field::V5_SIZE.as_meters() / 2.0;
+    /// let west = Wall::new(Vec2::new(-half, -half), Vec2::new(-half, half));
+    /// let south = Wall::new(Vec2::new(-half, -half), Vec2::new(half, -half));
+    ///
+    /// chassis.wall_reset(&[(&back_sensor, west), (&right_sensor, south)], false)?;
+    /// ```
+    pub fn wall_reset<S: RangeFinder>(
+        &mut self,
+        sensors: &[(&MountedSensor<S>, Wall)],
+        heading: bool,
+    ) -> Result<Pose, WallResetError> {
+        let readings = sensors
+            .iter()
+            .enumerate()
+            .map(|(i, (sensor, wall))| {
+                let distance = sensor.read().ok_or(WallResetError::NoDetection(i))?;
+                Ok(WallReading {
+                    mount: sensor.mount(),
+                    wall: *wall,
+                    distance,
+                })
+            })
+            .collect::<Result<std::vec::Vec<_>, _>>()?;
+
+        let pose = relocalize(self.get_pose(), &readings, heading)?;
+        self.set_pose(&pose);
+        Ok(pose)
     }
 
     /// Turns the robot to face a target pose.
     ///
     /// Computes the angle from the robot's current position to the target pose
-    /// and turns to face that direction. Requires a tracking rig for position.
+    /// and turns to face that direction.
     ///
     /// # Arguments
     ///
@@ -666,12 +1647,11 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Turn completed successfully
+    /// * `Ok(reason)` - Why the turn ended
     /// * `Err(DriveError::Motor)` - Motor communication error
-    ///
-    /// # Panics
-    ///
-    /// Panics if no tracking rig is present (required for position feedback).
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
     ///
     /// # Example
     ///
@@ -701,12 +1681,8 @@ impl OdomChassis {
     ///     );
     ///     chassis.turn_to_pose(target).await;
     /// ```
-    pub async fn turn_to_pose(&mut self, pose: Pose) -> Result<(), DriveError> {
-        {
-            assert!(self.tracking.is_some(), "must have tracking");
-        }
-
-        let pos = self.tracking.as_ref().unwrap().pose().position();
+    pub async fn turn_to_pose(&mut self, pose: Pose) -> Result<ExitReason, DriveError> {
+        let pos = self.tracking.pose().position();
 
         let dx = pose.position().x - pos.x;
         let dy = pose.position().y - pos.y;
@@ -728,12 +1704,14 @@ impl OdomChassis {
     ///
     /// # Returns
     ///
-    /// * `Ok(())` - Movement completed successfully
+    /// * `Ok(reason)` - Why the drive ended, or [`ExitReason::Timeout`] if the
+    ///   turn timed out, in which case the robot does not drive
     /// * `Err(DriveError::Motor)` - Motor communication error
-    ///
-    /// # Panics
-    ///
-    /// Panics if no tracking rig is present (required for position feedback).
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
     ///
     /// # Example
     ///
@@ -763,12 +1741,321 @@ impl OdomChassis {
     ///     );
     ///     chassis.shoot_to_pose(target).await;
     /// ```
-    pub async fn shoot_to_pose(&mut self, pose: Pose) -> Result<(), DriveError> {
-        self.turn_to_pose(pose).await?;
-        let pos = self.tracking.as_ref().unwrap().pose().position();
+    pub async fn shoot_to_pose(&mut self, pose: Pose) -> Result<ExitReason, DriveError> {
+        let turned = self.turn_to_pose(pose).await?;
+        if turned.is_timeout() {
+            return Ok(turned);
+        }
+        let pos = self.tracking.pose().position();
         let dx = pose.position().x - pos.x;
         let dy = pose.position().y - pos.y;
         let dist = libm::sqrt(dx * dx + dy * dy);
         self.shoot(QLength::from_meters(dist)).await
     }
+
+    /// Drives to a pose along a curve with the boomerang controller, arriving
+    /// at the pose's heading.
+    ///
+    /// Unlike [`shoot_to_pose`](Self::shoot_to_pose), which turns in place and
+    /// ignores the target heading, the robot steers toward a [`Boomerang`]
+    /// carrot point behind the target. Once it is close to the target it holds
+    /// the target heading while closing the last of the distance. The
+    /// remaining distance sets the speed, limited by `params` and the
+    /// chassis's acceleration, which the linear PID and feedforward track; the
+    /// angular PID steers.
+    ///
+    /// The motion ends when the default linear exit conditions, on the
+    /// distance to the target, and the default angular exit conditions, on
+    /// the heading error, are both met.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    /// * `params` - Direction, lead and speed limits
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the movement ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Arrive 1 m ahead and 0.5 m left, facing left.
+    /// let target = Pose::new(Vec2::new(1.0, 0.5), QAngle::from_degrees(90.0));
+    /// chassis.move_to_pose(target, MoveParams::new()).await?;
+    ///
+    /// // Back into the same pose, capped at 0.8 m/s.
+    /// let params = MoveParams::new().with_forwards(false).with_max_speed(0.8);
+    /// chassis.move_to_pose(target, params).await?;
+    /// ```
+    pub async fn move_to_pose(
+        &mut self,
+        pose: Pose,
+        params: MoveParams,
+    ) -> Result<ExitReason, DriveError> {
+        self.move_to_pose_with(pose, params, self.linear_exit).await
+    }
+
+    /// Drives to a pose with the boomerang controller and exit conditions for
+    /// this motion only.
+    ///
+    /// `exit` applies to the distance to the target, and its timeout and
+    /// chain range end the motion on their own. The heading must still settle
+    /// under the chassis's angular exit conditions. See
+    /// [`move_to_pose`](Self::move_to_pose).
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    /// * `params` - Direction, lead and speed limits
+    /// * `exit` - When to end the motion, with errors in metres
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the movement ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source stopped measuring
+    ///   the robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
+    pub async fn move_to_pose_with(
+        &mut self,
+        pose: Pose,
+        params: MoveParams,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        if !self.constraints.is_valid() {
+            return Err(DriveError::InvalidConstraints);
+        }
+        let controller = Boomerang::new(pose, params).facing(self.tracking.pose());
+        let forwards = controller.params().direction != DriveDirection::Backwards;
+        let target = pose.position();
+        let travel_heading = controller.travel_heading();
+        let direction = if forwards { 1.0 } else { -1.0 };
+        let (max_speed, min_speed) = self.speed_limits(params);
+
+        let mut linear = ExitTracker::new(exit, self.clock.now());
+        let mut angular = ExitTracker::new(self.angular_exit, self.clock.now());
+        let mut fault = None;
+        let initial = self.tracking.pose().position().distance(target);
+        let mut close = false;
+        // Speed in the direction of travel, starting from the current one
+        let mut speed = direction * self.tracking.linear_velocity();
+        let mut last_time = self.clock.now();
+
+        self.linear_pid.reset();
+        self.angular_pid.reset();
+
+        let reason = loop {
+            let now = self.clock.now();
+            let current = self.tracking.pose();
+            let distance = current.position().distance(target);
+            // Heading of the robot's direction of travel
+            let heading = if forwards {
+                current.heading()
+            } else {
+                current.heading() + QAngle::from_radians(core::f64::consts::PI)
+            };
+            let heading_error = (travel_heading - heading).remainder(QAngle::TAU);
+
+            let turned = angular.update(
+                heading_error.as_radians(),
+                self.tracking.angular_velocity(),
+                now,
+            );
+            match linear.update(distance, self.tracking.linear_velocity(), now) {
+                Some(reason @ (ExitReason::Chained | ExitReason::Timeout)) => break reason,
+                Some(reason) if turned.is_some_and(|turned| !turned.is_timeout()) => break reason,
+                _ => {}
+            }
+            if let Err(err) = self.checkpoint(fraction(initial - distance, initial), &mut fault) {
+                return self.abort(err).await;
+            }
+
+            close |= distance < SETTLE_RADIUS;
+            let aim = if close {
+                target
+            } else {
+                controller.carrot(current)
+            };
+            let to_aim = aim - current.position();
+            let aim_heading = if close {
+                travel_heading
+            } else {
+                QAngle::from_radians(libm::atan2(to_aim.y, to_aim.x))
+            };
+            // Distance along the direction of travel; negative once the aim
+            // point is behind the robot.
+            let along = to_aim.dot(Vec2::new(heading.cos(), heading.sin()));
+
+            let step = self.constraints.max_acceleration
+                * now.saturating_sub(last_time).as_secs_f64();
+            last_time = now;
+            speed = ramp(speed, approach_speed(along, max_speed, min_speed), step);
+
+            let turn = self.angular_pid.calculate(aim_heading, heading);
+            self.drive_steered(direction * speed, turn).await?;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
+
+        self.finish(reason).await
+    }
+
+    /// Drives to a field point, turning and driving at the same time.
+    ///
+    /// Unlike [`shoot_to_pose`](Self::shoot_to_pose), the robot does not stop
+    /// to turn first: the angular PID steers toward the point while the
+    /// robot drives, and the speed is scaled by the cosine of the heading
+    /// error, so the robot slows while it faces away from the point and backs
+    /// up if the point is behind it. With [`DriveDirection::Auto`] the robot
+    /// drives whichever way faces the point, reversing to points behind it.
+    /// Close to the point it stops steering and closes the last of the
+    /// distance along its heading.
+    ///
+    /// The motion needs a pose source that measures position. It fails at
+    /// once, without moving, if the pose source has lost the heading or the
+    /// robot's travel; the drive motor encoders count as a pose source.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The field point to reach, in metres
+    /// * `params` - Direction and speed limits; the lead is not used
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the movement ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source cannot measure the
+    ///   robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Drive to the point, backing up if it is behind the robot.
+    /// chassis.move_to_point(Vec2::new(1.0, -0.5), MoveParams::new()).await?;
+    ///
+    /// // Always drive forwards, turning around if needed.
+    /// let params = MoveParams::new().with_forwards(true);
+    /// chassis.move_to_point(Vec2::new(0.0, 0.0), params).await?;
+    /// ```
+    pub async fn move_to_point(
+        &mut self,
+        point: Vec2<f64>,
+        params: MoveParams,
+    ) -> Result<ExitReason, DriveError> {
+        self.move_to_point_with(point, params, self.linear_exit).await
+    }
+
+    /// Drives to a field point with exit conditions for this motion only.
+    ///
+    /// See [`move_to_point`](Self::move_to_point).
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The field point to reach, in metres
+    /// * `params` - Direction and speed limits
+    /// * `exit` - When to end the motion, with errors in metres
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(reason)` - Why the movement ended
+    /// * `Err(DriveError::Motor)` - Motor communication error
+    /// * `Err(DriveError::Imu)`, `Err(DriveError::TrackingWheel)` or
+    ///   `Err(DriveError::DriveEncoders)` - The pose source cannot measure the
+    ///   robot's motion
+    /// * `Err(DriveError::InvalidConstraints)` - The motion constraints are not
+    ///   positive
+    pub async fn move_to_point_with(
+        &mut self,
+        point: Vec2<f64>,
+        params: MoveParams,
+        exit: ExitConditions,
+    ) -> Result<ExitReason, DriveError> {
+        if !self.constraints.is_valid() {
+            return Err(DriveError::InvalidConstraints);
+        }
+        if let Some(err) = self.pose_fault() {
+            return Err(err);
+        }
+        let (max_speed, min_speed) = self.speed_limits(params);
+
+        let mut tracker = ExitTracker::new(exit, self.clock.now());
+        let mut fault = None;
+        let initial = self.tracking.pose().position().distance(point);
+        let mut close = false;
+        let mut velocity = self.tracking.linear_velocity();
+        let mut last_time = self.clock.now();
+
+        self.linear_pid.reset();
+        self.angular_pid.reset();
+
+        let reason = loop {
+            let now = self.clock.now();
+            let current = self.tracking.pose();
+            let to_point = point - current.position();
+            let distance = to_point.norm();
+
+            if let Some(reason) = tracker.update(distance, self.tracking.linear_velocity(), now) {
+                break reason;
+            }
+            if let Err(err) = self.checkpoint(fraction(initial - distance, initial), &mut fault) {
+                return self.abort(err).await;
+            }
+
+            let heading = current.heading();
+            let bearing = QAngle::from_radians(libm::atan2(to_point.y, to_point.x));
+            let error = (bearing - heading).remainder(QAngle::TAU);
+            let forwards = match params.direction {
+                DriveDirection::Forwards => true,
+                DriveDirection::Backwards => false,
+                DriveDirection::Auto => error.as_radians().abs() <= core::f64::consts::FRAC_PI_2,
+            };
+            // Heading of the robot's direction of travel
+            let travel = if forwards {
+                heading
+            } else {
+                heading + QAngle::from_radians(core::f64::consts::PI)
+            };
+            let travel_error = (bearing - travel).remainder(QAngle::TAU);
+
+            // Close to the point the bearing swings wildly, so hold the
+            // heading and drive along it.
+            close |= distance < SETTLE_RADIUS;
+            let turn = if close {
+                0.0
+            } else {
+                self.angular_pid.calculate(bearing, travel)
+            };
+            let along = to_point.dot(Vec2::new(heading.cos(), heading.sin()));
+            let target_v = if close {
+                approach_speed(along, max_speed, min_speed)
+            } else {
+                // Slow down while facing away from the point, and only turn
+                // while travelling would carry the robot away from it.
+                let speed = approach_speed(distance, max_speed, min_speed);
+                let scaled = speed * travel_error.cos().max(0.0);
+                if forwards { scaled } else { -scaled }
+            };
+
+            let step = self.constraints.max_acceleration
+                * now.saturating_sub(last_time).as_secs_f64();
+            last_time = now;
+            velocity = ramp(velocity, target_v, step);
+
+            self.drive_steered(velocity, turn).await?;
+            self.clock.sleep(Duration::from_millis(10)).await;
+        };
+
+        self.finish(reason).await
+    }
 }
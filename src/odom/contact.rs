@@ -0,0 +1,318 @@
+//! Wheel slip, collision and push detection.
+//!
+//! Dead reckoning assumes the wheels roll without slipping and that only the
+//! drivetrain moves the robot. When the drive wheels spin against a wall, the
+//! robot hits something, or another robot shoves it, that assumption breaks
+//! and the pose estimate quietly goes wrong. [`ContactDetector`] notices these
+//! situations by comparing three views of the robot's motion:
+//!
+//! - the ground motion measured by the tracking wheels and the heading sensor,
+//! - the drivetrain's own velocity estimate from its motor encoders, reported
+//!   through its [`Drivetrain`](crate::Drivetrain) impl, and
+//! - the heading sensor's accelerometer.
+//!
+//! | Flag | Condition |
+//! |------|-----------|
+//! | slipping | The drive is moving and its velocity disagrees with the ground motion |
+//! | pushed | The robot moves sideways, or moves while the drive is still |
+//! | colliding | The acceleration, or the drop in ground speed, exceeds a limit |
+//!
+//! Slip and push must persist for a short time before they are flagged, so a
+//! single noisy reading does not trigger them. Each time a flag turns on, a
+//! [`ContactEvent`] is recorded.
+//!
+//! [`TrackingRig`](crate::TrackingRig) runs a detector on every update; see
+//! [`TrackingRig::contact`](crate::TrackingRig::contact).
+//!
+//! # Example
+//!
+//! ```ignore
+//! for event in rig.take_contact_events() {
+//!     if event.kind == ContactKind::Collision {
+//!         // The robot ran into the corner; re-measure from both walls.
+//!         chassis.wall_reset(&[(&back_sensor, west), (&right_sensor, south)], false)?;
+//!     }
+//! }
+//! ```
+
+use crate::odom::pose::Pose;
+use core::time::Duration;
+use std::collections::VecDeque;
+
+/// Most events kept before the oldest are discarded.
+const MAX_EVENTS: usize = 32;
+
+/// Thresholds for contact detection.
+///
+/// Use the `with_*` builder methods to adjust individual values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactConfig {
+    /// Largest disagreement between drive and ground velocity before the
+    /// wheels count as slipping, and the speed above which the robot counts
+    /// as moving (m/s).
+    pub velocity_tolerance: f64,
+    /// Largest disagreement between drive and ground turn rate before the
+    /// wheels count as slipping, and the rate above which the robot counts as
+    /// turning (rad/s).
+    pub rate_tolerance: f64,
+    /// Horizontal acceleration or ground deceleration that counts as a
+    /// collision (m/s²).
+    pub collision_acceleration: f64,
+    /// How long slip and push conditions must persist before they are
+    /// flagged, and how long every flag stays on after its condition ends.
+    pub hold_time: Duration,
+}
+
+impl ContactConfig {
+    /// Creates a configuration with a 0.15 m/s and 0.5 rad/s tolerance, a
+    /// 12 m/s² collision limit and a 50 ms hold time.
+    pub const fn new() -> Self {
+        Self {
+            velocity_tolerance: 0.15,
+            rate_tolerance: 0.5,
+            collision_acceleration: 12.0,
+            hold_time: Duration::from_millis(50),
+        }
+    }
+
+    /// Sets the velocity tolerance (m/s).
+    pub const fn with_velocity_tolerance(mut self, tolerance: f64) -> Self {
+        self.velocity_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the turn rate tolerance (rad/s).
+    pub const fn with_rate_tolerance(mut self, tolerance: f64) -> Self {
+        self.rate_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the acceleration that counts as a collision (m/s²).
+    pub const fn with_collision_acceleration(mut self, acceleration: f64) -> Self {
+        self.collision_acceleration = acceleration;
+        self
+    }
+
+    /// Sets how long conditions must persist, and flags stay on.
+    pub const fn with_hold_time(mut self, time: Duration) -> Self {
+        self.hold_time = time;
+        self
+    }
+}
+
+impl Default for ContactConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What the robot is currently experiencing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContactState {
+    /// The drive wheels are slipping against the ground.
+    pub slipping: bool,
+    /// Something other than the drivetrain is moving the robot.
+    pub pushed: bool,
+    /// The robot has hit something.
+    pub colliding: bool,
+}
+
+impl ContactState {
+    /// Returns `true` if any flag is set, meaning the pose estimate may have
+    /// lost accuracy.
+    pub const fn any(&self) -> bool {
+        self.slipping || self.pushed || self.colliding
+    }
+}
+
+/// Kinds of [`ContactEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactKind {
+    /// The drive wheels started slipping.
+    Slip,
+    /// The robot started being pushed.
+    Push,
+    /// The robot hit something.
+    Collision,
+}
+
+/// A contact flag turning on.
+#[derive(Debug, Clone, Copy)]
+pub struct ContactEvent {
+    /// What happened
+    pub kind: ContactKind,
+    /// When it was detected, on the detector's clock
+    pub time: Duration,
+    /// The estimated pose when it was detected
+    pub pose: Pose,
+}
+
+/// Motion measured over one update, passed to [`ContactDetector::update`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContactInput {
+    /// Forward ground velocity from the tracking wheels (m/s)
+    pub linear_velocity: f64,
+    /// Leftward ground velocity from horizontal tracking wheels (m/s), or
+    /// `None` without them
+    pub lateral_velocity: Option<f64>,
+    /// Ground turn rate (rad/s), counter-clockwise positive
+    pub angular_velocity: f64,
+    /// Horizontal acceleration (m/s²), or `None` if it could not be read
+    pub acceleration: Option<f64>,
+    /// The drivetrain's forward velocity (m/s) and turn rate (rad/s), or
+    /// `None` if unknown
+    pub drive: Option<(f64, f64)>,
+}
+
+/// Flags slip, pushes and collisions from successive motion measurements.
+#[derive(Debug, Clone)]
+pub struct ContactDetector {
+    config: ContactConfig,
+    state: ContactState,
+    /// Since when each condition has held: slip, push, collision
+    since: [Option<Duration>; 3],
+    /// When each condition last held
+    last_seen: [Option<Duration>; 3],
+    previous: Option<(f64, Duration)>,
+    events: VecDeque<ContactEvent>,
+}
+
+impl ContactDetector {
+    /// Creates a detector with no flags set.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Detection thresholds
+    pub fn new(config: ContactConfig) -> Self {
+        Self {
+            config,
+            state: ContactState::default(),
+            since: [None; 3],
+            last_seen: [None; 3],
+            previous: None,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Returns the thresholds.
+    pub fn config(&self) -> ContactConfig {
+        self.config
+    }
+
+    /// Changes the thresholds.
+    pub fn set_config(&mut self, config: ContactConfig) {
+        self.config = config;
+    }
+
+    /// Returns the current flags.
+    pub fn state(&self) -> ContactState {
+        self.state
+    }
+
+    /// Removes and returns the recorded events, oldest first.
+    ///
+    /// Only the most recent 32 events are kept.
+    pub fn take_events(&mut self) -> Vec<ContactEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Clears the flags and events.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.config);
+    }
+
+    /// Updates the flags with a new measurement.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Motion measured since the previous update
+    /// * `pose` - The estimated pose, recorded with any new event
+    /// * `time` - Time of the measurement
+    ///
+    /// # Returns
+    ///
+    /// The updated flags.
+    pub fn update(&mut self, input: ContactInput, pose: Pose, time: Duration) -> ContactState {
+        let cfg = self.config;
+        let speed = input.linear_velocity;
+        let rate = input.angular_velocity;
+
+        let (slip, pushed_by_drive) = match input.drive {
+            Some((drive_speed, drive_rate)) => {
+                let drive_moving = drive_speed.abs() > cfg.velocity_tolerance
+                    || drive_rate.abs() > cfg.rate_tolerance;
+                let ground_moving =
+                    speed.abs() > cfg.velocity_tolerance || rate.abs() > cfg.rate_tolerance;
+                let mismatch = (drive_speed - speed).abs() > cfg.velocity_tolerance
+                    || (drive_rate - rate).abs() > cfg.rate_tolerance;
+                (drive_moving && mismatch, !drive_moving && ground_moving)
+            }
+            None => (false, false),
+        };
+        let sideways = input
+            .lateral_velocity
+            .is_some_and(|lateral| lateral.abs() > cfg.velocity_tolerance);
+        let push = pushed_by_drive || sideways;
+
+        // A sudden drop in ground speed is an impact even without an
+        // accelerometer.
+        let deceleration = match self.previous {
+            Some((previous, then)) if time > then => {
+                (previous.abs() - speed.abs()) / (time - then).as_secs_f64()
+            }
+            _ => 0.0,
+        };
+        self.previous = Some((speed, time));
+        let impact = input.acceleration.unwrap_or(0.0) > cfg.collision_acceleration
+            || deceleration > cfg.collision_acceleration;
+
+        let slipping = self.debounce(0, slip, time, cfg.hold_time);
+        let pushed = self.debounce(1, push, time, cfg.hold_time);
+        let colliding = self.debounce(2, impact, time, Duration::ZERO);
+        let state = ContactState {
+            slipping,
+            pushed,
+            colliding,
+        };
+
+        let started = [
+            (ContactKind::Slip, slipping && !self.state.slipping),
+            (ContactKind::Push, pushed && !self.state.pushed),
+            (ContactKind::Collision, colliding && !self.state.colliding),
+        ];
+        for (kind, _) in started.into_iter().filter(|(_, started)| *started) {
+            if self.events.len() == MAX_EVENTS {
+                self.events.pop_front();
+            }
+            self.events.push_back(ContactEvent { kind, time, pose });
+        }
+
+        self.state = state;
+        state
+    }
+
+    /// Returns whether condition `index` should be flagged.
+    ///
+    /// A condition is flagged once it has held for `delay`, and stays flagged
+    /// until it has been absent for the hold time.
+    fn debounce(&mut self, index: usize, active: bool, time: Duration, delay: Duration) -> bool {
+        if active {
+            self.last_seen[index] = Some(time);
+            let since = *self.since[index].get_or_insert(time);
+            if time.saturating_sub(since) >= delay {
+                return true;
+            }
+        } else {
+            self.since[index] = None;
+        }
+
+        let flagged = match index {
+            0 => self.state.slipping,
+            1 => self.state.pushed,
+            _ => self.state.colliding,
+        };
+        flagged
+            && self.last_seen[index]
+                .is_some_and(|seen| time.saturating_sub(seen) < self.config.hold_time)
+    }
+}
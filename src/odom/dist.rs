@@ -0,0 +1,387 @@
+//! Field geometry for distance sensor measurements.
+//!
+//! A distance sensor reports how far its beam travels before hitting
+//! something. To turn that into information about the robot's pose, the
+//! estimator needs to know what the beam can hit. This module models those
+//! surfaces as straight [`Wall`] segments in field coordinates, collected into
+//! a [`Field`] map, and describes where each sensor sits on the robot with a
+//! [`MountedSensor`].
+//!
+//! [`relocalize`] turns readings against known walls directly into a pose,
+//! the basis of the wall reset routine on
+//! [`OdomChassis`](crate::OdomChassis::wall_reset).
+//!
+//! # Example
+//!
+//! ```
+//! use kernelvex::odom::dist::Wall;
+//! use kernelvex::{QAngle, Vec2};
+//!
+//! // A wall along x = 1.8 m
+//! let wall = Wall::new(Vec2::new(1.8, -1.8), Vec2::new(1.8, 1.8));
+//!
+//! // A beam from the origin facing +x hits it 1.8 m away
+//! let hit = wall.ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(0.0));
+//! assert!((hit.unwrap().as_meters() - 1.8).abs() < 1e-9);
+//! ```
+
+use crate::hal::distance::RangeFinder;
+use crate::odom::pose::Pose;
+use crate::util::si::{QAngle, QLength, Vec2};
+use core::f64::consts::{FRAC_PI_4, PI, TAU};
+
+/// A straight wall segment in field coordinates (metres).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wall {
+    start: Vec2<f64>,
+    end: Vec2<f64>,
+}
+
+impl Wall {
+    /// Creates a wall between two points.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - One end of the wall in metres
+    /// * `end` - The other end of the wall in metres
+    pub const fn new(start: Vec2<f64>, end: Vec2<f64>) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the first end point of the wall.
+    pub fn start(&self) -> Vec2<f64> {
+        self.start
+    }
+
+    /// Returns the second end point of the wall.
+    pub fn end(&self) -> Vec2<f64> {
+        self.end
+    }
+
+    /// Returns the unit normal of the wall.
+    ///
+    /// The normal points to the left of the direction from `start` to `end`.
+    pub fn normal(&self) -> Vec2<f64> {
+        (self.end - self.start).perp().normalize()
+    }
+
+    /// Casts a ray against the wall.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Start of the ray in metres
+    /// * `heading` - Direction of the ray (counter-clockwise from +x)
+    ///
+    /// # Returns
+    ///
+    /// The distance along the ray to the wall, or `None` if the ray is
+    /// parallel to the wall, points away from it, or passes beyond either end.
+    pub fn ray_cast(&self, origin: Vec2<f64>, heading: QAngle) -> Option<QLength> {
+        let direction = Vec2::new(heading.cos(), heading.sin());
+        let edge = self.end - self.start;
+
+        let denom = direction.cross(edge);
+        if denom.abs() < 1e-12 {
+            return None;
+        }
+
+        let offset = self.start - origin;
+        let t = offset.cross(edge) / denom;
+        let u = offset.cross(direction) / denom;
+
+        if t >= 0.0 && (0.0..=1.0).contains(&u) {
+            Some(QLength::from_meters(t))
+        } else {
+            None
+        }
+    }
+}
+
+/// A map of the surfaces a distance sensor can see.
+///
+/// Coordinates are metres in the same frame as the robot's [`Pose`]. The
+/// [`v5`](Self::v5) map places the origin at the centre of the field.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Field {
+    walls: std::vec::Vec<Wall>,
+}
+
+impl Field {
+    /// Side length of a V5 competition field (12 ft).
+    pub const V5_SIZE: QLength = QLength::from_inches(144.0);
+
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a rectangular perimeter centred on the origin.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Extent along x
+    /// * `height` - Extent along y
+    pub fn perimeter(width: QLength, height: QLength) -> Self {
+        let (hx, hy) = (width.as_meters() / 2.0, height.as_meters() / 2.0);
+        Self::new().with_rectangle(Vec2::new(-hx, -hy), Vec2::new(hx, hy))
+    }
+
+    /// Creates the perimeter of a V5 competition field, centred on the origin.
+    pub fn v5() -> Self {
+        Self::perimeter(Self::V5_SIZE, Self::V5_SIZE)
+    }
+
+    /// Adds a wall to the map.
+    pub fn with_wall(mut self, wall: Wall) -> Self {
+        self.walls.push(wall);
+        self
+    }
+
+    /// Adds the four sides of an axis-aligned rectangle, such as a field
+    /// perimeter or a solid obstacle.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Corner with the smallest x and y
+    /// * `max` - Corner with the largest x and y
+    pub fn with_rectangle(self, min: Vec2<f64>, max: Vec2<f64>) -> Self {
+        let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+
+        (0..4).fold(self, |field, i| {
+            field.with_wall(Wall::new(corners[i], corners[(i + 1) % 4]))
+        })
+    }
+
+    /// Returns the walls in the map.
+    pub fn walls(&self) -> &[Wall] {
+        &self.walls
+    }
+
+    /// Casts a ray against every wall and returns the nearest hit.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - Start of the ray in metres
+    /// * `heading` - Direction of the ray (counter-clockwise from +x)
+    pub fn ray_cast(&self, origin: Vec2<f64>, heading: QAngle) -> Option<QLength> {
+        self.walls
+            .iter()
+            .filter_map(|wall| wall.ray_cast(origin, heading))
+            .min_by(|a, b| a.as_meters().total_cmp(&b.as_meters()))
+    }
+
+    /// Returns the distance a mounted sensor should read from a robot pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `robot` - The robot pose
+    /// * `mount` - Sensor pose relative to the robot centre (x forward, y left)
+    pub fn expected_reading(&self, robot: Pose, mount: Pose) -> Option<QLength> {
+        let beam = sensor_pose(robot, mount);
+        self.ray_cast(beam.position(), beam.heading())
+    }
+}
+
+/// A distance sensor and where it sits on the robot.
+#[derive(Debug)]
+pub struct MountedSensor<S: RangeFinder> {
+    sensor: S,
+    mount: Pose,
+}
+
+impl<S: RangeFinder> MountedSensor<S> {
+    /// Creates a mounted sensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensor` - The distance sensor
+    /// * `mount` - Sensor position and beam direction relative to the robot
+    ///   centre (x forward, y left, heading counter-clockwise from forward)
+    pub fn new(sensor: S, mount: Pose) -> Self {
+        Self { sensor, mount }
+    }
+
+    /// Returns the mounting pose.
+    pub fn mount(&self) -> Pose {
+        self.mount
+    }
+
+    /// Returns the underlying sensor.
+    pub fn sensor(&self) -> &S {
+        &self.sensor
+    }
+
+    /// Reads the sensor.
+    ///
+    /// # Returns
+    ///
+    /// The measured distance, or `None` if nothing was detected or the read
+    /// failed.
+    pub fn read(&self) -> Option<QLength> {
+        self.sensor.distance().ok().flatten()
+    }
+}
+
+/// Returns the field pose of a sensor mounted at `mount` on a robot at `robot`.
+pub(crate) fn sensor_pose(robot: Pose, mount: Pose) -> Pose {
+    let theta = robot.heading();
+    let (sin, cos) = (theta.sin(), theta.cos());
+    let (ox, oy) = (mount.position().x, mount.position().y);
+
+    Pose::new(
+        Vec2::new(
+            robot.position().x + ox * cos - oy * sin,
+            robot.position().y + ox * sin + oy * cos,
+        ),
+        theta + mount.heading(),
+    )
+}
+
+/// A distance sensor reading against a known wall.
+#[derive(Debug, Clone, Copy)]
+pub struct WallReading {
+    /// Sensor position and beam direction relative to the robot centre
+    /// (x forward, y left)
+    pub mount: Pose,
+    /// The wall the beam hits
+    pub wall: Wall,
+    /// The measured distance
+    pub distance: QLength,
+}
+
+/// Reasons a wall reset could not be computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallResetError {
+    /// No readings were given.
+    NoReadings,
+    /// The sensor at this index detected nothing.
+    NoDetection(usize),
+    /// The beam at this index runs almost along its wall, so its reading
+    /// says little about the distance to it.
+    GrazingBeam(usize),
+    /// A heading was requested but no two readings hit the same wall at
+    /// distinct points.
+    NoHeadingPair,
+    /// The readings disagree with the heading estimate.
+    Inconsistent,
+}
+
+/// Smallest `|cos|` between a beam and its wall's normal that is accepted.
+const MIN_INCIDENCE: f64 = 0.2;
+
+/// Recomputes the robot's pose from distance readings against known walls.
+///
+/// Each reading fixes the robot's distance from one wall. Readings against
+/// perpendicular walls fix both coordinates; readings against a single wall
+/// (or parallel walls) only correct the coordinate across it and leave the
+/// other unchanged.
+///
+/// When `heading` is set, the heading is first recomputed from two readings
+/// against the same wall, usually from a pair of parallel sensors: the
+/// points where the beams hit trace the wall's direction in the robot frame.
+///
+/// # Arguments
+///
+/// * `estimate` - The current pose estimate; supplies the heading when it is
+///   not recomputed, and the coordinates no reading constrains
+/// * `readings` - Distance readings and the walls they hit
+/// * `heading` - Whether to recompute the heading
+///
+/// # Returns
+///
+/// The corrected pose, or a [`WallResetError`] describing why it could not
+/// be computed.
+pub fn relocalize(
+    estimate: Pose,
+    readings: &[WallReading],
+    heading: bool,
+) -> Result<Pose, WallResetError> {
+    if readings.is_empty() {
+        return Err(WallResetError::NoReadings);
+    }
+
+    let theta = if heading {
+        relocalize_heading(estimate.heading(), readings)?
+    } else {
+        estimate.heading()
+    };
+    let (sin, cos) = (theta.sin(), theta.cos());
+
+    // Each reading gives a line constraint n · p = c on the robot position.
+    // Accumulate the normal equations of the least-squares fit.
+    let (mut a00, mut a01, mut a11, mut b0, mut b1) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (i, reading) in readings.iter().enumerate() {
+        let n = reading.wall.normal();
+        let beam = theta + reading.mount.heading();
+        let u = Vec2::new(beam.cos(), beam.sin());
+        if n.dot(u).abs() < MIN_INCIDENCE {
+            return Err(WallResetError::GrazingBeam(i));
+        }
+
+        let m = reading.mount.position();
+        let offset = Vec2::new(m.x * cos - m.y * sin, m.x * sin + m.y * cos);
+        let c =
+            n.dot(reading.wall.start()) - n.dot(offset) - reading.distance.as_meters() * n.dot(u);
+
+        a00 += n.x * n.x;
+        a01 += n.x * n.y;
+        a11 += n.y * n.y;
+        b0 += n.x * c;
+        b1 += n.y * c;
+    }
+
+    let det = a00 * a11 - a01 * a01;
+    let position = if det > 1e-6 * readings.len() as f64 {
+        Vec2::new((a11 * b0 - a01 * b1) / det, (a00 * b1 - a01 * b0) / det)
+    } else {
+        // All walls are parallel: only the coordinate across them is known.
+        let n = readings[0].wall.normal();
+        let current = n.dot(estimate.position());
+        let target = (b0 * n.x + b1 * n.y) / readings.len() as f64;
+        estimate.position() + n * (target - current)
+    };
+
+    Ok(Pose::new(position, theta))
+}
+
+/// Recomputes the heading from the first two readings against the same wall.
+fn relocalize_heading(
+    estimate: QAngle,
+    readings: &[WallReading],
+) -> Result<QAngle, WallResetError> {
+    let hit = |reading: &WallReading| {
+        let beam = reading.mount.heading();
+        reading.mount.position() + Vec2::new(beam.cos(), beam.sin()) * reading.distance.as_meters()
+    };
+
+    for (i, first) in readings.iter().enumerate() {
+        for second in &readings[i + 1..] {
+            if first.wall != second.wall {
+                continue;
+            }
+
+            // Direction of the wall as seen from the robot.
+            let seen = hit(second) - hit(first);
+            if seen.norm() < 0.01 {
+                continue;
+            }
+
+            let edge = first.wall.end() - first.wall.start();
+            let field = libm::atan2(edge.y, edge.x);
+            let local = libm::atan2(seen.y, seen.x);
+
+            // The readings fix the wall's direction only up to its sign, so
+            // take whichever solution is closer to the current estimate.
+            let candidate = field - local;
+            let error = libm::remainder(candidate - estimate.as_radians(), PI);
+            let theta = estimate.as_radians() + error;
+
+            if error.abs() > FRAC_PI_4 {
+                return Err(WallResetError::Inconsistent);
+            }
+            return Ok(QAngle::from_radians(libm::remainder(theta, TAU)));
+        }
+    }
+
+    Err(WallResetError::NoHeadingPair)
+}
@@ -0,0 +1,945 @@
+//! Extended Kalman filter pose estimation.
+//!
+//! [`TrackingRig`](crate::TrackingRig) dead-reckons: every sensor error it
+//! integrates stays in the pose forever. The estimator in this module instead
+//! carries a 3×3 covariance alongside the pose and can be pulled back toward
+//! the truth by absolute measurements such as a GPS fix or a distance sensor
+//! reading against a known wall.
+//!
+//! - [`Ekf`]: the filter itself, with explicit `predict` and `correct` steps
+//! - [`EkfRig`]: a background task that feeds the filter from tracking wheels,
+//!   drive encoders and an IMU, and accepts corrections at any time
+//! - [`Measurement`]: the absolute observations the filter understands
+//!
+//! # Process Model
+//!
+//! Each update, the rig converts sensor deltas into a local displacement
+//! `(Δx, Δy)` and heading change `Δθ`:
+//!
+//! - `Δθ` fuses the IMU yaw rate (integrated over the loop period) with any
+//!   heading implied by parallel tracking wheels or the drive encoders,
+//!   weighted by their variances.
+//! - `Δx` fuses the vertical tracking wheels with the drive encoders.
+//! - `Δy` comes from horizontal tracking wheels, or is assumed zero with a
+//!   small lateral slip variance when there are none.
+//!
+//! The noise on each input grows with the distance travelled (or time elapsed
+//! for the gyro), so a stationary robot stays confident while a long drive
+//! becomes uncertain until the next correction.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let ekf = EkfRig::new(
+//!     Pose::default(),
+//!     [horizontal],
+//!     [left, right],
+//!     Some(&dt),
+//!     Some(imu),
+//!     EkfConfig::new(),
+//! );
+//!
+//! // Later, when a GPS reading arrives
+//! ekf.correct(Measurement::Pose {
+//!     pose: gps_pose,
+//!     position_std: QLength::from_centimeters(2.0),
+//!     heading_std: QAngle::from_degrees(2.0),
+//! });
+//! ```
+
+use crate::dt::differential::DifferentialDrive;
+use crate::hal::encoder::RotaryEncoder;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::odom::dist::Wall;
+use crate::odom::health::{TravelSource, WheelHealth, WheelStatus};
+use crate::odom::pose::Pose;
+use crate::odom::source::PoseSource;
+use crate::odom::wheel::{HeadingSource, TrackingWheel, find_parallel_forward_indices};
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, QLength, Vec2};
+use crate::util::utils::wrap_angle;
+use core::time::Duration;
+use heapless::Vec;
+use nalgebra::{Matrix3, SMatrix, SVector, Vector3};
+use std::cell::RefCell;
+use std::rc::Rc;
+use vexide_async::task::{Task, spawn};
+
+/// Smallest variance assigned to any input, to keep the filter well-conditioned.
+const MIN_VARIANCE: f64 = 1e-10;
+
+/// An absolute observation of the robot's state.
+///
+/// Standard deviations describe the sensor's uncertainty; smaller values pull
+/// the estimate harder toward the measurement.
+#[derive(Debug, Clone, Copy)]
+pub enum Measurement {
+    /// A full pose, such as from a GPS sensor.
+    Pose {
+        /// The measured pose
+        pose: Pose,
+        /// Standard deviation of each position axis
+        position_std: QLength,
+        /// Standard deviation of the heading
+        heading_std: QAngle,
+    },
+    /// A field position without heading.
+    Position {
+        /// The measured position in metres
+        position: Vec2<f64>,
+        /// Standard deviation of each axis
+        std_dev: QLength,
+    },
+    /// A heading without position.
+    Heading {
+        /// The measured heading (counter-clockwise positive)
+        heading: QAngle,
+        /// Standard deviation of the heading
+        std_dev: QAngle,
+    },
+    /// A distance sensor reading against a known wall.
+    WallDistance {
+        /// Sensor position and beam direction relative to the robot centre
+        /// (x forward, y left)
+        mount: Pose,
+        /// The wall the beam is expected to hit
+        wall: Wall,
+        /// The measured distance
+        distance: QLength,
+        /// Standard deviation of the reading
+        std_dev: QLength,
+    },
+}
+
+/// Noise parameters for the [`EkfRig`] process model.
+///
+/// Distance-based noise is given as a standard deviation per square-root metre
+/// travelled, so the variance grows linearly with distance. Use the `with_*`
+/// builder methods to adjust individual values.
+#[derive(Debug, Clone, Copy)]
+pub struct EkfConfig {
+    /// Tracking wheel noise in m/√m.
+    pub wheel_noise: f64,
+    /// Drive encoder noise in m/√m. Usually larger than `wheel_noise`, since
+    /// driven wheels slip.
+    pub drive_noise: f64,
+    /// Gyro yaw rate noise density in rad/√s.
+    pub gyro_noise: f64,
+    /// Unmeasured lateral slip in m/√m, used when there are no horizontal wheels.
+    pub lateral_noise: f64,
+    /// Initial standard deviation of each position axis.
+    pub position_std: QLength,
+    /// Initial standard deviation of the heading.
+    pub heading_std: QAngle,
+    /// Normalized innovation squared above which a measurement is rejected.
+    pub outlier_gate: f64,
+}
+
+impl EkfConfig {
+    /// Creates a configuration with defaults suited to a typical V5 robot.
+    pub const fn new() -> Self {
+        Self {
+            wheel_noise: 0.01,
+            drive_noise: 0.05,
+            gyro_noise: 0.002,
+            lateral_noise: 0.01,
+            position_std: QLength::from_centimeters(1.0),
+            heading_std: QAngle::from_degrees(1.0),
+            outlier_gate: 16.0,
+        }
+    }
+
+    /// Sets the tracking wheel noise in m/√m.
+    pub const fn with_wheel_noise(mut self, noise: f64) -> Self {
+        self.wheel_noise = noise;
+        self
+    }
+
+    /// Sets the drive encoder noise in m/√m.
+    pub const fn with_drive_noise(mut self, noise: f64) -> Self {
+        self.drive_noise = noise;
+        self
+    }
+
+    /// Sets the gyro noise density in rad/√s.
+    pub const fn with_gyro_noise(mut self, noise: f64) -> Self {
+        self.gyro_noise = noise;
+        self
+    }
+
+    /// Sets the lateral slip noise in m/√m.
+    pub const fn with_lateral_noise(mut self, noise: f64) -> Self {
+        self.lateral_noise = noise;
+        self
+    }
+
+    /// Sets the initial pose uncertainty.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Standard deviation of each position axis
+    /// * `heading` - Standard deviation of the heading
+    pub const fn with_initial_std_dev(mut self, position: QLength, heading: QAngle) -> Self {
+        self.position_std = position;
+        self.heading_std = heading;
+        self
+    }
+
+    /// Sets the outlier gate.
+    ///
+    /// Measurements whose normalized innovation squared exceeds `gate` are
+    /// rejected. Use `f64::INFINITY` to accept every measurement.
+    pub const fn with_outlier_gate(mut self, gate: f64) -> Self {
+        self.outlier_gate = gate;
+        self
+    }
+
+    /// Returns the initial covariance described by this configuration.
+    pub fn initial_covariance(&self) -> Matrix3<f64> {
+        let p = self.position_std.as_meters();
+        let h = self.heading_std.as_radians();
+        Matrix3::from_diagonal(&Vector3::new(p * p, p * p, h * h))
+    }
+}
+
+impl Default for EkfConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An extended Kalman filter over the robot pose `(x, y, θ)`.
+///
+/// The filter is independent of any hardware: [`predict`](Self::predict)
+/// takes a local displacement and its noise, and [`correct`](Self::correct)
+/// takes a [`Measurement`]. [`EkfRig`] drives it from real sensors.
+#[derive(Debug, Clone)]
+pub struct Ekf {
+    /// State vector `(x, y, θ)` in metres and radians.
+    state: Vector3<f64>,
+    /// State covariance.
+    covariance: Matrix3<f64>,
+    /// Normalized innovation squared rejection threshold.
+    gate: f64,
+}
+
+impl Ekf {
+    /// Creates a filter at the given pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose estimate
+    /// * `covariance` - The initial state covariance
+    pub fn new(origin: Pose, covariance: Matrix3<f64>) -> Self {
+        let position = origin.position();
+        Self {
+            state: Vector3::new(position.x, position.y, origin.heading().as_radians()),
+            covariance,
+            gate: EkfConfig::new().outlier_gate,
+        }
+    }
+
+    /// Sets the outlier gate (see [`EkfConfig::with_outlier_gate`]).
+    pub fn with_outlier_gate(mut self, gate: f64) -> Self {
+        self.gate = gate;
+        self
+    }
+
+    /// Returns the current pose estimate.
+    pub fn pose(&self) -> Pose {
+        Pose::new(
+            Vec2::new(self.state.x, self.state.y),
+            QAngle::from_radians(self.state.z),
+        )
+    }
+
+    /// Returns the current state covariance.
+    ///
+    /// Rows and columns are ordered `x`, `y`, `θ`, in metres and radians.
+    pub fn covariance(&self) -> Matrix3<f64> {
+        self.covariance
+    }
+
+    /// Replaces the estimate with a new pose and covariance.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The new pose estimate
+    /// * `covariance` - The new state covariance
+    pub fn reset(&mut self, pose: Pose, covariance: Matrix3<f64>) {
+        let gate = self.gate;
+        *self = Self::new(pose, covariance).with_outlier_gate(gate);
+    }
+
+    /// Advances the estimate by a motion measured in the robot frame.
+    ///
+    /// The displacement is applied along the mid-point heading, matching the
+    /// arc approximation used by [`TrackingRig`](crate::TrackingRig).
+    ///
+    /// # Arguments
+    ///
+    /// * `displacement` - Local displacement in metres (x forward, y left)
+    /// * `delta_heading` - Heading change (counter-clockwise positive)
+    /// * `noise` - Covariance of `(Δx, Δy, Δθ)`
+    pub fn predict(&mut self, displacement: Vec2<f64>, delta_heading: QAngle, noise: Matrix3<f64>) {
+        let (lx, ly) = (displacement.x, displacement.y);
+        let dtheta = delta_heading.as_radians();
+        let mid = self.state.z + dtheta * 0.5;
+        let (sin, cos) = (libm::sin(mid), libm::cos(mid));
+
+        let dx_dtheta = -lx * sin - ly * cos;
+        let dy_dtheta = lx * cos - ly * sin;
+
+        self.state += Vector3::new(lx * cos - ly * sin, lx * sin + ly * cos, dtheta);
+
+        #[rustfmt::skip]
+        let f = Matrix3::new(
+            1.0, 0.0, dx_dtheta,
+            0.0, 1.0, dy_dtheta,
+            0.0, 0.0, 1.0,
+        );
+        #[rustfmt::skip]
+        let g = Matrix3::new(
+            cos, -sin, 0.5 * dx_dtheta,
+            sin,  cos, 0.5 * dy_dtheta,
+            0.0,  0.0, 1.0,
+        );
+
+        self.covariance = f * self.covariance * f.transpose() + g * noise * g.transpose();
+    }
+
+    /// Fuses an absolute measurement into the estimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The observation to apply
+    ///
+    /// # Returns
+    ///
+    /// `true` if the measurement was applied. `false` if it was rejected by
+    /// the outlier gate, or if a wall distance beam would not hit its wall
+    /// from the current estimate.
+    pub fn correct(&mut self, measurement: &Measurement) -> bool {
+        match *measurement {
+            Measurement::Pose {
+                pose,
+                position_std,
+                heading_std,
+            } => {
+                let p = position_std.as_meters();
+                let h = heading_std.as_radians();
+                let z = Vector3::new(
+                    pose.position().x - self.state.x,
+                    pose.position().y - self.state.y,
+                    wrap_angle(pose.heading().as_radians() - self.state.z),
+                );
+                let r = Matrix3::from_diagonal(&Vector3::new(p * p, p * p, h * h));
+                self.update(z, Matrix3::identity(), r)
+            }
+            Measurement::Position { position, std_dev } => {
+                let s = std_dev.as_meters();
+                let z =
+                    SVector::<f64, 2>::new(position.x - self.state.x, position.y - self.state.y);
+                #[rustfmt::skip]
+                let h = SMatrix::<f64, 2, 3>::new(
+                    1.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0,
+                );
+                self.update(z, h, SMatrix::<f64, 2, 2>::identity() * (s * s))
+            }
+            Measurement::Heading { heading, std_dev } => {
+                let s = std_dev.as_radians();
+                let z = SVector::<f64, 1>::new(wrap_angle(heading.as_radians() - self.state.z));
+                let h = SMatrix::<f64, 1, 3>::new(0.0, 0.0, 1.0);
+                self.update(z, h, SMatrix::<f64, 1, 1>::new(s * s))
+            }
+            Measurement::WallDistance {
+                mount,
+                wall,
+                distance,
+                std_dev,
+            } => {
+                let Some((expected, h)) = self.wall_model(mount, wall) else {
+                    return false;
+                };
+                let s = std_dev.as_meters();
+                let z = SVector::<f64, 1>::new(distance.as_meters() - expected);
+                self.update(z, h, SMatrix::<f64, 1, 1>::new(s * s))
+            }
+        }
+    }
+
+    /// Predicted wall distance and its Jacobian with respect to the state.
+    ///
+    /// The wall is treated as an infinite line for the Jacobian, but the beam
+    /// must hit the actual segment for the reading to be used.
+    fn wall_model(&self, mount: Pose, wall: Wall) -> Option<(f64, SMatrix<f64, 1, 3>)> {
+        let theta = self.state.z;
+        let (sin, cos) = (libm::sin(theta), libm::cos(theta));
+        let (ox, oy) = (mount.position().x, mount.position().y);
+
+        let sensor = Vec2::new(
+            self.state.x + ox * cos - oy * sin,
+            self.state.y + ox * sin + oy * cos,
+        );
+        let beam = theta + mount.heading().as_radians();
+        let direction = Vec2::new(libm::cos(beam), libm::sin(beam));
+
+        let expected = wall
+            .ray_cast(sensor, QAngle::from_radians(beam))?
+            .as_meters();
+
+        let normal = wall.normal();
+        let facing = normal.dot(direction);
+        if facing.abs() < 1e-6 {
+            return None;
+        }
+
+        let gap = normal.dot(wall.start() - sensor);
+        let dsensor = Vec2::new(-ox * sin - oy * cos, ox * cos - oy * sin);
+        let dtheta = (-normal.dot(dsensor) * facing - gap * normal.dot(direction.perp()))
+            / (facing * facing);
+
+        Some((
+            expected,
+            SMatrix::<f64, 1, 3>::new(-normal.x / facing, -normal.y / facing, dtheta),
+        ))
+    }
+
+    /// Standard EKF update with a Joseph-form covariance step.
+    fn update<const D: usize>(
+        &mut self,
+        innovation: SVector<f64, D>,
+        h: SMatrix<f64, D, 3>,
+        r: SMatrix<f64, D, D>,
+    ) -> bool {
+        let s = h * self.covariance * h.transpose() + r;
+        let Some(s_inv) = s.try_inverse() else {
+            return false;
+        };
+
+        let nis = (innovation.transpose() * s_inv * innovation)[(0, 0)];
+        // NaN innovations fail this check too.
+        if nis.is_nan() || nis > self.gate {
+            return false;
+        }
+
+        let k = self.covariance * h.transpose() * s_inv;
+        self.state += k * innovation;
+
+        let i_kh = Matrix3::identity() - k * h;
+        self.covariance = i_kh * self.covariance * i_kh.transpose() + k * r * k.transpose();
+
+        true
+    }
+}
+
+/// An odometry rig that estimates pose with an [`Ekf`].
+///
+/// Like [`TrackingRig`](crate::TrackingRig), `EkfRig` runs a background task
+/// at approximately 100Hz that reads tracking wheels and an optional IMU. It
+/// additionally reads the drivetrain's motor encoders, and accepts absolute
+/// [`Measurement`]s at any time through [`correct`](Self::correct).
+///
+/// Tracking wheels are only checked for failed reads:
+/// [`wheel_status`](Self::wheel_status) reports them as
+/// [`Disconnected`](WheelHealth::Disconnected), never stale or implausible.
+///
+/// The task stops when the rig is dropped.
+pub struct EkfRig {
+    data: Rc<RefCell<EkfData>>,
+    _task: Task<()>,
+}
+
+impl EkfRig {
+    /// Creates a new EKF rig and starts the background estimation task.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose of the robot
+    /// * `horizontal` - Horizontal tracking wheels (maximum of 2)
+    /// * `vertical` - Vertical tracking wheels (maximum of 2)
+    /// * `drive` - Optional drivetrain whose motor encoders to read. Pass
+    ///   `None::<&DifferentialDrive>` to use tracking wheels alone.
+    /// * `imu` - Optional heading sensor, used for its yaw rate
+    /// * `config` - Process noise configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - More than 2 horizontal or vertical tracking wheels are provided
+    /// - Neither vertical wheels nor drive encoders are provided
+    /// - No IMU, parallel vertical wheels or drive encoders exist to measure heading
+    pub fn new<const N: usize, const U: usize, E, M, G>(
+        origin: Pose,
+        horizontal: [TrackingWheel<E>; N],
+        vertical: [TrackingWheel<E>; U],
+        drive: Option<&DifferentialDrive<M>>,
+        imu: Option<G>,
+        config: EkfConfig,
+    ) -> Self
+    where
+        E: RotaryEncoder + 'static,
+        M: MotorOutput + 'static,
+        G: Gyro + 'static,
+    {
+        Self::with_clock(
+            origin,
+            horizontal,
+            vertical,
+            drive,
+            imu,
+            config,
+            SystemClock::new(),
+        )
+    }
+
+    /// Creates a new EKF rig whose background task runs on the given clock.
+    ///
+    /// See [`EkfRig::new`] for the other arguments and panics.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for the update loop and gyro integration
+    pub fn with_clock<const N: usize, const U: usize, E, M, G, C>(
+        origin: Pose,
+        horizontal: [TrackingWheel<E>; N],
+        vertical: [TrackingWheel<E>; U],
+        drive: Option<&DifferentialDrive<M>>,
+        imu: Option<G>,
+        config: EkfConfig,
+        clock: C,
+    ) -> Self
+    where
+        E: RotaryEncoder + 'static,
+        M: MotorOutput + 'static,
+        G: Gyro + 'static,
+        C: Clock + 'static,
+    {
+        const {
+            assert!(N <= 2 && U <= 2, "cannot have over 2 tracking wheels each");
+        }
+
+        let vertical: Vec<TrackingWheel<E>, 2> = Vec::from_array(vertical);
+        let parallel = find_parallel_forward_indices(&vertical);
+
+        assert!(
+            !vertical.is_empty() || drive.is_some(),
+            "vertical tracking wheels or drive encoders are required to measure travel"
+        );
+        assert!(
+            imu.is_some() || parallel.is_some() || drive.is_some(),
+            "a gyro, two parallel forward wheels or drive encoders are required to determine heading"
+        );
+
+        let sensors = Sensors {
+            horizontal: Channel::new(Vec::from_array(horizontal)),
+            vertical: Channel::new(vertical),
+            drive: drive.map(|dt| DriveChannel {
+                dt: dt.clone(),
+                previous: None,
+            }),
+            parallel,
+            imu,
+        };
+
+        let data = Rc::new(RefCell::new(EkfData {
+            filter: Ekf::new(origin, config.initial_covariance())
+                .with_outlier_gate(config.outlier_gate),
+            linear_velocity: 0.0,
+            angular_velocity: 0.0,
+            heading_source: if sensors.imu.is_some() {
+                HeadingSource::Imu
+            } else {
+                HeadingSource::Wheels
+            },
+            travel_source: if sensors.vertical.wheels.is_empty() {
+                TravelSource::Drive
+            } else {
+                TravelSource::Wheels
+            },
+            wheels: sensors.status(),
+        }));
+
+        let task_data = Rc::clone(&data);
+        let task = spawn(async move {
+            Self::task(sensors, config, task_data, clock).await;
+        });
+
+        Self { data, _task: task }
+    }
+
+    /// Returns the latest pose estimate.
+    pub fn pose(&self) -> Pose {
+        self.data.borrow().filter.pose()
+    }
+
+    /// Returns the latest state covariance (`x`, `y`, `θ`).
+    pub fn covariance(&self) -> Matrix3<f64> {
+        self.data.borrow().filter.covariance()
+    }
+
+    /// Returns the latest forward velocity estimate in m/s.
+    pub fn linear_velocity(&self) -> f64 {
+        self.data.borrow().linear_velocity
+    }
+
+    /// Returns the latest angular velocity estimate in rad/s.
+    pub fn angular_velocity(&self) -> f64 {
+        self.data.borrow().angular_velocity
+    }
+
+    /// Returns the sensor the latest heading change was measured with.
+    ///
+    /// [`HeadingSource::Wheels`] means parallel vertical wheels or the drive
+    /// sides were used without the IMU.
+    pub fn heading_source(&self) -> HeadingSource {
+        self.data.borrow().heading_source
+    }
+
+    /// Returns where the latest forward travel was taken from.
+    ///
+    /// [`TravelSource::Drive`] means no vertical wheel could be read and only
+    /// the motor encoders were used.
+    pub fn travel_source(&self) -> TravelSource {
+        self.data.borrow().travel_source
+    }
+
+    /// Returns the health of each tracking wheel.
+    ///
+    /// Vertical wheels come first, then horizontal wheels, each in the order
+    /// they were passed to the constructor.
+    pub fn wheel_status(&self) -> std::vec::Vec<WheelStatus> {
+        self.data.borrow().wheels.to_vec()
+    }
+
+    /// Moves the estimate to a new pose, keeping the current covariance.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's new pose
+    pub fn set_pose(&self, pose: Pose) {
+        let mut data = self.data.borrow_mut();
+        let covariance = data.filter.covariance();
+        data.filter.reset(pose, covariance);
+    }
+
+    /// Fuses an absolute measurement into the estimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `measurement` - The observation to apply
+    ///
+    /// # Returns
+    ///
+    /// `true` if the measurement was applied, `false` if it was rejected
+    /// (see [`Ekf::correct`]).
+    pub fn correct(&self, measurement: Measurement) -> bool {
+        self.data.borrow_mut().filter.correct(&measurement)
+    }
+
+    /// Background task that feeds sensor deltas into the filter.
+    async fn task<E: RotaryEncoder, M: MotorOutput, G: Gyro, C: Clock>(
+        mut sensors: Sensors<E, M, G>,
+        config: EkfConfig,
+        data: Rc<RefCell<EkfData>>,
+        clock: C,
+    ) {
+        let mut prev_time = clock.now();
+
+        loop {
+            clock.sleep(Duration::from_millis(10)).await;
+
+            let now = clock.now();
+            let dt = now.saturating_sub(prev_time).as_secs_f64();
+            prev_time = now;
+
+            let vertical_deltas = sensors.vertical.deltas();
+            let drive_deltas = match sensors.drive.as_mut() {
+                Some(drive) => drive.deltas().await,
+                None => None,
+            };
+            let readable = |deltas: &[Option<(f64, f64)>]| -> Vec<(f64, f64), 2> {
+                deltas.iter().flatten().copied().collect()
+            };
+            let vertical = readable(&vertical_deltas);
+            let horizontal = readable(&sensors.horizontal.deltas());
+            let drive = drive_deltas.as_ref().map_or(&[][..], |deltas| &deltas[..]);
+
+            let gyro_rate = sensors
+                .imu
+                .as_ref()
+                .and_then(|imu| imu.gyro_rate().ok())
+                .map(f64::to_radians);
+
+            // Heading change: fuse every available source by inverse variance.
+            let mut heading = Fusion::default();
+            let mut heading_source = HeadingSource::Unavailable;
+            if let Some((Some(left), Some(right))) =
+                sensors.parallel.map(|(l, r)| (vertical_deltas[l], vertical_deltas[r]))
+            {
+                heading.add_differential(left, right, config.wheel_noise);
+                heading_source = HeadingSource::Wheels;
+            }
+            if let Some([left, right]) = drive_deltas {
+                heading.add_differential(left, right, config.drive_noise);
+                heading_source = HeadingSource::Wheels;
+            }
+            if let Some(rate) = gyro_rate {
+                heading.add(rate * dt, config.gyro_noise * config.gyro_noise * dt);
+                heading_source = HeadingSource::Imu;
+            }
+            let (dtheta, var_theta) = heading.result().unwrap_or_else(|| {
+                let previous = data.borrow().angular_velocity;
+                (previous * dt, dt)
+            });
+
+            // Forward travel: tracking wheels and drive encoders.
+            let mut forward = Fusion::default();
+            forward.add_mean(&vertical, dtheta, config.wheel_noise);
+            forward.add_mean(drive, dtheta, config.drive_noise);
+            let (lx, var_x) = forward.result().unwrap_or((0.0, MIN_VARIANCE));
+
+            // Lateral travel: horizontal wheels, or assume no sideways motion.
+            let mut lateral = Fusion::default();
+            lateral.add_mean(&horizontal, dtheta, config.wheel_noise);
+            let (ly, var_y) = lateral
+                .result()
+                .unwrap_or((0.0, config.lateral_noise * config.lateral_noise * lx.abs()));
+
+            let noise = Matrix3::from_diagonal(&Vector3::new(
+                var_x.max(MIN_VARIANCE),
+                var_y.max(MIN_VARIANCE),
+                var_theta.max(MIN_VARIANCE),
+            ));
+
+            let mut state = data.borrow_mut();
+            state
+                .filter
+                .predict(Vec2::new(lx, ly), QAngle::from_radians(dtheta), noise);
+
+            if dt > 0.0 {
+                state.linear_velocity = lx / dt;
+                state.angular_velocity = gyro_rate.unwrap_or(dtheta / dt);
+            }
+            state.heading_source = heading_source;
+            state.travel_source = if !vertical.is_empty() {
+                TravelSource::Wheels
+            } else if !drive.is_empty() {
+                TravelSource::Drive
+            } else {
+                TravelSource::Unavailable
+            };
+            state.wheels = sensors.status();
+        }
+    }
+}
+
+impl PoseSource for EkfRig {
+    fn pose(&self) -> Pose {
+        EkfRig::pose(self)
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        EkfRig::linear_velocity(self)
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        EkfRig::angular_velocity(self)
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        EkfRig::set_pose(self, pose);
+    }
+
+    fn wheel_status(&self) -> std::vec::Vec<WheelStatus> {
+        EkfRig::wheel_status(self)
+    }
+
+    fn heading_source(&self) -> Option<HeadingSource> {
+        Some(EkfRig::heading_source(self))
+    }
+
+    fn travel_source(&self) -> Option<TravelSource> {
+        Some(EkfRig::travel_source(self))
+    }
+}
+
+/// State shared between an [`EkfRig`] and its background task.
+#[derive(Debug, Clone)]
+struct EkfData {
+    /// The filter
+    filter: Ekf,
+    /// Current linear velocity (m/s)
+    linear_velocity: f64,
+    /// Current angular velocity (rad/s)
+    angular_velocity: f64,
+    /// Sensor the latest heading change was measured with
+    heading_source: HeadingSource,
+    /// Where the latest forward travel was taken from
+    travel_source: TravelSource,
+    /// Health of each tracking wheel, vertical wheels first
+    wheels: Vec<WheelStatus, 4>,
+}
+
+/// Sensors owned by the [`EkfRig`] task.
+struct Sensors<E: RotaryEncoder, M: MotorOutput, G: Gyro> {
+    horizontal: Channel<E>,
+    vertical: Channel<E>,
+    drive: Option<DriveChannel<M>>,
+    parallel: Option<(usize, usize)>,
+    imu: Option<G>,
+}
+
+impl<E: RotaryEncoder, M: MotorOutput, G: Gyro> Sensors<E, M, G> {
+    /// Returns the health of each tracking wheel, vertical wheels first.
+    fn status(&self) -> Vec<WheelStatus, 4> {
+        self.vertical
+            .status
+            .iter()
+            .chain(&self.horizontal.status)
+            .copied()
+            .collect()
+    }
+}
+
+/// A set of wheels and their previous readings.
+struct Channel<E: RotaryEncoder> {
+    wheels: Vec<TrackingWheel<E>, 2>,
+    /// Previous reading of each wheel, `None` after a failed read
+    previous: Vec<Option<f64>, 2>,
+    /// Health of each wheel at its latest reading
+    status: Vec<WheelStatus, 2>,
+}
+
+impl<E: RotaryEncoder> Channel<E> {
+    fn new(wheels: Vec<TrackingWheel<E>, 2>) -> Self {
+        let previous = wheels
+            .iter()
+            .map(|w| w.distance().ok().map(|d| d.as_meters()))
+            .collect();
+        let status = wheels.iter().map(|_| WheelStatus::default()).collect();
+        Self {
+            wheels,
+            previous,
+            status,
+        }
+    }
+
+    /// Returns `(travel since last call, offset)` for each wheel in metres.
+    ///
+    /// A wheel that cannot be read, or is read for the first time since it
+    /// failed, reports `None`.
+    fn deltas(&mut self) -> Vec<Option<(f64, f64)>, 2> {
+        self.wheels
+            .iter()
+            .zip(self.previous.iter_mut())
+            .zip(self.status.iter_mut())
+            .map(|((wheel, previous), status)| {
+                let travel = wheel.distance().ok().map(|d| d.as_meters());
+                let delta = travel.zip(*previous).map(|(now, then)| now - then);
+                *previous = travel;
+
+                if travel.is_some() {
+                    status.health = WheelHealth::Healthy;
+                } else {
+                    status.health = WheelHealth::Disconnected;
+                    status.failures += 1;
+                }
+                status.used = delta.is_some();
+
+                delta.map(|delta| (delta, wheel.offset().as_meters()))
+            })
+            .collect()
+    }
+}
+
+/// The drivetrain's motor encoders and their previous reading.
+struct DriveChannel<M: MotorOutput> {
+    dt: DifferentialDrive<M>,
+    /// Previous `(left, right)` travel, `None` before the first reading and
+    /// after a failed one
+    previous: Option<(f64, f64)>,
+}
+
+impl<M: MotorOutput> DriveChannel<M> {
+    /// Returns `[left, right]` as `(travel since last call, offset)` in
+    /// metres.
+    ///
+    /// Like [`Channel::deltas`], the sides report `None` when they cannot be
+    /// read and on the first reading after a failure.
+    async fn deltas(&mut self) -> Option<[(f64, f64); 2]> {
+        let travel = self
+            .dt
+            .travel()
+            .await
+            .ok()
+            .map(|(left, right)| (left.as_meters(), right.as_meters()));
+        let delta = travel.zip(self.previous);
+        self.previous = travel;
+
+        let half_width = self.dt.width().as_meters() / 2.0;
+        delta.map(|((left, right), (prev_left, prev_right))| {
+            [
+                (left - prev_left, -half_width),
+                (right - prev_right, half_width),
+            ]
+        })
+    }
+}
+
+/// Inverse-variance weighted combination of independent estimates.
+#[derive(Debug, Default)]
+struct Fusion {
+    weighted: f64,
+    weight: f64,
+}
+
+impl Fusion {
+    fn add(&mut self, value: f64, variance: f64) {
+        let w = 1.0 / variance.max(MIN_VARIANCE);
+        self.weighted += value * w;
+        self.weight += w;
+    }
+
+    /// Adds the heading change implied by two parallel wheels.
+    fn add_differential(&mut self, left: (f64, f64), right: (f64, f64), noise: f64) {
+        let width = right.1 - left.1;
+        if width.abs() < 1e-6 {
+            return;
+        }
+        let variance = noise * noise * (left.0.abs() + right.0.abs()) / (width * width);
+        self.add((right.0 - left.0) / width, variance);
+    }
+
+    /// Adds the mean arc-corrected local travel of a set of wheels.
+    fn add_mean(&mut self, wheels: &[(f64, f64)], dtheta: f64, noise: f64) {
+        if wheels.is_empty() {
+            return;
+        }
+        let n = wheels.len() as f64;
+        let travel: f64 = wheels
+            .iter()
+            .map(|&(delta, offset)| arc_local(delta, offset, dtheta))
+            .sum();
+        let variance: f64 =
+            wheels.iter().map(|(delta, _)| delta.abs()).sum::<f64>() * noise * noise / (n * n);
+        self.add(travel / n, variance);
+    }
+
+    fn result(&self) -> Option<(f64, f64)> {
+        (self.weight > 0.0).then(|| (self.weighted / self.weight, 1.0 / self.weight))
+    }
+}
+
+/// Local travel of the robot centre implied by a wheel at `offset`.
+///
+/// Uses the same chord approximation as [`TrackingRig`](crate::TrackingRig).
+fn arc_local(delta: f64, offset: f64, dtheta: f64) -> f64 {
+    if dtheta == 0.0 {
+        delta
+    } else {
+        2.0 * libm::sin(dtheta / 2.0) * (delta / dtheta - offset)
+    }
+}
@@ -0,0 +1,437 @@
+//! VEX GPS sensor as an absolute pose source.
+//!
+//! The V5 GPS sensor reads the position strips on the field perimeter and
+//! reports where it is on the field, so unlike wheel odometry its error does
+//! not grow over time. Its readings are noisier than odometry over short
+//! distances and drop out near walls, so this module offers two ways to use
+//! it:
+//!
+//! - **Replace**: [`GpsRig`] without odometry reports the latest good GPS pose
+//!   directly.
+//! - **Reset**: [`GpsRig`] wrapping a [`TrackingRig`] reports the smooth
+//!   odometry pose, and periodically re-anchors it on the latest good GPS
+//!   pose to remove accumulated drift.
+//!
+//! For finer control, [`GpsReader`] converts and validates single readings
+//! without a background task, for example to feed
+//! [`EkfRig::correct`](crate::odom::ekf::EkfRig::correct).
+//!
+//! # Frames
+//!
+//! The GPS measures the sensor's pose in its own field frame: metres from the
+//! field centre, with the heading clockwise from the positive y-axis. Readings
+//! are converted to the [`Pose`] convention (heading counter-clockwise from the
+//! positive x-axis), moved from the sensor to the robot centre using the
+//! mounting offset, and finally expressed relative to
+//! [`GpsConfig::origin`], the pose of your odometry frame's origin in GPS
+//! field coordinates.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Create the sensor with a zero offset; the mount is applied here.
+//! let gps = GpsSensor::new(peripherals.port_1, Point2 { x: 0.0, y: 0.0 }, Point2 { x: 0.0, y: 0.0 }, 0.0);
+//!
+//! // Sensor 10 cm behind centre, facing backward
+//! let mount = Pose::new(Vec2::new(-0.1, 0.0), QAngle::from_degrees(180.0));
+//!
+//! let gps = GpsRig::new(GpsReader::new(gps, mount, GpsConfig::new()), Some(rig));
+//! let chassis = OdomChassis::new(dt, imu, None).with_pose_source(gps);
+//! ```
+
+use crate::hal::gps::Gps;
+use crate::odom::pose::Pose;
+use crate::odom::source::PoseSource;
+use crate::odom::wheel::TrackingRig;
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, QLength, Vec2};
+use core::f64::consts::{FRAC_PI_2, TAU};
+use core::time::Duration;
+use std::cell::RefCell;
+use std::rc::Rc;
+use vexide::smart::PortError;
+use vexide_async::task::{Task, spawn};
+
+/// Time constant of the low-pass filter on velocities derived from GPS fixes.
+const VELOCITY_TIME_CONSTANT: f64 = 0.05;
+
+/// Settings for reading a GPS sensor.
+///
+/// Use the `with_*` builder methods to adjust individual values.
+#[derive(Debug, Clone, Copy)]
+pub struct GpsConfig {
+    /// Readings whose reported error exceeds this are rejected.
+    pub max_error: QLength,
+    /// How often a wrapped tracking rig is moved onto the GPS pose.
+    pub reset_interval: Duration,
+    /// Pose of the odometry frame's origin in GPS field coordinates.
+    pub origin: Pose,
+}
+
+impl GpsConfig {
+    /// Creates a configuration that accepts readings within 5 cm, resets
+    /// odometry every second, and uses the GPS field frame unchanged.
+    pub fn new() -> Self {
+        Self {
+            max_error: QLength::from_centimeters(5.0),
+            reset_interval: Duration::from_secs(1),
+            origin: Pose::default(),
+        }
+    }
+
+    /// Sets the largest reported error that is accepted.
+    pub const fn with_max_error(mut self, error: QLength) -> Self {
+        self.max_error = error;
+        self
+    }
+
+    /// Sets how often a wrapped tracking rig is reset.
+    pub const fn with_reset_interval(mut self, interval: Duration) -> Self {
+        self.reset_interval = interval;
+        self
+    }
+
+    /// Sets the pose of the odometry frame's origin in GPS field coordinates.
+    ///
+    /// For example, if odometry treats a corner of the field as `(0, 0)`,
+    /// pass that corner's GPS coordinates here.
+    pub const fn with_origin(mut self, origin: Pose) -> Self {
+        self.origin = origin;
+        self
+    }
+}
+
+impl Default for GpsConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A validated GPS reading.
+#[derive(Debug, Clone, Copy)]
+pub struct GpsFix {
+    /// Pose of the robot centre in the odometry frame
+    pub pose: Pose,
+    /// Position error reported by the sensor
+    pub error: QLength,
+}
+
+/// Reasons a GPS reading was not usable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpsError {
+    /// The sensor could not be read.
+    Port(PortError),
+    /// The sensor reported an error above the configured limit.
+    PoorQuality,
+}
+
+impl From<PortError> for GpsError {
+    fn from(error: PortError) -> Self {
+        Self::Port(error)
+    }
+}
+
+/// Converts and validates readings from a GPS sensor.
+#[derive(Debug)]
+pub struct GpsReader<G: Gps> {
+    gps: G,
+    mount: Pose,
+    config: GpsConfig,
+}
+
+impl<G: Gps> GpsReader<G> {
+    /// Creates a reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `gps` - The GPS sensor, configured with a zero offset
+    /// * `mount` - Sensor position and facing relative to the robot centre
+    ///   (x forward, y left, heading counter-clockwise from forward)
+    /// * `config` - Quality limit and field frame
+    pub fn new(gps: G, mount: Pose, config: GpsConfig) -> Self {
+        Self { gps, mount, config }
+    }
+
+    /// Returns the configuration.
+    pub fn config(&self) -> GpsConfig {
+        self.config
+    }
+
+    /// Returns the underlying sensor.
+    pub fn gps(&self) -> &G {
+        &self.gps
+    }
+
+    /// Reads the robot pose from the sensor.
+    ///
+    /// # Returns
+    ///
+    /// The robot pose in the odometry frame, or a [`GpsError`] if the sensor
+    /// could not be read or reported too large an error.
+    pub fn read(&self) -> Result<GpsFix, GpsError> {
+        let error = self.gps.error()?;
+        if error.as_meters().is_nan() || error.as_meters() > self.config.max_error.as_meters() {
+            return Err(GpsError::PoorQuality);
+        }
+
+        let sensor = from_gps_frame(self.gps.position()?, self.gps.heading()?);
+        let robot = sensor_to_robot(sensor, self.mount);
+
+        Ok(GpsFix {
+            pose: to_frame(robot, self.config.origin),
+            error,
+        })
+    }
+}
+
+/// Converts a raw GPS reading to the [`Pose`] convention.
+///
+/// # Arguments
+///
+/// * `position` - Position in metres from the field centre
+/// * `heading` - Clockwise heading with zero facing the positive y-axis
+///
+/// # Returns
+///
+/// The same pose with the heading counter-clockwise from the positive x-axis.
+pub fn from_gps_frame(position: Vec2<f64>, heading: QAngle) -> Pose {
+    let theta = libm::remainder(FRAC_PI_2 - heading.as_radians(), TAU);
+    Pose::new(position, QAngle::from_radians(theta))
+}
+
+/// Returns the pose of the robot centre given the pose of a sensor mounted on it.
+fn sensor_to_robot(sensor: Pose, mount: Pose) -> Pose {
+    let theta = sensor.heading() - mount.heading();
+    let (sin, cos) = (theta.sin(), theta.cos());
+    let (ox, oy) = (mount.position().x, mount.position().y);
+
+    Pose::new(
+        sensor.position() - Vec2::new(ox * cos - oy * sin, ox * sin + oy * cos),
+        theta,
+    )
+}
+
+/// Places a pose given relative to `origin` back in the field frame.
+fn from_frame(pose: Pose, origin: Pose) -> Pose {
+    let theta = origin.heading();
+    let (sin, cos) = (theta.sin(), theta.cos());
+    let d = pose.position();
+
+    Pose::new(
+        origin.position() + Vec2::new(d.x * cos - d.y * sin, d.x * sin + d.y * cos),
+        QAngle::from_radians(libm::remainder((pose.heading() + theta).as_radians(), TAU)),
+    )
+}
+
+/// Expresses a field pose relative to another pose.
+fn to_frame(pose: Pose, origin: Pose) -> Pose {
+    let theta = origin.heading();
+    let (sin, cos) = (theta.sin(), theta.cos());
+    let d = pose.position() - origin.position();
+
+    Pose::new(
+        Vec2::new(d.x * cos + d.y * sin, -d.x * sin + d.y * cos),
+        QAngle::from_radians(libm::remainder((pose.heading() - theta).as_radians(), TAU)),
+    )
+}
+
+/// A GPS sensor running in the background as a [`PoseSource`].
+///
+/// Without odometry, the reported pose is the latest accepted GPS fix and
+/// velocities are estimated from successive fixes. With a [`TrackingRig`],
+/// the reported velocities are the rig's, and the reported pose is the rig's
+/// motion since the last reset, starting from the fix accepted at that reset.
+/// Resets happen every [`GpsConfig::reset_interval`].
+///
+/// The task runs at approximately 100Hz and stops when the rig is dropped.
+pub struct GpsRig {
+    odometry: Option<Rc<TrackingRig>>,
+    data: Rc<RefCell<GpsData>>,
+    _task: Task<()>,
+}
+
+impl GpsRig {
+    /// Creates a GPS rig and starts the background task.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The GPS sensor and its mounting offset
+    /// * `odometry` - Optional tracking rig to reset; `None` uses the GPS alone
+    pub fn new<G: Gps + 'static>(reader: GpsReader<G>, odometry: Option<TrackingRig>) -> Self {
+        Self::with_clock(reader, odometry, SystemClock::new())
+    }
+
+    /// Creates a GPS rig whose background task runs on the given clock.
+    ///
+    /// See [`GpsRig::new`] for the other arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for the update loop and reset interval
+    pub fn with_clock<G: Gps + 'static, C: Clock + 'static>(
+        reader: GpsReader<G>,
+        odometry: Option<TrackingRig>,
+        clock: C,
+    ) -> Self {
+        let odometry = odometry.map(Rc::new);
+        let initial = reader.read().ok();
+
+        let data = Rc::new(RefCell::new(GpsData {
+            pose: initial.map_or_else(
+                || odometry.as_ref().map(|rig| rig.pose()).unwrap_or_default(),
+                |fix| fix.pose,
+            ),
+            last_fix: initial,
+            anchor: None,
+            linear_velocity: 0.0,
+            angular_velocity: 0.0,
+            rejected: 0,
+        }));
+
+        let task_odometry = odometry.clone();
+        let task_data = Rc::clone(&data);
+        let task = spawn(async move {
+            Self::task(reader, task_odometry, task_data, clock).await;
+        });
+
+        Self {
+            odometry,
+            data,
+            _task: task,
+        }
+    }
+
+    /// Returns the latest pose estimate.
+    pub fn pose(&self) -> Pose {
+        let data = self.data.borrow();
+        match (self.odometry.as_ref(), data.anchor) {
+            (Some(rig), Some((odometry, fix))) => from_frame(to_frame(rig.pose(), odometry), fix),
+            (Some(rig), None) => rig.pose(),
+            (None, _) => data.pose,
+        }
+    }
+
+    /// Moves the estimate to a new pose.
+    ///
+    /// With a tracking rig, the estimate is re-anchored on the new pose until
+    /// the next periodic reset. Without one, the pose is reported until the
+    /// next accepted GPS fix replaces it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's new pose
+    pub fn set_pose(&self, pose: Pose) {
+        let mut data = self.data.borrow_mut();
+        match self.odometry.as_ref() {
+            Some(rig) => data.anchor = Some((rig.pose(), pose)),
+            None => data.pose = pose,
+        }
+    }
+
+    /// Returns the latest accepted GPS fix, if any.
+    pub fn last_fix(&self) -> Option<GpsFix> {
+        self.data.borrow().last_fix
+    }
+
+    /// Returns the number of readings rejected since the rig was created.
+    pub fn rejected(&self) -> u32 {
+        self.data.borrow().rejected
+    }
+
+    /// Returns the wrapped tracking rig, if any.
+    pub fn odometry(&self) -> Option<&TrackingRig> {
+        self.odometry.as_deref()
+    }
+
+    /// Background task that reads the sensor and resets the odometry.
+    async fn task<G: Gps, C: Clock>(
+        reader: GpsReader<G>,
+        odometry: Option<Rc<TrackingRig>>,
+        data: Rc<RefCell<GpsData>>,
+        clock: C,
+    ) {
+        let interval = reader.config().reset_interval;
+        let mut prev_time = clock.now();
+        let mut last_reset = prev_time;
+
+        loop {
+            clock.sleep(Duration::from_millis(10)).await;
+
+            let now = clock.now();
+            let mut state = data.borrow_mut();
+            let fix = match reader.read() {
+                Ok(fix) => fix,
+                Err(_) => {
+                    state.rejected = state.rejected.saturating_add(1);
+                    continue;
+                }
+            };
+
+            // The pose moved since the last accepted fix, not since the last
+            // attempt.
+            let dt = now.saturating_sub(prev_time).as_secs_f64();
+            prev_time = now;
+
+            if dt > 0.0 {
+                let previous = state.pose;
+                let theta = previous.heading();
+                let d = fix.pose.position() - previous.position();
+                let forward = (d.x * theta.cos() + d.y * theta.sin()) / dt;
+                let turn = libm::remainder((fix.pose.heading() - theta).as_radians(), TAU) / dt;
+
+                let alpha = dt / (VELOCITY_TIME_CONSTANT + dt);
+                state.linear_velocity += alpha * (forward - state.linear_velocity);
+                state.angular_velocity += alpha * (turn - state.angular_velocity);
+            }
+
+            state.pose = fix.pose;
+            state.last_fix = Some(fix);
+
+            if let Some(rig) = odometry.as_ref()
+                && now.saturating_sub(last_reset) >= interval
+            {
+                state.anchor = Some((rig.pose(), fix.pose));
+                last_reset = now;
+            }
+        }
+    }
+}
+
+impl PoseSource for GpsRig {
+    fn pose(&self) -> Pose {
+        GpsRig::pose(self)
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        match self.odometry.as_ref() {
+            Some(rig) => rig.linear_velocity(),
+            None => self.data.borrow().linear_velocity,
+        }
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        match self.odometry.as_ref() {
+            Some(rig) => rig.angular_velocity(),
+            None => self.data.borrow().angular_velocity,
+        }
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        GpsRig::set_pose(self, pose);
+    }
+}
+
+/// State shared between [`GpsRig`] and its background task.
+#[derive(Debug)]
+struct GpsData {
+    /// Latest accepted GPS pose
+    pose: Pose,
+    last_fix: Option<GpsFix>,
+    /// Odometry pose and GPS fix at the latest reset
+    anchor: Option<(Pose, Pose)>,
+    /// Filtered forward velocity from successive fixes (m/s)
+    linear_velocity: f64,
+    /// Filtered angular velocity from successive fixes (rad/s)
+    angular_velocity: f64,
+    rejected: u32,
+}
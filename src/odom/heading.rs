@@ -0,0 +1,393 @@
+//! Heading fusion from several inertial sensors.
+//!
+//! A single V5 inertial sensor has two well-known weaknesses: a scale error of
+//! a few degrees per full turn, and a slow drift while the robot sits still.
+//! It can also simply stop responding when its cable is knocked loose.
+//! [`ImuFusion`] combines two or more sensors into one [`Gyro`] that:
+//!
+//! - corrects each sensor's scale error with a per-sensor factor,
+//! - averages the sensors' heading changes, discarding a sensor whose change
+//!   disagrees with the others,
+//! - learns the gyro bias whenever the robot is still and removes it from the
+//!   heading, and
+//! - keeps reporting from the remaining sensors when one drops out, recording
+//!   the dropout in [`ImuFusion::status`].
+//!
+//! Because it implements [`Gyro`], an `ImuFusion` can be passed anywhere a
+//! single sensor can, including [`TrackingRig`](crate::TrackingRig).
+//!
+//! # Example
+//!
+//! ```ignore
+//! // This sensor reads 357° per full turn.
+//! let imus = ImuFusion::new(
+//!     [(imu_a, 360.0 / 357.0), (imu_b, 1.0)],
+//!     ImuFusionConfig::new(),
+//! );
+//! let rig = TrackingRig::new(Pose::default(), [horizontal], [left, right], Some(imus));
+//! ```
+
+use crate::hal::imu::Gyro;
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::QAngle;
+use core::f64::consts::TAU;
+use core::time::Duration;
+use std::cell::RefCell;
+use vexide::smart::PortError;
+use vexide::smart::imu::InertialError;
+
+/// Settings for fusing inertial sensors.
+///
+/// Use the `with_*` builder methods to adjust individual values.
+#[derive(Debug, Clone, Copy)]
+pub struct ImuFusionConfig {
+    /// Largest difference between one sensor's rate and the others' before it
+    /// is discarded as an outlier (deg/s).
+    pub outlier_rate: f64,
+    /// Rates below this count as the robot being still (deg/s).
+    pub stationary_rate: f64,
+    /// How long the robot must be still before the bias estimate updates.
+    pub stationary_time: Duration,
+    /// Time constant of the bias estimate's low-pass filter.
+    pub bias_time_constant: Duration,
+}
+
+impl ImuFusionConfig {
+    /// Creates a configuration that discards sensors 20°/s away from the
+    /// others, and learns bias after 0.5 s below 1°/s with a 2 s time constant.
+    pub const fn new() -> Self {
+        Self {
+            outlier_rate: 20.0,
+            stationary_rate: 1.0,
+            stationary_time: Duration::from_millis(500),
+            bias_time_constant: Duration::from_secs(2),
+        }
+    }
+
+    /// Sets the rate disagreement above which a sensor is discarded (deg/s).
+    pub const fn with_outlier_rate(mut self, rate: f64) -> Self {
+        self.outlier_rate = rate;
+        self
+    }
+
+    /// Sets the rate below which the robot counts as still (deg/s), and how
+    /// long it must stay below it.
+    pub const fn with_stationary(mut self, rate: f64, time: Duration) -> Self {
+        self.stationary_rate = rate;
+        self.stationary_time = time;
+        self
+    }
+
+    /// Sets the time constant of the bias estimate.
+    pub const fn with_bias_time_constant(mut self, time: Duration) -> Self {
+        self.bias_time_constant = time;
+        self
+    }
+}
+
+impl Default for ImuFusionConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Health of one sensor in an [`ImuFusion`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImuStatus {
+    /// Whether the latest read succeeded.
+    pub connected: bool,
+    /// Whether the latest reading was used in the fused heading.
+    pub used: bool,
+    /// Number of failed reads since creation.
+    pub failures: u32,
+    /// Number of readings discarded as outliers since creation.
+    pub rejections: u32,
+}
+
+/// Several inertial sensors fused into one [`Gyro`].
+///
+/// The fused heading advances each time [`heading`](Gyro::heading) is read,
+/// so read it regularly (a [`TrackingRig`](crate::TrackingRig) reads it every
+/// update).
+pub struct ImuFusion<G: Gyro, C: Clock = SystemClock> {
+    imus: Vec<(G, f64)>,
+    config: ImuFusionConfig,
+    clock: C,
+    state: RefCell<FusionState>,
+}
+
+impl<G: Gyro> ImuFusion<G> {
+    /// Creates a fused sensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `imus` - Each sensor with its scale factor: degrees actually turned
+    ///   per degree reported, for example `360.0 / 357.0` for a sensor that
+    ///   reads 357° per full turn
+    /// * `config` - Outlier and bias settings
+    pub fn new<const N: usize>(imus: [(G, f64); N], config: ImuFusionConfig) -> Self {
+        Self::with_clock(imus, config, SystemClock::new())
+    }
+}
+
+impl<G: Gyro, C: Clock> ImuFusion<G, C> {
+    /// Creates a fused sensor that measures time with the given clock.
+    ///
+    /// See [`ImuFusion::new`] for the other arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for rates and the bias estimate
+    pub fn with_clock<const N: usize>(
+        imus: [(G, f64); N],
+        config: ImuFusionConfig,
+        clock: C,
+    ) -> Self {
+        let state = FusionState {
+            heading: 0.0,
+            bias: 0.0,
+            rate: 0.0,
+            still_since: None,
+            last_time: clock.now(),
+            previous: vec![None; N],
+            status: vec![ImuStatus::default(); N],
+        };
+
+        let fusion = Self {
+            imus: Vec::from(imus),
+            config,
+            clock,
+            state: RefCell::new(state),
+        };
+
+        // Start from the first sensor that can be read.
+        {
+            let mut state = fusion.state.borrow_mut();
+            for (i, (imu, scale)) in fusion.imus.iter().enumerate() {
+                if let Ok(heading) = imu.heading() {
+                    state.previous[i] = Some(heading.as_radians());
+                    state.status[i].connected = true;
+                    if state.status.iter().take(i).all(|s| !s.connected) {
+                        state.heading = heading.as_radians() * scale;
+                    }
+                }
+            }
+        }
+
+        fusion
+    }
+
+    /// Returns the health of each sensor, in the order they were given.
+    pub fn status(&self) -> Vec<ImuStatus> {
+        self.state.borrow().status.clone()
+    }
+
+    /// Returns the number of sensors whose latest read succeeded.
+    pub fn connected(&self) -> usize {
+        self.state
+            .borrow()
+            .status
+            .iter()
+            .filter(|s| s.connected)
+            .count()
+    }
+
+    /// Returns the current gyro bias estimate in deg/s, counter-clockwise
+    /// positive.
+    pub fn bias(&self) -> f64 {
+        -self.state.borrow().bias.to_degrees()
+    }
+
+    /// Returns the settings.
+    pub fn config(&self) -> ImuFusionConfig {
+        self.config
+    }
+
+    /// Reads every sensor and advances the fused heading.
+    fn update(&self) -> Result<f64, InertialError> {
+        let now = self.clock.now();
+        let mut state = self.state.borrow_mut();
+        let dt = now.saturating_sub(state.last_time).as_secs_f64();
+        state.last_time = now;
+
+        // Scaled clockwise heading change of each sensor read both times.
+        let mut deltas: Vec<(usize, f64)> = Vec::with_capacity(self.imus.len());
+        let mut last_error = None;
+        for (i, (imu, scale)) in self.imus.iter().enumerate() {
+            match imu.heading() {
+                Ok(heading) => {
+                    let heading = heading.as_radians();
+                    if let Some(previous) = state.previous[i] {
+                        deltas.push((i, libm::remainder(heading - previous, TAU) * scale));
+                    }
+                    state.previous[i] = Some(heading);
+                    state.status[i].connected = true;
+                }
+                Err(error) => {
+                    state.previous[i] = None;
+                    state.status[i].connected = false;
+                    state.status[i].failures = state.status[i].failures.saturating_add(1);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        if let Some(error) = last_error
+            && state.status.iter().all(|s| !s.connected)
+        {
+            return Err(error);
+        }
+
+        // Readings at the same instant carry no rate to compare or integrate.
+        if dt <= 0.0 {
+            return Ok(state.heading);
+        }
+
+        let accepted = self.reject_outliers(&deltas, dt, state.rate);
+        for status in &mut state.status {
+            status.used = false;
+        }
+        for &(i, _) in &deltas {
+            if accepted.iter().any(|&(j, _)| j == i) {
+                state.status[i].used = true;
+            } else {
+                state.status[i].rejections = state.status[i].rejections.saturating_add(1);
+            }
+        }
+        if accepted.is_empty() {
+            return Ok(state.heading);
+        }
+
+        let delta = accepted.iter().map(|(_, d)| d).sum::<f64>() / accepted.len() as f64;
+        state.rate = delta / dt;
+
+        // Learn the bias while still; otherwise remove it.
+        let still = (delta / dt).abs() < self.config.stationary_rate.to_radians();
+        if !still {
+            state.still_since = None;
+        } else if state.still_since.is_none() {
+            state.still_since = Some(now);
+        }
+        let settled = state
+            .still_since
+            .is_some_and(|since| now.saturating_sub(since) >= self.config.stationary_time);
+
+        if settled {
+            let tau = self.config.bias_time_constant.as_secs_f64();
+            let alpha = dt / (tau + dt);
+            state.bias += alpha * (delta / dt - state.bias);
+        } else {
+            state.heading += delta - state.bias * dt;
+        }
+
+        Ok(state.heading)
+    }
+
+    /// Returns the deltas that agree with the others.
+    ///
+    /// With three or more sensors, deltas far from the median are dropped.
+    /// Two sensors that disagree have no majority, so the one closer to the
+    /// previous fused rate is kept.
+    fn reject_outliers(&self, deltas: &[(usize, f64)], dt: f64, rate: f64) -> Vec<(usize, f64)> {
+        if deltas.len() < 2 {
+            return deltas.to_vec();
+        }
+
+        let limit = self.config.outlier_rate.to_radians() * dt;
+        if let [a, b] = deltas {
+            if (a.1 - b.1).abs() <= limit {
+                return deltas.to_vec();
+            }
+            let expected = rate * dt;
+            let closer = if (a.1 - expected).abs() <= (b.1 - expected).abs() {
+                *a
+            } else {
+                *b
+            };
+            return vec![closer];
+        }
+
+        let mut sorted: Vec<f64> = deltas.iter().map(|(_, d)| *d).collect();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        let median = if n % 2 == 1 {
+            sorted[n / 2]
+        } else {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        };
+
+        deltas
+            .iter()
+            .copied()
+            .filter(|(_, d)| (d - median).abs() <= limit)
+            .collect()
+    }
+}
+
+impl<G: Gyro, C: Clock> Gyro for ImuFusion<G, C> {
+    fn heading(&self) -> Result<QAngle, InertialError> {
+        let heading = self.update()?;
+        Ok(QAngle::from_radians(
+            libm::remainder(heading, TAU).rem_euclid(TAU),
+        ))
+    }
+
+    fn gyro_rate(&self) -> Result<f64, PortError> {
+        let mut total = 0.0;
+        let mut count = 0.0;
+        let mut last_error = None;
+
+        for (imu, scale) in &self.imus {
+            match imu.gyro_rate() {
+                Ok(rate) => {
+                    total += rate * scale;
+                    count += 1.0;
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        match last_error {
+            Some(error) if count == 0.0 => Err(error),
+            _ => Ok(total / count - self.bias()),
+        }
+    }
+
+    fn acceleration(&self) -> Result<f64, PortError> {
+        let mut total = 0.0;
+        let mut count = 0.0;
+        let mut last_error = None;
+
+        for (imu, _) in &self.imus {
+            match imu.acceleration() {
+                Ok(accel) => {
+                    total += accel;
+                    count += 1.0;
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        match last_error {
+            Some(error) if count == 0.0 => Err(error),
+            _ => Ok(total / count),
+        }
+    }
+}
+
+/// Fused state, updated on every heading read.
+#[derive(Debug)]
+struct FusionState {
+    /// Fused clockwise heading (rad), unwrapped
+    heading: f64,
+    /// Clockwise gyro bias (rad/s)
+    bias: f64,
+    /// Latest fused clockwise rate (rad/s)
+    rate: f64,
+    /// When the robot last became still
+    still_since: Option<Duration>,
+    last_time: Duration,
+    /// Latest raw clockwise heading of each sensor (rad)
+    previous: Vec<Option<f64>>,
+    status: Vec<ImuStatus>,
+}
@@ -0,0 +1,236 @@
+//! Tracking wheel fault detection.
+//!
+//! A tracking wheel can fail in several ways during a match: a rotation
+//! sensor's cable comes loose and reads fail, an ADI encoder is unplugged and
+//! its count freezes, or a damaged cable produces a burst of counts that no
+//! robot could have rolled. Integrating any of these corrupts the pose
+//! estimate. [`TrackingRig`](crate::TrackingRig) checks every reading and
+//! leaves out wheels that look wrong:
+//!
+//! | Health | Condition |
+//! |--------|-----------|
+//! | [`Healthy`](WheelHealth::Healthy) | The reading is plausible |
+//! | [`Disconnected`](WheelHealth::Disconnected) | The encoder could not be read |
+//! | [`Stale`](WheelHealth::Stale) | The reading stayed frozen while the robot was moving |
+//! | [`Implausible`](WheelHealth::Implausible) | The reading jumped faster than the robot can move |
+//!
+//! A wheel rejoins as soon as it produces a healthy reading again; travel
+//! while it was out is not counted.
+//!
+//! # Example
+//!
+//! ```ignore
+//! for (i, wheel) in chassis.wheel_status().iter().enumerate() {
+//!     if wheel.health != WheelHealth::Healthy {
+//!         println!("tracking wheel {i}: {:?}", wheel.health);
+//!     }
+//! }
+//! ```
+
+use crate::util::si::QLength;
+use core::time::Duration;
+use vexide::smart::PortError;
+
+/// Thresholds for tracking wheel fault detection.
+///
+/// Use the `with_*` builder methods to adjust individual values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelHealthConfig {
+    /// Fastest a wheel can plausibly roll (m/s). Faster readings are
+    /// discarded as jumps.
+    pub max_speed: f64,
+    /// How long a reading may stay frozen while the robot moves before the
+    /// wheel counts as stale.
+    pub stale_time: Duration,
+    /// Speed above which a wheel is expected to turn (m/s).
+    pub motion_threshold: f64,
+}
+
+impl WheelHealthConfig {
+    /// Creates a configuration with a 4 m/s speed limit, a 250 ms stale time
+    /// and a 0.05 m/s motion threshold.
+    pub const fn new() -> Self {
+        Self {
+            max_speed: 4.0,
+            stale_time: Duration::from_millis(250),
+            motion_threshold: 0.05,
+        }
+    }
+
+    /// Sets the fastest plausible wheel speed (m/s).
+    pub const fn with_max_speed(mut self, speed: f64) -> Self {
+        self.max_speed = speed;
+        self
+    }
+
+    /// Sets how long a frozen reading is tolerated while moving.
+    pub const fn with_stale_time(mut self, time: Duration) -> Self {
+        self.stale_time = time;
+        self
+    }
+
+    /// Sets the speed above which a wheel is expected to turn (m/s).
+    pub const fn with_motion_threshold(mut self, speed: f64) -> Self {
+        self.motion_threshold = speed;
+        self
+    }
+}
+
+impl Default for WheelHealthConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Condition of a tracking wheel at its latest reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WheelHealth {
+    /// The reading is plausible.
+    #[default]
+    Healthy,
+    /// The encoder could not be read.
+    Disconnected,
+    /// The reading stayed frozen while the drivetrain and the other sensors
+    /// showed the wheel should be turning.
+    Stale,
+    /// The reading jumped further than the wheel could have rolled.
+    Implausible,
+}
+
+/// Health of one tracking wheel, as reported by
+/// [`TrackingRig::wheel_status`](crate::TrackingRig::wheel_status).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WheelStatus {
+    /// Condition at the latest reading
+    pub health: WheelHealth,
+    /// Whether the latest pose update used this wheel
+    pub used: bool,
+    /// Number of failed reads
+    pub failures: u32,
+    /// Number of readings discarded as jumps
+    pub jumps: u32,
+}
+
+/// Where a [`TrackingRig`](crate::TrackingRig) or [`ImeRig`](crate::ImeRig)
+/// took its latest forward travel from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelSource {
+    /// The healthy vertical tracking wheels
+    Wheels,
+    /// The drivetrain's motor encoders: always for an `ImeRig`, and for a
+    /// `TrackingRig` the velocity reported through
+    /// [`observe_drive`](crate::TrackingRig::observe_drive) because no
+    /// vertical wheel was healthy
+    Drive,
+    /// Neither was available, so only the heading was updated
+    Unavailable,
+}
+
+/// Checks the readings of one tracking wheel.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct WheelMonitor {
+    status: WheelStatus,
+    /// Reading the next travel is measured from; `None` until a reading
+    /// succeeds
+    previous: Option<f64>,
+    /// How long the reading has been frozen while it should have moved
+    frozen: Duration,
+}
+
+impl WheelMonitor {
+    /// Returns the wheel's status.
+    pub(crate) fn status(&self) -> WheelStatus {
+        self.status
+    }
+
+    /// Checks a new reading for read failures and jumps.
+    ///
+    /// # Arguments
+    ///
+    /// * `reading` - The wheel's total travel
+    /// * `elapsed` - Time since the previous reading was committed (s)
+    /// * `config` - Detection thresholds
+    ///
+    /// # Returns
+    ///
+    /// The travel since the previous committed reading (m), or `None` if the
+    /// reading cannot be used.
+    pub(crate) fn read(
+        &mut self,
+        reading: Result<QLength, PortError>,
+        elapsed: f64,
+        config: &WheelHealthConfig,
+    ) -> Option<f64> {
+        self.status.used = false;
+        let travel = match reading {
+            Ok(travel) => travel.as_meters(),
+            Err(_) => {
+                self.status.failures = self.status.failures.saturating_add(1);
+                self.status.health = WheelHealth::Disconnected;
+                self.previous = None;
+                self.frozen = Duration::ZERO;
+                return None;
+            }
+        };
+
+        // The first reading after a failure is only a reference for the next.
+        let Some(previous) = self.previous else {
+            self.previous = Some(travel);
+            self.status.health = WheelHealth::Healthy;
+            return None;
+        };
+
+        let delta = travel - previous;
+        if elapsed > 0.0 && delta.abs() > config.max_speed * elapsed {
+            self.status.jumps = self.status.jumps.saturating_add(1);
+            self.status.health = WheelHealth::Implausible;
+            self.previous = Some(travel);
+            return None;
+        }
+
+        Some(delta)
+    }
+
+    /// Checks whether a wheel that read successfully has frozen.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - Travel since the previous committed reading (m)
+    /// * `should_move` - Whether the other sensors show the wheel should
+    ///   have turned during this update
+    /// * `step` - Time since the previous update
+    /// * `config` - Detection thresholds
+    ///
+    /// # Returns
+    ///
+    /// Whether the wheel can be used.
+    pub(crate) fn check_frozen(
+        &mut self,
+        delta: f64,
+        should_move: bool,
+        step: Duration,
+        config: &WheelHealthConfig,
+    ) -> bool {
+        if delta != 0.0 {
+            self.frozen = Duration::ZERO;
+        } else if should_move {
+            self.frozen += step;
+        }
+
+        if self.frozen >= config.stale_time {
+            self.status.health = WheelHealth::Stale;
+            false
+        } else {
+            self.status.health = WheelHealth::Healthy;
+            true
+        }
+    }
+
+    /// Makes `delta` part of the integrated travel.
+    pub(crate) fn commit(&mut self, delta: f64) {
+        self.status.used = true;
+        if let Some(previous) = self.previous.as_mut() {
+            *previous += delta;
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Time-stamped pose history.
+//!
+//! Measurements such as GPS fixes or vision detections describe where the
+//! robot was when they were taken, which is often tens of milliseconds before
+//! they arrive. [`PoseHistory`] keeps the most recent odometry samples in a
+//! bounded ring buffer so such a measurement can be compared against the pose
+//! the robot believed it had at that moment. The same buffer is useful after a
+//! failed run to inspect what the robot believed along the way.
+//!
+//! [`TrackingRig`](crate::TrackingRig) records one sample per update; see
+//! [`TrackingRig::pose_at`](crate::TrackingRig::pose_at).
+//!
+//! # Interpolation
+//!
+//! Lookups between samples interpolate the same way as
+//! [`Trajectory::sample`](crate::Trajectory::sample): position and velocities
+//! linearly, heading along the shortest angular path.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // Vision reported a target 40 ms ago; compare it against the pose then.
+//! let then = clock.now() - Duration::from_millis(40);
+//! if let Some(pose) = rig.pose_at(then) {
+//!     // ...
+//! }
+//! ```
+
+use crate::motion::trajectory::{interpolate_pose, lerp};
+use crate::odom::pose::Pose;
+use core::time::Duration;
+use std::collections::VecDeque;
+
+/// A pose estimate and velocities at a point in time.
+#[derive(Debug, Clone, Copy)]
+pub struct PoseSample {
+    /// Estimated pose
+    pub pose: Pose,
+    /// Forward velocity (m/s)
+    pub linear_velocity: f64,
+    /// Angular velocity (rad/s), counter-clockwise positive
+    pub angular_velocity: f64,
+    /// Time the estimate was made, as reported by the estimator's clock
+    pub time: Duration,
+}
+
+/// A bounded ring buffer of [`PoseSample`]s ordered by time.
+///
+/// Once full, pushing a sample discards the oldest one.
+#[derive(Debug, Clone, Default)]
+pub struct PoseHistory {
+    samples: VecDeque<PoseSample>,
+    capacity: usize,
+}
+
+impl PoseHistory {
+    /// Creates an empty history.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of samples kept. Zero disables recording.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of samples kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the maximum number of samples kept, discarding the oldest
+    /// samples if there are now too many.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of samples kept
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the number of samples stored.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if no samples are stored.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Removes all samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Records a sample.
+    ///
+    /// Samples must arrive in time order; a sample older than the newest one
+    /// is ignored. A sample with the same time as the newest one replaces it.
+    ///
+    /// # Arguments
+    ///
+    /// * `sample` - The sample to record
+    pub fn push(&mut self, sample: PoseSample) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(newest) = self.samples.back_mut() {
+            if sample.time < newest.time {
+                return;
+            }
+            if sample.time == newest.time {
+                *newest = sample;
+                return;
+            }
+        }
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Returns the oldest stored sample.
+    pub fn oldest(&self) -> Option<PoseSample> {
+        self.samples.front().copied()
+    }
+
+    /// Returns the newest stored sample.
+    pub fn newest(&self) -> Option<PoseSample> {
+        self.samples.back().copied()
+    }
+
+    /// Iterates over the stored samples from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &PoseSample> {
+        self.samples.iter()
+    }
+
+    /// Returns the estimated state at a given time.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time to look up, on the same clock as the samples
+    ///
+    /// # Returns
+    ///
+    /// The state interpolated between the samples on either side of `time`,
+    /// or `None` if `time` lies outside the stored samples.
+    pub fn sample_at(&self, time: Duration) -> Option<PoseSample> {
+        let oldest = self.samples.front()?;
+        let newest = self.samples.back()?;
+        if time < oldest.time || time > newest.time {
+            return None;
+        }
+
+        // First sample at or after `time`; the one before it starts the span.
+        let index = self.samples.partition_point(|sample| sample.time < time);
+        let b = self.samples[index];
+        if index == 0 || b.time == time {
+            return Some(b);
+        }
+        let a = self.samples[index - 1];
+
+        let t = (time - a.time).as_secs_f64() / (b.time - a.time).as_secs_f64();
+        Some(PoseSample {
+            pose: interpolate_pose(a.pose, b.pose, t),
+            linear_velocity: lerp(a.linear_velocity, b.linear_velocity, t),
+            angular_velocity: lerp(a.angular_velocity, b.angular_velocity, t),
+            time,
+        })
+    }
+
+    /// Returns the estimated pose at a given time.
+    ///
+    /// See [`sample_at`](Self::sample_at).
+    pub fn pose_at(&self, time: Duration) -> Option<Pose> {
+        self.sample_at(time).map(|sample| sample.pose)
+    }
+}
@@ -0,0 +1,308 @@
+//! Odometry from the drive motors' integrated encoders.
+//!
+//! Robots without tracking wheels can still estimate their pose: every V5 motor
+//! has an integrated motor encoder (IME), and [`DifferentialDrive::travel`]
+//! converts its readings into the distance each side has rolled using the
+//! drivetrain's wheel size and gear ratio. [`ImeRig`] integrates those
+//! distances in a background task, the same way [`TrackingRig`](crate::TrackingRig)
+//! integrates tracking wheels, taking heading from an IMU when one is given.
+//!
+//! Drive wheels slip more than unpowered tracking wheels, especially under
+//! hard acceleration and on turns, so expect more drift than with a tracking
+//! rig. An IMU removes the largest source of error, turning scrub.
+//!
+//! [`OdomChassis`](crate::OdomChassis) uses an `ImeRig` automatically when it
+//! is created without a tracking rig.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let rig = ImeRig::new(Pose::default(), &dt, Some(imu));
+//! let chassis = OdomChassis::new(dt, other_imu, None).with_pose_source(rig);
+//! ```
+
+use crate::dt::differential::DifferentialDrive;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::odom::health::TravelSource;
+use crate::odom::pose::Pose;
+use crate::odom::source::PoseSource;
+use crate::odom::wheel::{HeadingSource, imu_heading};
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, Vec2};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use vexide::smart::imu::InertialSensor;
+use vexide_async::task::{Task, spawn};
+
+/// Pose estimation from the drive motor encoders and an optional IMU.
+///
+/// The background task reads both sides of the drivetrain about every 10 ms,
+/// or at the period set with [`with_period`](Self::with_period). Heading
+/// comes from the IMU while it can be read, and otherwise from the
+/// difference between the sides divided by the track width.
+///
+/// While the motor encoders cannot be read, the pose holds still, the
+/// velocities read zero and [`travel_source`](Self::travel_source) reports
+/// [`TravelSource::Unavailable`]. Motion during the dropout is added once
+/// the encoders can be read again.
+///
+/// Cloning a [`DifferentialDrive`] shares its motors, so the rig reads the
+/// same encoders the drivetrain drives with.
+pub struct ImeRig {
+    data: Rc<RefCell<ImeData>>,
+    _task: Task<()>,
+}
+
+impl ImeRig {
+    /// Creates a rig and starts its background task.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose of the robot
+    /// * `dt` - The drivetrain whose motor encoders to read
+    /// * `imu` - Optional heading sensor. Use
+    ///   [`without_imu`](Self::without_imu) to avoid naming the sensor type.
+    pub fn new<M, G>(origin: Pose, dt: &DifferentialDrive<M>, imu: Option<G>) -> Self
+    where
+        M: MotorOutput + 'static,
+        G: Gyro + 'static,
+    {
+        Self::with_clock(origin, dt, imu, SystemClock::new())
+    }
+
+    /// Creates a rig that takes its heading from the drive sides, and starts
+    /// its background task.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose of the robot
+    /// * `dt` - The drivetrain whose motor encoders to read
+    pub fn without_imu<M>(origin: Pose, dt: &DifferentialDrive<M>) -> Self
+    where
+        M: MotorOutput + 'static,
+    {
+        Self::new(origin, dt, None::<InertialSensor>)
+    }
+
+    /// Creates a rig whose background task runs on the given clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose of the robot
+    /// * `dt` - The drivetrain whose motor encoders to read
+    /// * `imu` - Optional heading sensor
+    /// * `clock` - Time source for the update loop
+    pub fn with_clock<M, G, C>(
+        origin: Pose,
+        dt: &DifferentialDrive<M>,
+        imu: Option<G>,
+        clock: C,
+    ) -> Self
+    where
+        M: MotorOutput + 'static,
+        G: Gyro + 'static,
+        C: Clock + 'static,
+    {
+        let data = Rc::new(RefCell::new(ImeData {
+            pose: origin,
+            linear_velocity: 0.0,
+            angular_velocity: 0.0,
+            heading_source: if imu.is_some() {
+                HeadingSource::Imu
+            } else {
+                HeadingSource::Wheels
+            },
+            travel_source: TravelSource::Drive,
+            period: DEFAULT_PERIOD,
+        }));
+
+        let task = spawn(Self::task(dt.clone(), imu, Rc::clone(&data), clock));
+
+        Self { data, _task: task }
+    }
+
+    /// Sets the time between updates.
+    ///
+    /// Shorter periods track fast motion more closely but leave less CPU time
+    /// for other tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Time between updates, 10 ms by default
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn with_period(self, period: Duration) -> Self {
+        assert!(!period.is_zero(), "odometry period must be positive");
+        self.data.borrow_mut().period = period;
+        self
+    }
+
+    /// Returns the latest pose estimate.
+    pub fn pose(&self) -> Pose {
+        self.data.borrow().pose
+    }
+
+    /// Returns the latest forward velocity estimate in m/s.
+    pub fn linear_velocity(&self) -> f64 {
+        self.data.borrow().linear_velocity
+    }
+
+    /// Returns the latest angular velocity estimate in rad/s
+    /// (counter-clockwise positive).
+    pub fn angular_velocity(&self) -> f64 {
+        self.data.borrow().angular_velocity
+    }
+
+    /// Returns the sensor the latest heading change was measured with.
+    ///
+    /// [`HeadingSource::Wheels`] means the drive sides were used.
+    pub fn heading_source(&self) -> HeadingSource {
+        self.data.borrow().heading_source
+    }
+
+    /// Returns where the latest forward travel was taken from.
+    ///
+    /// [`TravelSource::Drive`] while the motor encoders can be read, and
+    /// [`TravelSource::Unavailable`] while they cannot.
+    pub fn travel_source(&self) -> TravelSource {
+        self.data.borrow().travel_source
+    }
+
+    /// Moves the pose estimate to a new pose.
+    ///
+    /// Motion measured after the latest update is added on top of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's new pose
+    pub fn set_pose(&self, pose: Pose) {
+        self.data.borrow_mut().pose = pose;
+    }
+
+    /// Background task integrating the motor encoders.
+    async fn task<M: MotorOutput, G: Gyro, C: Clock>(
+        dt: DifferentialDrive<M>,
+        imu: Option<G>,
+        data: Rc<RefCell<ImeData>>,
+        clock: C,
+    ) {
+        let width = dt.width().as_meters();
+        let mut previous: Option<(f64, f64)> = None;
+        let mut prev_imu = imu.as_ref().and_then(imu_heading);
+        let mut prev_time = clock.now();
+
+        loop {
+            let period = data.borrow().period;
+            clock.sleep(period).await;
+
+            // On a failed read, motion carries over to the next update.
+            let Ok((left, right)) = dt.travel().await else {
+                let mut state = data.borrow_mut();
+                state.travel_source = TravelSource::Unavailable;
+                state.linear_velocity = 0.0;
+                state.angular_velocity = 0.0;
+                continue;
+            };
+            let (left, right) = (left.as_meters(), right.as_meters());
+            let imu_now = imu.as_ref().and_then(imu_heading);
+            let now = clock.now();
+            let elapsed = now.saturating_sub(prev_time).as_secs_f64();
+
+            let Some((prev_left, prev_right)) = previous.replace((left, right)) else {
+                prev_imu = imu_now;
+                prev_time = now;
+                continue;
+            };
+            let (delta_left, delta_right) = (left - prev_left, right - prev_right);
+
+            let (delta_heading, source) = match (prev_imu, imu_now) {
+                (Some(prev), Some(current)) => (
+                    (current - prev).remainder(QAngle::TAU).as_radians(),
+                    HeadingSource::Imu,
+                ),
+                _ => ((delta_right - delta_left) / width, HeadingSource::Wheels),
+            };
+            prev_imu = imu_now;
+            prev_time = now;
+
+            let forward = (delta_left + delta_right) / 2.0;
+            let local_x = if delta_heading == 0.0 {
+                forward
+            } else {
+                2.0 * libm::sin(delta_heading / 2.0) * forward / delta_heading
+            };
+
+            let gyro_rate = imu
+                .as_ref()
+                .filter(|_| imu_now.is_some())
+                .and_then(|imu| imu.gyro_rate().ok());
+            let (linear_velocity, angular_velocity) = if elapsed > 0.0 {
+                (
+                    forward / elapsed,
+                    gyro_rate.map_or(delta_heading / elapsed, f64::to_radians),
+                )
+            } else {
+                (0.0, 0.0)
+            };
+
+            let mut state = data.borrow_mut();
+            let heading = state.pose.heading().as_radians();
+            let avg_heading = heading + delta_heading / 2.0;
+            let position = state.pose.position()
+                + Vec2::new(
+                    local_x * libm::cos(avg_heading),
+                    local_x * libm::sin(avg_heading),
+                );
+            state.pose = Pose::new(position, QAngle::from_radians(heading + delta_heading));
+            state.linear_velocity = linear_velocity;
+            state.angular_velocity = angular_velocity;
+            state.heading_source = source;
+            state.travel_source = TravelSource::Drive;
+        }
+    }
+}
+
+impl PoseSource for ImeRig {
+    fn pose(&self) -> Pose {
+        ImeRig::pose(self)
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        ImeRig::linear_velocity(self)
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        ImeRig::angular_velocity(self)
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        ImeRig::set_pose(self, pose);
+    }
+
+    fn heading_source(&self) -> Option<HeadingSource> {
+        Some(ImeRig::heading_source(self))
+    }
+
+    fn travel_source(&self) -> Option<TravelSource> {
+        Some(ImeRig::travel_source(self))
+    }
+}
+
+/// State shared between an [`ImeRig`] and its background task.
+#[derive(Debug)]
+struct ImeData {
+    pose: Pose,
+    linear_velocity: f64,
+    angular_velocity: f64,
+    heading_source: HeadingSource,
+    travel_source: TravelSource,
+    /// Time between updates
+    period: Duration,
+}
+
+/// Time between updates by default.
+const DEFAULT_PERIOD: Duration = Duration::from_millis(10);
@@ -0,0 +1,678 @@
+//! Monte Carlo localization against a field map.
+//!
+//! Dead reckoning drifts, and over a long skills run the drift reaches several
+//! inches. Monte Carlo localization (MCL) corrects it continuously by comparing
+//! distance sensor readings with what those sensors *should* read from many
+//! candidate poses, then keeping the candidates that agree.
+//!
+//! - [`ParticleFilter`]: the filter itself, with explicit `predict` and
+//!   `update` steps that can be driven by hand or in tests
+//! - [`Mcl`]: a background task that feeds the filter with odometry deltas and
+//!   [`MountedSensor`] readings
+//! - [`MclConfig`]: particle count and noise parameters
+//!
+//! # Algorithm
+//!
+//! Each particle is a pose hypothesis with a weight. Every update:
+//!
+//! 1. **Motion.** The odometry change since the last update is applied to
+//!    every particle, with random noise proportional to the motion so that the
+//!    cloud spreads as uncertainty grows.
+//! 2. **Measurement.** Each particle ray casts every sensor beam against the
+//!    [`Field`] and is weighted by how well the expected distances match the
+//!    readings. A small uniform term keeps readings of unmapped objects (other
+//!    robots, game elements) from wiping out good particles. [`Mcl`] only
+//!    applies a reading when a sensor reports a new value or the robot has
+//!    moved since the last measurement, so a reading held between sensor
+//!    refreshes is not counted as fresh evidence.
+//! 3. **Resampling.** When the weights become uneven, particles are redrawn in
+//!    proportion to their weight and lightly jittered to keep the cloud from
+//!    collapsing onto a single hypothesis.
+//!
+//! The best estimate is the weighted mean of the cloud, and its spread is a
+//! measure of how confident the filter is.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let rig = TrackingRig::new(origin, [horizontal], [vertical], Some(imu));
+//! let sensors = [
+//!     MountedSensor::new(front, Pose::new(Vec2::new(0.15, 0.0), QAngle::from_degrees(0.0))),
+//!     MountedSensor::new(left, Pose::new(Vec2::new(0.0, 0.15), QAngle::from_degrees(90.0))),
+//! ];
+//! let mcl = Mcl::new(rig, sensors, Field::v5(), MclConfig::new());
+//!
+//! let chassis = OdomChassis::new(dt, imu, None).with_pose_source(mcl);
+//! ```
+
+use crate::hal::distance::RangeFinder;
+use crate::odom::dist::{Field, MountedSensor};
+use crate::odom::pose::Pose;
+use crate::odom::source::PoseSource;
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::rng::Rng;
+use crate::util::si::{QAngle, QLength, Vec2};
+use crate::util::utils::wrap_angle;
+use core::f64::consts::TAU;
+use core::time::Duration;
+use std::cell::RefCell;
+use std::rc::Rc;
+use vexide_async::task::{Task, spawn};
+
+/// Parameters for the [`ParticleFilter`].
+///
+/// Motion noise is given as a standard deviation per square-root unit of
+/// motion, so the variance grows linearly with distance travelled and does
+/// not depend on how often the filter is updated. Use the `with_*` builder
+/// methods to adjust individual values.
+#[derive(Debug, Clone, Copy)]
+pub struct MclConfig {
+    /// Number of particles.
+    pub particles: usize,
+    /// Translation noise in m/√m.
+    pub translation_noise: f64,
+    /// Heading noise from turning in rad/√rad.
+    pub rotation_noise: f64,
+    /// Heading noise from driving in rad/√m.
+    pub drift_noise: f64,
+    /// Standard deviation of a distance sensor reading.
+    pub sensor_std: QLength,
+    /// Probability that a reading comes from an object not on the map.
+    pub random_weight: f64,
+    /// Readings longer than this are ignored.
+    pub max_range: QLength,
+    /// Travel after which [`Mcl`] applies readings that have not changed.
+    pub update_distance: QLength,
+    /// Rotation after which [`Mcl`] applies readings that have not changed.
+    pub update_angle: QAngle,
+    /// Initial standard deviation of each position axis.
+    pub position_std: QLength,
+    /// Initial standard deviation of the heading.
+    pub heading_std: QAngle,
+    /// Resample when the effective sample size falls below this fraction of
+    /// the particle count.
+    pub resample_threshold: f64,
+    /// Position jitter applied to each particle after resampling.
+    pub roughening: QLength,
+    /// Heading jitter applied to each particle after resampling.
+    pub heading_roughening: QAngle,
+    /// Seed for the random number generator.
+    pub seed: u64,
+}
+
+impl MclConfig {
+    /// Creates a configuration with defaults suited to a V5 distance sensor.
+    pub const fn new() -> Self {
+        Self {
+            particles: 200,
+            translation_noise: 0.03,
+            rotation_noise: 0.02,
+            drift_noise: 0.01,
+            sensor_std: QLength::from_centimeters(2.0),
+            random_weight: 0.05,
+            max_range: QLength::from_meters(2.0),
+            update_distance: QLength::from_centimeters(1.0),
+            update_angle: QAngle::from_degrees(1.0),
+            position_std: QLength::from_centimeters(2.0),
+            heading_std: QAngle::from_degrees(2.0),
+            resample_threshold: 0.5,
+            roughening: QLength::from_centimeters(0.2),
+            heading_roughening: QAngle::from_degrees(0.2),
+            seed: 1,
+        }
+    }
+
+    /// Sets the number of particles.
+    pub const fn with_particles(mut self, particles: usize) -> Self {
+        self.particles = particles;
+        self
+    }
+
+    /// Sets the motion noise.
+    ///
+    /// # Arguments
+    ///
+    /// * `translation` - Translation noise in m/√m
+    /// * `rotation` - Heading noise from turning in rad/√rad
+    pub const fn with_motion_noise(mut self, translation: f64, rotation: f64) -> Self {
+        self.translation_noise = translation;
+        self.rotation_noise = rotation;
+        self
+    }
+
+    /// Sets the heading noise from driving in rad/√m.
+    pub const fn with_drift_noise(mut self, noise: f64) -> Self {
+        self.drift_noise = noise;
+        self
+    }
+
+    /// Sets the standard deviation of a distance sensor reading.
+    pub const fn with_sensor_std(mut self, std_dev: QLength) -> Self {
+        self.sensor_std = std_dev;
+        self
+    }
+
+    /// Sets the probability that a reading comes from an unmapped object.
+    pub const fn with_random_weight(mut self, weight: f64) -> Self {
+        self.random_weight = weight;
+        self
+    }
+
+    /// Sets the longest reading that is used.
+    pub const fn with_max_range(mut self, range: QLength) -> Self {
+        self.max_range = range;
+        self
+    }
+
+    /// Sets how far the robot must move before [`Mcl`] applies readings that
+    /// have not changed since the last measurement.
+    ///
+    /// Distance sensors refresh more slowly than the filter runs, so most
+    /// loops see the previous reading again. Applying it would count the same
+    /// measurement several times and make the filter overconfident.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - Travel since the last measurement
+    /// * `angle` - Rotation since the last measurement
+    pub const fn with_update_threshold(mut self, distance: QLength, angle: QAngle) -> Self {
+        self.update_distance = distance;
+        self.update_angle = angle;
+        self
+    }
+
+    /// Sets the initial pose uncertainty.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Standard deviation of each position axis
+    /// * `heading` - Standard deviation of the heading
+    pub const fn with_initial_std_dev(mut self, position: QLength, heading: QAngle) -> Self {
+        self.position_std = position;
+        self.heading_std = heading;
+        self
+    }
+
+    /// Sets the effective sample size fraction below which particles are resampled.
+    pub const fn with_resample_threshold(mut self, threshold: f64) -> Self {
+        self.resample_threshold = threshold;
+        self
+    }
+
+    /// Sets the jitter applied after resampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Standard deviation of each position axis
+    /// * `heading` - Standard deviation of the heading
+    pub const fn with_roughening(mut self, position: QLength, heading: QAngle) -> Self {
+        self.roughening = position;
+        self.heading_roughening = heading;
+        self
+    }
+
+    /// Sets the random seed.
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for MclConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A weighted pose hypothesis.
+#[derive(Debug, Clone, Copy)]
+pub struct Particle {
+    /// The hypothesised robot pose
+    pub pose: Pose,
+    /// Normalised weight; the weights of all particles sum to one
+    pub weight: f64,
+}
+
+/// A particle filter over robot poses.
+///
+/// Poses follow the [`Pose`] convention: metres, with headings
+/// counter-clockwise positive.
+#[derive(Debug, Clone)]
+pub struct ParticleFilter {
+    particles: std::vec::Vec<Particle>,
+    config: MclConfig,
+    rng: Rng,
+}
+
+impl ParticleFilter {
+    /// Creates a filter with particles scattered around an initial pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose guess
+    /// * `config` - Particle count and noise parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if `config.particles` is zero.
+    pub fn new(origin: Pose, config: MclConfig) -> Self {
+        assert!(config.particles > 0, "a particle filter needs particles");
+
+        let mut filter = Self {
+            particles: std::vec::Vec::with_capacity(config.particles),
+            config,
+            rng: Rng::new(config.seed),
+        };
+        filter.reset(origin);
+        filter
+    }
+
+    /// Scatters the particles around a new pose guess.
+    ///
+    /// The spread is given by the configured initial standard deviations.
+    pub fn reset(&mut self, pose: Pose) {
+        let weight = 1.0 / self.config.particles as f64;
+        let position_std = self.config.position_std.as_meters();
+        let heading_std = self.config.heading_std.as_radians();
+
+        self.particles.clear();
+        for _ in 0..self.config.particles {
+            let x = self.rng.gaussian(pose.position().x, position_std);
+            let y = self.rng.gaussian(pose.position().y, position_std);
+            let theta = self.rng.gaussian(pose.heading().as_radians(), heading_std);
+            self.particles.push(Particle {
+                pose: Pose::new(Vec2::new(x, y), QAngle::from_radians(wrap_angle(theta))),
+                weight,
+            });
+        }
+    }
+
+    /// Returns the particles.
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// Moves every particle by an odometry delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `displacement` - Travel in metres, in the robot frame at the start of
+    ///   the step (x forward, y left)
+    /// * `delta_heading` - Heading change (counter-clockwise positive)
+    pub fn predict(&mut self, displacement: Vec2<f64>, delta_heading: QAngle) {
+        let distance = displacement.norm();
+        let dtheta = delta_heading.as_radians();
+
+        let translation_std = self.config.translation_noise * libm::sqrt(distance);
+        let rotation_std = libm::sqrt(
+            self.config.rotation_noise.powi(2) * dtheta.abs()
+                + self.config.drift_noise.powi(2) * distance,
+        );
+
+        for particle in &mut self.particles {
+            let dx = self.rng.gaussian(displacement.x, translation_std);
+            let dy = self.rng.gaussian(displacement.y, translation_std);
+            let dh = self.rng.gaussian(dtheta, rotation_std);
+
+            let theta = particle.pose.heading().as_radians();
+            let (sin, cos) = (libm::sin(theta), libm::cos(theta));
+            let position =
+                particle.pose.position() + Vec2::new(dx * cos - dy * sin, dx * sin + dy * cos);
+
+            particle.pose = Pose::new(position, QAngle::from_radians(wrap_angle(theta + dh)));
+        }
+    }
+
+    /// Weights the particles by how well they explain distance readings.
+    ///
+    /// # Arguments
+    ///
+    /// * `readings` - Pairs of sensor mount (relative to the robot centre) and
+    ///   measured distance
+    /// * `field` - The surfaces the beams can hit
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one reading was within range and used.
+    pub fn update(&mut self, readings: &[(Pose, QLength)], field: &Field) -> bool {
+        let max_range = self.config.max_range.as_meters();
+        let sigma = self.config.sensor_std.as_meters();
+        let random = self.config.random_weight;
+
+        let usable = readings
+            .iter()
+            .filter(|(_, distance)| distance.as_meters() <= max_range)
+            .copied()
+            .collect::<std::vec::Vec<_>>();
+        if usable.is_empty() {
+            return false;
+        }
+
+        let norm = 1.0 / (sigma * libm::sqrt(TAU));
+        let floor = random / max_range;
+
+        let log_likelihoods = self
+            .particles
+            .iter()
+            .map(|particle| {
+                usable
+                    .iter()
+                    .map(|&(mount, distance)| {
+                        let hit = field
+                            .expected_reading(particle.pose, mount)
+                            .filter(|expected| expected.as_meters() <= max_range)
+                            .map_or(0.0, |expected| {
+                                let z = (distance.as_meters() - expected.as_meters()) / sigma;
+                                norm * libm::exp(-0.5 * z * z)
+                            });
+                        libm::log((1.0 - random) * hit + floor)
+                    })
+                    .sum::<f64>()
+            })
+            .collect::<std::vec::Vec<_>>();
+
+        // Subtract the best log-likelihood so the largest factor is exactly 1.
+        let best = log_likelihoods
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let mut total = 0.0;
+        for (particle, log_likelihood) in self.particles.iter_mut().zip(&log_likelihoods) {
+            particle.weight *= libm::exp(log_likelihood - best);
+            total += particle.weight;
+        }
+
+        if total.is_finite() && total > 0.0 {
+            for particle in &mut self.particles {
+                particle.weight /= total;
+            }
+        } else {
+            let weight = 1.0 / self.particles.len() as f64;
+            for particle in &mut self.particles {
+                particle.weight = weight;
+            }
+        }
+
+        if self.effective_sample_size()
+            < self.config.resample_threshold * self.particles.len() as f64
+        {
+            self.resample();
+        }
+
+        true
+    }
+
+    /// Returns the effective sample size, `1 / Σw²`.
+    ///
+    /// Equal weights give the particle count; a single dominant particle gives 1.
+    pub fn effective_sample_size(&self) -> f64 {
+        1.0 / self
+            .particles
+            .iter()
+            .map(|p| p.weight * p.weight)
+            .sum::<f64>()
+    }
+
+    /// Redraws the particles in proportion to their weights.
+    ///
+    /// Uses low-variance resampling, then jitters each particle by the
+    /// configured roughening so duplicates do not stay identical.
+    pub fn resample(&mut self) {
+        let count = self.particles.len();
+        let step = 1.0 / count as f64;
+        let roughening = self.config.roughening.as_meters();
+        let heading_roughening = self.config.heading_roughening.as_radians();
+
+        let start = self.rng.range(0.0, step);
+        let mut resampled = std::vec::Vec::with_capacity(count);
+        let mut index = 0;
+        let mut cumulative = self.particles[0].weight;
+
+        for m in 0..count {
+            let target = start + m as f64 * step;
+            while target > cumulative && index < count - 1 {
+                index += 1;
+                cumulative += self.particles[index].weight;
+            }
+
+            let pose = self.particles[index].pose;
+            let x = self.rng.gaussian(pose.position().x, roughening);
+            let y = self.rng.gaussian(pose.position().y, roughening);
+            let theta = self
+                .rng
+                .gaussian(pose.heading().as_radians(), heading_roughening);
+            resampled.push(Particle {
+                pose: Pose::new(Vec2::new(x, y), QAngle::from_radians(wrap_angle(theta))),
+                weight: step,
+            });
+        }
+
+        self.particles = resampled;
+    }
+
+    /// Returns the weighted mean pose of the particles.
+    ///
+    /// The heading is averaged on the circle, so particles either side of
+    /// ±180° average correctly.
+    pub fn estimate(&self) -> Pose {
+        let (mut x, mut y, mut sin, mut cos) = (0.0, 0.0, 0.0, 0.0);
+        for particle in &self.particles {
+            let theta = particle.pose.heading().as_radians();
+            x += particle.weight * particle.pose.position().x;
+            y += particle.weight * particle.pose.position().y;
+            sin += particle.weight * libm::sin(theta);
+            cos += particle.weight * libm::cos(theta);
+        }
+
+        Pose::new(Vec2::new(x, y), QAngle::atan2(sin, cos))
+    }
+
+    /// Returns the weighted RMS distance of the particles from the estimate.
+    ///
+    /// A small spread means the particles agree on the robot's position.
+    pub fn spread(&self) -> QLength {
+        let mean = self.estimate().position();
+        let variance = self
+            .particles
+            .iter()
+            .map(|particle| particle.weight * particle.pose.position().distance(mean).powi(2))
+            .sum::<f64>();
+
+        QLength::from_meters(libm::sqrt(variance))
+    }
+}
+
+/// Monte Carlo localization running in the background.
+///
+/// Wraps an odometry source, typically a [`TrackingRig`](crate::TrackingRig),
+/// and corrects its drift with distance sensors. The odometry keeps running on
+/// its own; only its pose changes are used, so its absolute pose may drift
+/// freely without affecting the localized estimate.
+///
+/// The task runs at approximately 100Hz and stops when the localizer is
+/// dropped.
+pub struct Mcl<O: PoseSource> {
+    odometry: Rc<O>,
+    data: Rc<RefCell<MclData>>,
+    _task: Task<()>,
+}
+
+impl<O: PoseSource + 'static> Mcl<O> {
+    /// Creates a localizer and starts the background task.
+    ///
+    /// The particles start around the odometry's current pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `odometry` - Source of motion updates
+    /// * `sensors` - Distance sensors and their mounting poses
+    /// * `field` - The walls and obstacles the sensors can see
+    /// * `config` - Particle count and noise parameters
+    pub fn new<const K: usize, S: RangeFinder + 'static>(
+        odometry: O,
+        sensors: [MountedSensor<S>; K],
+        field: Field,
+        config: MclConfig,
+    ) -> Self {
+        Self::with_clock(odometry, sensors, field, config, SystemClock::new())
+    }
+
+    /// Creates a localizer whose background task runs on the given clock.
+    ///
+    /// See [`Mcl::new`] for the other arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - Time source for the update loop
+    pub fn with_clock<const K: usize, S: RangeFinder + 'static, C: Clock + 'static>(
+        odometry: O,
+        sensors: [MountedSensor<S>; K],
+        field: Field,
+        config: MclConfig,
+        clock: C,
+    ) -> Self {
+        let odometry = Rc::new(odometry);
+        let filter = ParticleFilter::new(odometry.pose(), config);
+
+        let data = Rc::new(RefCell::new(MclData {
+            estimate: filter.estimate(),
+            spread: filter.spread(),
+            filter,
+        }));
+
+        let task_odometry = Rc::clone(&odometry);
+        let task_data = Rc::clone(&data);
+        let task = spawn(async move {
+            Self::task(task_odometry, sensors, field, config, task_data, clock).await;
+        });
+
+        Self {
+            odometry,
+            data,
+            _task: task,
+        }
+    }
+
+    /// Returns the best pose estimate.
+    pub fn pose(&self) -> Pose {
+        self.data.borrow().estimate
+    }
+
+    /// Returns the RMS distance of the particles from the estimate.
+    pub fn spread(&self) -> QLength {
+        self.data.borrow().spread
+    }
+
+    /// Returns a copy of the current particles.
+    pub fn particles(&self) -> std::vec::Vec<Particle> {
+        self.data.borrow().filter.particles().to_vec()
+    }
+
+    /// Returns the wrapped odometry source.
+    pub fn odometry(&self) -> &O {
+        &self.odometry
+    }
+
+    /// Scatters the particles around a new pose guess.
+    pub fn reset(&self, pose: Pose) {
+        let mut data = self.data.borrow_mut();
+        data.filter.reset(pose);
+        data.estimate = data.filter.estimate();
+        data.spread = data.filter.spread();
+    }
+
+    /// Background task that feeds odometry and sensor readings into the filter.
+    ///
+    /// Readings are applied when any sensor reports a value different from
+    /// the last applied one, or once the robot has moved past the
+    /// [`update_distance`](MclConfig::update_distance) or
+    /// [`update_angle`](MclConfig::update_angle) threshold.
+    async fn task<const K: usize, S: RangeFinder, C: Clock>(
+        odometry: Rc<O>,
+        sensors: [MountedSensor<S>; K],
+        field: Field,
+        config: MclConfig,
+        data: Rc<RefCell<MclData>>,
+        clock: C,
+    ) {
+        let mut previous = odometry.pose();
+        let mut applied = [None::<f64>; K];
+        let mut travelled = 0.0;
+        let mut turned = 0.0;
+
+        loop {
+            clock.sleep(Duration::from_millis(10)).await;
+
+            // Express the odometry change in the robot frame at the previous pose.
+            let current = odometry.pose();
+            let theta = previous.heading().as_radians();
+            let (sin, cos) = (libm::sin(theta), libm::cos(theta));
+            let global = current.position() - previous.position();
+            let displacement = Vec2::new(
+                global.x * cos + global.y * sin,
+                -global.x * sin + global.y * cos,
+            );
+            let dtheta = wrap_angle((current.heading() - previous.heading()).as_radians());
+            previous = current;
+            travelled += displacement.norm();
+            turned += dtheta.abs();
+
+            let distances = sensors
+                .each_ref()
+                .map(|sensor| sensor.read().map(|distance| distance.as_meters()));
+            let fresh = distances
+                .iter()
+                .zip(&applied)
+                .any(|(distance, last)| distance.is_some() && distance != last);
+            let moved = travelled >= config.update_distance.as_meters()
+                || turned >= config.update_angle.as_radians();
+
+            let mut data = data.borrow_mut();
+            data.filter
+                .predict(displacement, QAngle::from_radians(dtheta));
+            if fresh || moved {
+                let readings = sensors
+                    .iter()
+                    .zip(&distances)
+                    .filter_map(|(sensor, distance)| {
+                        distance.map(|distance| (sensor.mount(), QLength::from_meters(distance)))
+                    })
+                    .collect::<std::vec::Vec<_>>();
+                data.filter.update(&readings, &field);
+
+                applied = distances;
+                travelled = 0.0;
+                turned = 0.0;
+            }
+            data.estimate = data.filter.estimate();
+            data.spread = data.filter.spread();
+        }
+    }
+}
+
+impl<O: PoseSource + 'static> PoseSource for Mcl<O> {
+    fn pose(&self) -> Pose {
+        self.data.borrow().estimate
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        self.odometry.linear_velocity()
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        self.odometry.angular_velocity()
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        self.reset(pose);
+    }
+}
+
+/// State shared between [`Mcl`] and its background task.
+#[derive(Debug)]
+struct MclData {
+    filter: ParticleFilter,
+    estimate: Pose,
+    spread: QLength,
+}
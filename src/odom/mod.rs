@@ -1,6 +1,19 @@
+pub mod calibrate;
 pub mod chassis;
+pub mod contact;
+pub mod dist;
+pub mod ekf;
+pub mod gps;
+pub mod health;
+pub mod heading;
+pub mod history;
+pub mod ime;
+pub mod mcl;
+pub mod motion;
 pub mod pose;
+pub mod routine;
+pub mod source;
+pub mod velocity;
 pub mod wheel;
-mod dist;
 
 pub use chassis::OdomChassis;
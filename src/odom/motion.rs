@@ -0,0 +1,476 @@
+//! Chassis motions that run in the background.
+//!
+//! An [`OdomChassis`] motion runs until it ends when awaited, so a routine
+//! cannot act partway through one. [`BackgroundChassis`] runs each motion in
+//! its own task instead and returns a [`MotionHandle`] at once. The handle can
+//! wait until the robot has covered a distance or a fraction of the motion,
+//! cancel it, or be awaited for the motion's result.
+//!
+//! Starting a motion preempts the one running: the running motion stops the
+//! drivetrain and ends with [`DriveError::Cancelled`], and the new one starts
+//! as soon as it has.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut chassis = BackgroundChassis::new(chassis);
+//!
+//! // Start the intake 20 cm before reaching the goal.
+//! let drive = chassis.shoot(QLength::from_meters(1.0));
+//! drive.wait_until_distance(QLength::from_meters(0.8)).await;
+//! intake.set_voltage(12.0)?;
+//! drive.await?;
+//! ```
+
+use crate::control::boomerang::MoveParams;
+use crate::control::exit::ExitReason;
+use crate::control::purepursuit::PurePursuit;
+use crate::dt::differential::Side;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::motion::trajectory::Trajectory;
+use crate::odom::chassis::{DriveError, OdomChassis, TurnDirection};
+use crate::odom::pose::Pose;
+use crate::odom::source::PoseSource;
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, QLength, Vec2};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use vexide::smart::imu::InertialSensor;
+use vexide::smart::motor::Motor;
+use vexide_async::task::spawn;
+
+/// How often handles and waiting motions check on a motion.
+const POLL_PERIOD: Duration = Duration::from_millis(10);
+
+/// Progress of a background motion, shared between its task, its
+/// [`MotionHandle`] and the chassis running it.
+#[derive(Debug, Default)]
+pub(crate) struct MotionState {
+    /// Distance the robot has covered, in metres
+    distance: Cell<f64>,
+    /// Fraction of the motion completed
+    progress: Cell<f64>,
+    /// Position at the latest report
+    last: Cell<Option<Vec2<f64>>>,
+    cancelled: Cell<bool>,
+    /// The motion's result, once it has ended and until it is awaited
+    result: RefCell<Option<Result<ExitReason, DriveError>>>,
+    done: Cell<bool>,
+    /// Task awaiting the result
+    waker: RefCell<Option<Waker>>,
+}
+
+impl MotionState {
+    /// Records the robot's position and the motion's progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The robot's current position
+    /// * `progress` - Fraction of the motion completed, from 0 to 1
+    pub(crate) fn report(&self, position: Vec2<f64>, progress: f64) {
+        if let Some(last) = self.last.replace(Some(position)) {
+            self.distance
+                .set(self.distance.get() + position.distance(last));
+        }
+        self.progress.set(progress);
+    }
+
+    /// Returns whether the motion has been asked to stop.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancelled.get()
+    }
+
+    fn cancel(&self) {
+        self.cancelled.set(true);
+    }
+
+    fn finish(&self, result: Result<ExitReason, DriveError>) {
+        *self.result.borrow_mut() = Some(result);
+        self.done.set(true);
+        if let Some(waker) = self.waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A motion running in the background.
+///
+/// Returned by the [`BackgroundChassis`] motions. Awaiting the handle waits
+/// for the motion to end and yields its result, as awaiting the
+/// [`OdomChassis`] motion would. The result can only be taken once; polling
+/// the handle again yields [`DriveError::Cancelled`]. Dropping the handle
+/// leaves the motion running.
+#[derive(Debug)]
+#[must_use = "dropping a handle leaves the motion running; call `cancel` to stop it"]
+pub struct MotionHandle<C: Clock = SystemClock> {
+    state: Rc<MotionState>,
+    clock: C,
+}
+
+impl<C: Clock> MotionHandle<C> {
+    /// Returns whether the motion has ended.
+    pub fn is_done(&self) -> bool {
+        self.state.done.get()
+    }
+
+    /// Stops the motion.
+    ///
+    /// The motion stops the drivetrain at its next control loop iteration and
+    /// ends with [`DriveError::Cancelled`]. Cancelling a motion that has
+    /// ended does nothing.
+    pub fn cancel(&self) {
+        self.state.cancel();
+    }
+
+    /// Returns the distance the robot has covered during the motion.
+    ///
+    /// This is the length of the path the tracking centre has driven, not
+    /// the straight-line distance from the start.
+    pub fn distance(&self) -> QLength {
+        QLength::from_meters(self.state.distance.get())
+    }
+
+    /// Returns the fraction of the motion completed, from 0 to 1.
+    ///
+    /// Driving straight measures travel along the starting heading, turning
+    /// measures the heading change, trajectories measure time and pure
+    /// pursuit measures distance along the path. A motion made of a turn and
+    /// a drive, such as [`BackgroundChassis::shoot_to_pose`], reports each in
+    /// turn.
+    pub fn progress(&self) -> f64 {
+        self.state.progress.get()
+    }
+
+    /// Waits until the robot has covered `distance`, or the motion has ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - Distance along the driven path, as [`distance`](Self::distance)
+    pub async fn wait_until_distance(&self, distance: QLength) {
+        while !self.is_done() && self.state.distance.get() < distance.as_meters() {
+            self.clock.sleep(POLL_PERIOD).await;
+        }
+    }
+
+    /// Waits until the motion has completed a fraction of its work, or has
+    /// ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `fraction` - Fraction of the motion, from 0 to 1, as
+    ///   [`progress`](Self::progress)
+    pub async fn wait_until_progress(&self, fraction: f64) {
+        while !self.is_done() && self.progress() < fraction {
+            self.clock.sleep(POLL_PERIOD).await;
+        }
+    }
+}
+
+impl<C: Clock> Future for MotionHandle<C> {
+    type Output = Result<ExitReason, DriveError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.state.result.borrow_mut().take() {
+            Some(result) => Poll::Ready(result),
+            // The result was taken by an earlier poll.
+            None if self.state.done.get() => Poll::Ready(Err(DriveError::Cancelled)),
+            None => {
+                *self.state.waker.borrow_mut() = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// An [`OdomChassis`] whose motions run in the background.
+///
+/// Each motion returns a [`MotionHandle`] immediately and runs in its own
+/// task. Only one motion drives at a time: starting a motion cancels the one
+/// running and waits for it to stop the drivetrain.
+///
+/// The pose can be read and set while a motion runs. Use
+/// [`into_inner`](Self::into_inner) to get the chassis back, for example to
+/// change its configuration or hand it to driver control.
+pub struct BackgroundChassis<
+    M: MotorOutput = Motor,
+    G: Gyro = InertialSensor,
+    C: Clock = SystemClock,
+> {
+    /// The chassis, or `None` while a motion has it
+    chassis: Rc<RefCell<Option<OdomChassis<M, G, C>>>>,
+    /// The chassis's pose source, readable while a motion runs
+    tracking: Rc<dyn PoseSource>,
+    clock: C,
+    /// The latest motion started
+    current: Option<Rc<MotionState>>,
+}
+
+impl<M, G, C> BackgroundChassis<M, G, C>
+where
+    M: MotorOutput + Send + 'static,
+    G: Gyro + 'static,
+    C: Clock + 'static,
+{
+    /// Creates a `BackgroundChassis` running motions on `chassis`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chassis` - The configured chassis
+    pub fn new(chassis: OdomChassis<M, G, C>) -> Self {
+        Self {
+            tracking: chassis.pose_source(),
+            clock: chassis.clock().clone(),
+            chassis: Rc::new(RefCell::new(Some(chassis))),
+            current: None,
+        }
+    }
+
+    /// Starts a motion in the background.
+    ///
+    /// The motion runs once the previous motion, which is cancelled, has
+    /// released the chassis. If it fails, the drivetrain is stopped even if
+    /// the motion did not stop it itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `motion` - Drives the chassis, for example
+    ///   `async move |chassis| chassis.shoot(distance).await`
+    ///
+    /// # Returns
+    ///
+    /// A handle to the running motion. It ends with
+    /// [`DriveError::Cancelled`] if it is preempted before it starts.
+    pub fn run<F>(&mut self, motion: F) -> MotionHandle<C>
+    where
+        F: AsyncFnOnce(&mut OdomChassis<M, G, C>) -> Result<ExitReason, DriveError> + 'static,
+    {
+        self.cancel();
+
+        let state = Rc::new(MotionState::default());
+        let slot = Rc::clone(&self.chassis);
+        let clock = self.clock.clone();
+        let task_state = Rc::clone(&state);
+        spawn(async move {
+            // Wait for the preempted motion to stop and release the chassis.
+            let mut chassis = loop {
+                if task_state.is_cancelled() {
+                    task_state.finish(Err(DriveError::Cancelled));
+                    return;
+                }
+                if let Some(chassis) = slot.borrow_mut().take() {
+                    break chassis;
+                }
+                clock.sleep(POLL_PERIOD).await;
+            };
+
+            chassis.set_motion(Some(Rc::clone(&task_state)));
+            let result = motion(&mut chassis).await;
+            if result.is_err() {
+                // A failed motor command or a custom motion may have left the
+                // drivetrain running.
+                let _ = chassis.stop().await;
+            }
+            chassis.set_motion(None);
+            *slot.borrow_mut() = Some(chassis);
+            task_state.finish(result);
+        })
+        .detach();
+
+        self.current = Some(Rc::clone(&state));
+        MotionHandle {
+            state,
+            clock: self.clock.clone(),
+        }
+    }
+
+    /// Drives straight in the background. See [`OdomChassis::shoot`].
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance to travel (positive = forward, negative = backward)
+    pub fn shoot(&mut self, distance: QLength) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.shoot(distance).await)
+    }
+
+    /// Turns in place in the background. See [`OdomChassis::turn`].
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    pub fn turn(&mut self, target: QAngle) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.turn(target).await)
+    }
+
+    /// Follows a trajectory in the background. See
+    /// [`OdomChassis::trajectory`].
+    ///
+    /// # Arguments
+    ///
+    /// * `traj` - The trajectory to follow
+    pub fn trajectory(&mut self, traj: Trajectory) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.trajectory(&traj).await)
+    }
+
+    /// Follows a path with pure pursuit in the background. See
+    /// [`OdomChassis::pursuit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The pure pursuit controller
+    pub fn pursuit(&mut self, path: PurePursuit) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.pursuit(&path).await)
+    }
+
+    /// Turns to face a pose in the background. See
+    /// [`OdomChassis::turn_to_pose`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to face
+    pub fn turn_to_pose(&mut self, pose: Pose) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.turn_to_pose(pose).await)
+    }
+
+    /// Turns to and drives to a pose in the background. See
+    /// [`OdomChassis::shoot_to_pose`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    pub fn shoot_to_pose(&mut self, pose: Pose) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.shoot_to_pose(pose).await)
+    }
+
+    /// Drives to a pose with the boomerang controller in the background. See
+    /// [`OdomChassis::move_to_pose`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    /// * `params` - Direction, lead and speed limits
+    pub fn move_to_pose(&mut self, pose: Pose, params: MoveParams) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.move_to_pose(pose, params).await)
+    }
+
+    /// Drives to a field point in the background. See
+    /// [`OdomChassis::move_to_point`].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The field point to reach, in metres
+    /// * `params` - Direction and speed limits
+    pub fn move_to_point(&mut self, point: Vec2<f64>, params: MoveParams) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.move_to_point(point, params).await)
+    }
+
+    /// Swings about one side to a heading in the background. See
+    /// [`OdomChassis::swing`].
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `locked` - The side that holds still
+    /// * `direction` - Which way to rotate to the target
+    pub fn swing(
+        &mut self,
+        target: QAngle,
+        locked: Side,
+        direction: TurnDirection,
+    ) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.swing(target, locked, direction).await)
+    }
+
+    /// Drives around a circle to a heading in the background. See
+    /// [`OdomChassis::arc`].
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `radius` - The radius of the circle
+    /// * `centre` - The side the circle's centre lies on
+    /// * `direction` - Which way to rotate to the target
+    pub fn arc(
+        &mut self,
+        target: QAngle,
+        radius: QLength,
+        centre: Side,
+        direction: TurnDirection,
+    ) -> MotionHandle<C> {
+        self.run(async move |chassis| chassis.arc(target, radius, centre, direction).await)
+    }
+
+    /// Cancels the latest motion, if it is still running.
+    pub fn cancel(&mut self) {
+        if let Some(motion) = &self.current {
+            motion.cancel();
+        }
+    }
+
+    /// Cancels the latest motion, waits until it has ended and stops the
+    /// drivetrain.
+    ///
+    /// The drivetrain is stopped even if no motion was running, since a
+    /// chained motion leaves it moving after it ends. If another motion is
+    /// started while this waits, it is left to drive.
+    ///
+    /// The returned future does not borrow the chassis, so the chassis can
+    /// be used while it waits.
+    pub fn stop(&mut self) -> impl Future<Output = ()> + use<M, G, C> {
+        self.cancel();
+        let current = self.current.clone();
+        let slot = Rc::clone(&self.chassis);
+        let clock = self.clock.clone();
+        async move {
+            while current.as_ref().is_some_and(|motion| !motion.done.get()) {
+                clock.sleep(POLL_PERIOD).await;
+            }
+
+            let Some(mut chassis) = slot.borrow_mut().take() else {
+                return;
+            };
+            let _ = chassis.stop().await;
+            *slot.borrow_mut() = Some(chassis);
+        }
+    }
+
+    /// Returns whether no motion is running.
+    pub fn is_idle(&self) -> bool {
+        self.current.as_ref().is_none_or(|motion| motion.done.get())
+    }
+
+    /// Returns the clock the motions run on.
+    pub(crate) fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Returns the current pose estimate.
+    pub fn pose(&self) -> Pose {
+        self.tracking.pose()
+    }
+
+    /// Sets the pose estimate, even while a motion runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The new pose to set
+    pub fn set_pose(&self, pose: &Pose) {
+        self.tracking.set_pose(*pose);
+    }
+
+    /// Cancels any running motion and returns the chassis once it has
+    /// stopped.
+    pub async fn into_inner(mut self) -> OdomChassis<M, G, C> {
+        self.cancel();
+        loop {
+            if let Some(chassis) = self.chassis.borrow_mut().take() {
+                return chassis;
+            }
+            self.clock.sleep(POLL_PERIOD).await;
+        }
+    }
+}
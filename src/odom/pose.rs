@@ -23,11 +23,72 @@
 //! // Calculate distance between poses
 //! let dist = pose.distance(other);
 //! ```
+//!
+//! # Rigid-Body Motion
+//!
+//! Poses form the group SE(2): [`Mul`](std::ops::Mul) composes them,
+//! [`inverse`](Pose::inverse) undoes one, and [`relative_to`](Pose::relative_to)
+//! expresses one pose in another's frame. A [`Twist`] describes a constant
+//! velocity motion; [`exp`](Pose::exp) follows one from a pose, and
+//! [`log`](Pose::log) finds the twist that connects two poses.
+//!
+//! ```
+//! use kernelvex::odom::pose::{Pose, Twist};
+//! use kernelvex::util::si::{QAngle, Vec2};
+//!
+//! // A quarter circle of radius 1 to the left.
+//! let start = Pose::default();
+//! let end = start.exp(Twist::new(std::f64::consts::FRAC_PI_2, 0.0, std::f64::consts::FRAC_PI_2));
+//! assert!((end.position().x - 1.0).abs() < 1e-9);
+//! assert!((end.position().y - 1.0).abs() < 1e-9);
+//!
+//! let twist = start.log(end);
+//! assert!((twist.dtheta - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+//! ```
 
 use crate::util::si::{QAngle, QLength};
 use nalgebra::base::Matrix3;
 use crate::Vec2;
 
+/// A constant-velocity motion over one unit of time, in the frame of the pose
+/// it starts from.
+///
+/// A twist is an element of the Lie algebra se(2). Following it with
+/// [`Pose::exp`] moves along a circular arc (or a straight line when
+/// `dtheta` is zero).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Twist {
+    /// Forward travel in meters
+    pub dx: f64,
+    /// Leftward travel in meters
+    pub dy: f64,
+    /// Counter-clockwise rotation in radians
+    pub dtheta: f64,
+}
+
+impl Twist {
+    /// Creates a twist.
+    ///
+    /// # Arguments
+    ///
+    /// * `dx` - Forward travel in meters
+    /// * `dy` - Leftward travel in meters
+    /// * `dtheta` - Counter-clockwise rotation in radians
+    pub const fn new(dx: f64, dy: f64, dtheta: f64) -> Self {
+        Self { dx, dy, dtheta }
+    }
+}
+
+/// Scales every component of a twist, for example to turn a velocity into
+/// the motion over a time step.
+impl std::ops::Mul<f64> for Twist {
+    type Output = Twist;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Twist::new(self.dx * rhs, self.dy * rhs, self.dtheta * rhs)
+    }
+}
+
 /// Represents odom 2D pose (position and orientation) in space.
 ///
 /// A pose consists of an (x, y) position and odom heading angle. The pose is
@@ -294,11 +355,139 @@ impl Pose {
     pub fn move_global(&self, other: Pose) -> Pose {
         other * *self
     }
+
+    /// Returns the pose that undoes this one.
+    ///
+    /// Composing a pose with its inverse, in either order, gives the identity.
+    ///
+    /// # Returns
+    ///
+    /// The inverse transformation, with heading negated.
+    pub fn inverse(&self) -> Pose {
+        let (sin, cos) = (self.heading.sin(), self.heading.cos());
+        let position = self.position();
+
+        Pose::new(
+            Vec2::<f64>::new(
+                -(cos * position.x + sin * position.y),
+                sin * position.x - cos * position.y,
+            ),
+            -self.heading,
+        )
+    }
+
+    /// Expresses this pose in the frame of another.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The pose whose frame to use
+    ///
+    /// # Returns
+    ///
+    /// This pose as seen from `origin`: `x` is the distance ahead of it, `y`
+    /// the distance to its left, and heading the rotation relative to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kernelvex::odom::pose::Pose;
+    /// use kernelvex::util::si::{QAngle, Vec2};
+    ///
+    /// let robot = Pose::new(Vec2::new(1.0, 1.0), QAngle::from_degrees(90.0));
+    /// let goal = Pose::new(Vec2::new(1.0, 3.0), QAngle::from_degrees(90.0));
+    /// let local = goal.relative_to(robot);
+    /// assert!((local.position().x - 2.0).abs() < 1e-9);
+    /// assert!(local.position().y.abs() < 1e-9);
+    /// ```
+    pub fn relative_to(&self, origin: Pose) -> Pose {
+        origin.inverse() * *self
+    }
+
+    /// Applies a transformation expressed in this pose's frame.
+    ///
+    /// This is the inverse of [`relative_to`](Self::relative_to):
+    /// `origin.transform_by(pose.relative_to(origin))` is `pose`.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The motion to apply, relative to this pose
+    ///
+    /// # Returns
+    ///
+    /// The pose reached by moving `transform` from this pose.
+    pub fn transform_by(&self, transform: Pose) -> Pose {
+        *self * transform
+    }
+
+    /// Follows a twist from this pose.
+    ///
+    /// Uses the closed-form SE(2) exponential map:
+    /// ```text
+    /// x = dx · sin(dθ) / dθ - dy · (1 - cos(dθ)) / dθ
+    /// y = dx · (1 - cos(dθ)) / dθ + dy · sin(dθ) / dθ
+    /// ```
+    /// in this pose's frame, with Taylor expansions near `dθ = 0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `twist` - The motion to follow, in this pose's frame
+    ///
+    /// # Returns
+    ///
+    /// The pose at the end of the arc.
+    pub fn exp(&self, twist: Twist) -> Pose {
+        let theta = twist.dtheta;
+        let (s, c) = if theta.abs() < 1e-9 {
+            (1.0 - theta * theta / 6.0, 0.5 * theta)
+        } else {
+            (libm::sin(theta) / theta, (1.0 - libm::cos(theta)) / theta)
+        };
+
+        let transform = Pose::new(
+            Vec2::<f64>::new(twist.dx * s - twist.dy * c, twist.dx * c + twist.dy * s),
+            QAngle::from_radians(theta),
+        );
+        self.transform_by(transform)
+    }
+
+    /// Finds the twist that moves this pose to another.
+    ///
+    /// This is the inverse of [`exp`](Self::exp): `self.exp(self.log(end))`
+    /// is `end`. The rotation is taken the short way round, within ±π.
+    ///
+    /// # Arguments
+    ///
+    /// * `end` - The pose to reach
+    ///
+    /// # Returns
+    ///
+    /// The twist, in this pose's frame.
+    pub fn log(&self, end: Pose) -> Twist {
+        let transform = end.relative_to(*self);
+        let theta = transform.heading.remainder(QAngle::TAU).as_radians();
+        let half = theta / 2.0;
+        let cos_minus_one = libm::cos(theta) - 1.0;
+
+        // half · cot(half), which tends to 1 - θ²/12 near zero.
+        let half_cot = if cos_minus_one.abs() < 1e-9 {
+            1.0 - theta * theta / 12.0
+        } else {
+            -(half * libm::sin(theta)) / cos_minus_one
+        };
+
+        let position = transform.position();
+        Twist::new(
+            half_cot * position.x + half * position.y,
+            -half * position.x + half_cot * position.y,
+            theta,
+        )
+    }
 }
 
 /// Adds two poses by summing their positions.
 ///
-/// The heading is preserved from the left-hand operand.
+/// The heading is preserved from the left-hand operand. This is not
+/// rigid-body composition; use [`Pose::transform_by`] or `*` for that.
 impl std::ops::Add<Pose> for Pose {
     type Output = Pose;
     fn add(self, other: Pose) -> Pose {
@@ -311,7 +500,8 @@ impl std::ops::Add<Pose> for Pose {
 
 /// Subtracts two poses by subtracting their positions.
 ///
-/// The heading is preserved from the left-hand operand.
+/// The heading is preserved from the left-hand operand. To express one pose
+/// in another's frame, use [`Pose::relative_to`].
 impl std::ops::Sub<Pose> for Pose {
     type Output = Pose;
     fn sub(self, other: Pose) -> Pose {
@@ -0,0 +1,691 @@
+//! Autonomous routines built from sequential and parallel steps.
+//!
+//! Awaiting chassis motions one after another makes it easy to drop their
+//! errors and hard to run a mechanism alongside a drive. A [`Routine`] lists
+//! [`Step`]s instead: chassis motions, waits and mechanism actions, plus
+//! nested sequences and parallel groups. Running it on a
+//! [`BackgroundChassis`] returns a [`RoutineReport`] of what ran, how each
+//! step ended and how long it took.
+//!
+//! Each step may have a timeout, after which it is cut short, and an
+//! [`ErrorPolicy`] deciding whether a failure or timeout aborts the rest of
+//! the routine. Steps skipped by an abort are still listed in the report,
+//! as are the steps a timed-out group had finished.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let routine = Routine::new()
+//!     .then(Step::shoot(QLength::from_meters(1.0)).with_timeout(Duration::from_secs(2)))
+//!     .then(Step::parallel([
+//!         Routine::new().then(Step::turn(QAngle::from_degrees(90.0))),
+//!         Routine::new().then(Step::action("intake", async { intake.set_voltage(12.0) })),
+//!     ]))
+//!     .then(Step::wait(Duration::from_millis(250)))
+//!     .then(Step::pursuit(path).with_policy(ErrorPolicy::Continue));
+//!
+//! let report = routine.run(&mut chassis).await;
+//! for step in &report.steps {
+//!     println!("{}: {:?} in {:?}", step.name, step.outcome, step.elapsed);
+//! }
+//! ```
+
+use crate::control::boomerang::MoveParams;
+use crate::control::exit::ExitReason;
+use crate::control::purepursuit::PurePursuit;
+use crate::dt::differential::Side;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::motion::trajectory::Trajectory;
+use crate::odom::chassis::{DriveError, TurnDirection};
+use crate::odom::motion::{BackgroundChassis, MotionHandle};
+use crate::odom::pose::Pose;
+use crate::util::clock::{Clock, SystemClock};
+use crate::util::si::{QAngle, QLength, Vec2};
+use core::fmt::Debug;
+use core::future::{Future, poll_fn};
+use core::pin::{Pin, pin};
+use core::task::Poll;
+use core::time::Duration;
+use std::cell::RefCell;
+use vexide::smart::imu::InertialSensor;
+use vexide::smart::motor::Motor;
+
+/// A boxed future borrowed for the routine's lifetime.
+type LocalFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// Starts a chassis motion.
+type StartMotion<'a, M, G, C> =
+    Box<dyn FnOnce(&mut BackgroundChassis<M, G, C>) -> MotionHandle<C> + 'a>;
+
+/// The chassis shared by the steps of a running routine.
+type SharedChassis<'c, M, G, C> = RefCell<&'c mut BackgroundChassis<M, G, C>>;
+
+/// What a routine does when a step fails or times out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPolicy {
+    /// Skip the rest of the routine.
+    #[default]
+    Abort,
+    /// Carry on with the next step.
+    Continue,
+}
+
+/// Why a step failed.
+#[derive(Debug)]
+pub enum StepError {
+    /// A chassis motion failed.
+    Drive(DriveError),
+    /// A mechanism action returned an error, formatted with [`Debug`].
+    Action(String),
+    /// A routine in the group aborted; see the step's children.
+    Group,
+}
+
+/// How a step ended.
+#[derive(Debug)]
+pub enum StepOutcome {
+    /// The step finished. Chassis motions include why they ended.
+    Completed(Option<ExitReason>),
+    /// The step ran out of time and was cut short, or a chassis motion ended
+    /// on its own exit timeout with [`ExitReason::Timeout`].
+    TimedOut,
+    /// The step failed.
+    Failed(StepError),
+    /// An earlier step aborted the routine, so this one never ran.
+    Skipped,
+}
+
+impl StepOutcome {
+    /// Returns whether the step finished without failing or timing out.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Completed(_))
+    }
+}
+
+/// The record of one step.
+#[derive(Debug)]
+pub struct StepReport {
+    /// The step's name
+    pub name: String,
+    /// How the step ended
+    pub outcome: StepOutcome,
+    /// When the step started, measured from the start of the outermost
+    /// routine
+    pub started: Duration,
+    /// How long the step ran
+    pub elapsed: Duration,
+    /// Reports of the routines inside a sequence or parallel group
+    pub children: Vec<RoutineReport>,
+}
+
+/// The record of a routine run.
+#[derive(Debug, Default)]
+pub struct RoutineReport {
+    /// Every step, in the order they were listed
+    pub steps: Vec<StepReport>,
+    /// Whether a step aborted the routine
+    pub aborted: bool,
+    /// How long the routine ran
+    pub elapsed: Duration,
+}
+
+impl RoutineReport {
+    /// Returns whether every step finished without failing or timing out.
+    pub fn succeeded(&self) -> bool {
+        self.steps.iter().all(|step| step.outcome.is_ok())
+    }
+}
+
+/// What a step does.
+enum Action<'a, M: MotorOutput, G: Gyro, C: Clock> {
+    Motion(StartMotion<'a, M, G, C>),
+    Wait(Duration),
+    Mechanism(LocalFuture<'a, Result<(), String>>),
+    Sequence(Routine<'a, M, G, C>),
+    Parallel(Vec<Routine<'a, M, G, C>>),
+}
+
+/// One step of a [`Routine`].
+///
+/// Use the `with_*` builder methods to set a timeout, an error policy or a
+/// name for the report.
+pub struct Step<'a, M: MotorOutput = Motor, G: Gyro = InertialSensor, C: Clock = SystemClock> {
+    name: String,
+    action: Action<'a, M, G, C>,
+    timeout: Option<Duration>,
+    policy: ErrorPolicy,
+}
+
+impl<'a, M, G, C> Step<'a, M, G, C>
+where
+    M: MotorOutput + Send + 'static,
+    G: Gyro + 'static,
+    C: Clock + 'static,
+{
+    fn new(name: &str, action: Action<'a, M, G, C>) -> Self {
+        Self {
+            name: name.into(),
+            action,
+            timeout: None,
+            policy: ErrorPolicy::Abort,
+        }
+    }
+
+    /// Starts any chassis motion.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the step in the report
+    /// * `start` - Starts the motion, for example
+    ///   `|chassis| chassis.shoot(distance)`
+    pub fn motion<F>(name: &str, start: F) -> Self
+    where
+        F: FnOnce(&mut BackgroundChassis<M, G, C>) -> MotionHandle<C> + 'a,
+    {
+        Self::new(name, Action::Motion(Box::new(start)))
+    }
+
+    /// Drives straight. See [`OdomChassis::shoot`](crate::OdomChassis::shoot).
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - The distance to travel (positive = forward, negative = backward)
+    pub fn shoot(distance: QLength) -> Self {
+        Self::motion("shoot", move |chassis| chassis.shoot(distance))
+    }
+
+    /// Turns in place. See [`OdomChassis::turn`](crate::OdomChassis::turn).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    pub fn turn(target: QAngle) -> Self {
+        Self::motion("turn", move |chassis| chassis.turn(target))
+    }
+
+    /// Turns to face a pose. See
+    /// [`OdomChassis::turn_to_pose`](crate::OdomChassis::turn_to_pose).
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to face
+    pub fn turn_to_pose(pose: Pose) -> Self {
+        Self::motion("turn_to_pose", move |chassis| chassis.turn_to_pose(pose))
+    }
+
+    /// Turns to and drives to a pose. See
+    /// [`OdomChassis::shoot_to_pose`](crate::OdomChassis::shoot_to_pose).
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    pub fn shoot_to_pose(pose: Pose) -> Self {
+        Self::motion("shoot_to_pose", move |chassis| chassis.shoot_to_pose(pose))
+    }
+
+    /// Drives to a pose with the boomerang controller. See
+    /// [`OdomChassis::move_to_pose`](crate::OdomChassis::move_to_pose).
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The target pose to reach
+    /// * `params` - Direction, lead and speed limits
+    pub fn move_to_pose(pose: Pose, params: MoveParams) -> Self {
+        Self::motion("move_to_pose", move |chassis| {
+            chassis.move_to_pose(pose, params)
+        })
+    }
+
+    /// Drives to a field point. See
+    /// [`OdomChassis::move_to_point`](crate::OdomChassis::move_to_point).
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The field point to reach, in metres
+    /// * `params` - Direction and speed limits
+    pub fn move_to_point(point: Vec2<f64>, params: MoveParams) -> Self {
+        Self::motion("move_to_point", move |chassis| {
+            chassis.move_to_point(point, params)
+        })
+    }
+
+    /// Swings about one side to a heading. See
+    /// [`OdomChassis::swing`](crate::OdomChassis::swing).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `locked` - The side that holds still
+    /// * `direction` - Which way to rotate to the target
+    pub fn swing(target: QAngle, locked: Side, direction: TurnDirection) -> Self {
+        Self::motion("swing", move |chassis| {
+            chassis.swing(target, locked, direction)
+        })
+    }
+
+    /// Drives around a circle to a heading. See
+    /// [`OdomChassis::arc`](crate::OdomChassis::arc).
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The target absolute heading
+    /// * `radius` - The radius of the circle
+    /// * `centre` - The side the circle's centre lies on
+    /// * `direction` - Which way to rotate to the target
+    pub fn arc(target: QAngle, radius: QLength, centre: Side, direction: TurnDirection) -> Self {
+        Self::motion("arc", move |chassis| {
+            chassis.arc(target, radius, centre, direction)
+        })
+    }
+
+    /// Follows a trajectory. See
+    /// [`OdomChassis::trajectory`](crate::OdomChassis::trajectory).
+    ///
+    /// # Arguments
+    ///
+    /// * `traj` - The trajectory to follow
+    pub fn trajectory(traj: Trajectory) -> Self {
+        Self::motion("trajectory", move |chassis| chassis.trajectory(traj))
+    }
+
+    /// Follows a path with pure pursuit. See
+    /// [`OdomChassis::pursuit`](crate::OdomChassis::pursuit).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The pure pursuit controller
+    pub fn pursuit(path: PurePursuit) -> Self {
+        Self::motion("pursuit", move |chassis| chassis.pursuit(path))
+    }
+
+    /// Waits without moving.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long to wait
+    pub fn wait(duration: Duration) -> Self {
+        Self::new("wait", Action::Wait(duration))
+    }
+
+    /// Runs a mechanism action, such as spinning an intake or firing a
+    /// piston.
+    ///
+    /// The action starts when the step is reached. If it times out it is
+    /// dropped where it last waited, so leave mechanisms in a safe state at
+    /// each await.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the step in the report
+    /// * `action` - The work to do; an error fails the step
+    pub fn action<E, F>(name: &str, action: F) -> Self
+    where
+        E: Debug,
+        F: Future<Output = Result<(), E>> + 'a,
+    {
+        let action = async move { action.await.map_err(|err| format!("{err:?}")) };
+        Self::new(name, Action::Mechanism(Box::pin(action)))
+    }
+
+    /// Runs a routine as a single step.
+    ///
+    /// The step fails if the routine aborts.
+    ///
+    /// # Arguments
+    ///
+    /// * `routine` - The steps to run in order
+    pub fn sequence(routine: Routine<'a, M, G, C>) -> Self {
+        Self::new("sequence", Action::Sequence(routine))
+    }
+
+    /// Runs routines at the same time and waits for all of them.
+    ///
+    /// Only one chassis motion drives at a time, so a motion started in one
+    /// routine cancels a motion running in another. The step fails if any
+    /// routine aborts.
+    ///
+    /// # Arguments
+    ///
+    /// * `routines` - The routines to run together
+    pub fn parallel(routines: impl IntoIterator<Item = Routine<'a, M, G, C>>) -> Self {
+        Self::new("parallel", Action::Parallel(routines.into_iter().collect()))
+    }
+
+    /// Sets the step's name in the report.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Cuts the step short after `timeout`.
+    ///
+    /// A chassis motion is cancelled, stopping the drivetrain. The step is
+    /// reported as [`StepOutcome::TimedOut`], which counts as a failure for
+    /// its [`ErrorPolicy`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets what happens when the step fails or times out.
+    pub fn with_policy(mut self, policy: ErrorPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Runs the step.
+    ///
+    /// # Returns
+    ///
+    /// How the step ended and the reports of any routines inside it.
+    async fn execute(
+        self,
+        chassis: &SharedChassis<'_, M, G, C>,
+        clock: &C,
+        epoch: Duration,
+    ) -> (StepOutcome, Vec<RoutineReport>) {
+        let timeout = self.timeout;
+        match self.action {
+            Action::Motion(start) => {
+                let handle = start(&mut chassis.borrow_mut());
+                let mut handle = pin!(handle);
+                match within(clock, timeout, handle.as_mut()).await {
+                    Some(Ok(ExitReason::Timeout)) => (StepOutcome::TimedOut, Vec::new()),
+                    Some(Ok(reason)) => (StepOutcome::Completed(Some(reason)), Vec::new()),
+                    Some(Err(err)) => (StepOutcome::Failed(StepError::Drive(err)), Vec::new()),
+                    None => {
+                        handle.cancel();
+                        let _ = handle.await;
+                        (StepOutcome::TimedOut, Vec::new())
+                    }
+                }
+            }
+            Action::Wait(duration) => {
+                match within(clock, timeout, pin!(clock.sleep(duration))).await {
+                    Some(()) => (StepOutcome::Completed(None), Vec::new()),
+                    None => (StepOutcome::TimedOut, Vec::new()),
+                }
+            }
+            Action::Mechanism(mut action) => match within(clock, timeout, action.as_mut()).await {
+                Some(Ok(())) => (StepOutcome::Completed(None), Vec::new()),
+                Some(Err(err)) => (StepOutcome::Failed(StepError::Action(err)), Vec::new()),
+                None => (StepOutcome::TimedOut, Vec::new()),
+            },
+            Action::Sequence(routine) => {
+                let progress = vec![RefCell::new(Progress::default())];
+                let mut group = routine.execute(chassis, clock, epoch, &progress[0]);
+                let finished = within(clock, timeout, group.as_mut()).await.is_some();
+                drop(group);
+                group_outcome(chassis, clock, epoch, progress, finished).await
+            }
+            Action::Parallel(routines) => {
+                let progress: Vec<_> = routines
+                    .iter()
+                    .map(|_| RefCell::new(Progress::default()))
+                    .collect();
+                let group = join(
+                    routines
+                        .into_iter()
+                        .zip(&progress)
+                        .map(|(routine, progress)| routine.execute(chassis, clock, epoch, progress))
+                        .collect(),
+                );
+                let finished = within(clock, timeout, pin!(group)).await.is_some();
+                group_outcome(chassis, clock, epoch, progress, finished).await
+            }
+        }
+    }
+}
+
+/// A list of steps to run in order.
+///
+/// Build one with [`then`](Self::then) and run it with [`run`](Self::run).
+pub struct Routine<'a, M: MotorOutput = Motor, G: Gyro = InertialSensor, C: Clock = SystemClock> {
+    steps: Vec<Step<'a, M, G, C>>,
+}
+
+impl<'a, M, G, C> Routine<'a, M, G, C>
+where
+    M: MotorOutput + Send + 'static,
+    G: Gyro + 'static,
+    C: Clock + 'static,
+{
+    /// Creates an empty routine.
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Appends a step.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - The step to run after the ones already listed
+    pub fn then(mut self, step: Step<'a, M, G, C>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Runs the routine to the end, or until a step aborts it.
+    ///
+    /// # Arguments
+    ///
+    /// * `chassis` - The chassis the motions drive
+    ///
+    /// # Returns
+    ///
+    /// A report of every step, including any that were skipped.
+    pub async fn run(self, chassis: &mut BackgroundChassis<M, G, C>) -> RoutineReport {
+        let clock = chassis.clock().clone();
+        let epoch = clock.now();
+        let chassis = RefCell::new(chassis);
+        let progress = RefCell::new(Progress::default());
+        self.execute(&chassis, &clock, epoch, &progress).await;
+        progress.into_inner().report
+    }
+
+    /// Runs the steps in order, boxed so groups can nest routines.
+    ///
+    /// The report is kept in `progress` as the steps end, so it survives the
+    /// routine being cut short.
+    fn execute<'r>(
+        self,
+        chassis: &'r SharedChassis<'_, M, G, C>,
+        clock: &'r C,
+        epoch: Duration,
+        progress: &'r RefCell<Progress>,
+    ) -> LocalFuture<'r, ()>
+    where
+        'a: 'r,
+    {
+        Box::pin(async move {
+            {
+                let mut progress = progress.borrow_mut();
+                progress.start = clock.now();
+                progress.pending = self.steps.iter().map(|step| step.name.clone()).collect();
+            }
+
+            for step in self.steps {
+                let name = step.name.clone();
+                let started = clock.now();
+                {
+                    let mut progress = progress.borrow_mut();
+                    progress.pending.remove(0);
+                    if progress.report.aborted {
+                        progress.report.steps.push(StepReport {
+                            name,
+                            outcome: StepOutcome::Skipped,
+                            started: started.saturating_sub(epoch),
+                            elapsed: Duration::ZERO,
+                            children: Vec::new(),
+                        });
+                        continue;
+                    }
+                    progress.running = Some((name.clone(), started));
+                }
+
+                let policy = step.policy;
+                let (outcome, children) = step.execute(chassis, clock, epoch).await;
+
+                let mut progress = progress.borrow_mut();
+                progress.running = None;
+                progress.report.aborted = !outcome.is_ok() && policy == ErrorPolicy::Abort;
+                progress.report.steps.push(StepReport {
+                    name,
+                    outcome,
+                    started: started.saturating_sub(epoch),
+                    elapsed: clock.now().saturating_sub(started),
+                    children,
+                });
+            }
+
+            let mut progress = progress.borrow_mut();
+            progress.report.elapsed = clock.now().saturating_sub(progress.start);
+            progress.finished = true;
+        })
+    }
+}
+
+impl<M, G, C> Default for Routine<'_, M, G, C>
+where
+    M: MotorOutput + Send + 'static,
+    G: Gyro + 'static,
+    C: Clock + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Awaits `future`, giving up after `timeout` if one is set.
+///
+/// # Returns
+///
+/// The future's output, or `None` if the timeout elapsed first.
+async fn within<C: Clock, F: Future + ?Sized>(
+    clock: &C,
+    timeout: Option<Duration>,
+    mut future: Pin<&mut F>,
+) -> Option<F::Output> {
+    let Some(timeout) = timeout else {
+        return Some(future.await);
+    };
+    let mut sleep = pin!(clock.sleep(timeout));
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        sleep.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+/// Awaits every future at once.
+///
+/// # Returns
+///
+/// The outputs, in the order the futures were given.
+async fn join<T>(futures: Vec<LocalFuture<'_, T>>) -> Vec<T> {
+    let mut pending: Vec<_> = futures.into_iter().map(Some).collect();
+    let mut outputs: Vec<Option<T>> = pending.iter().map(|_| None).collect();
+    poll_fn(|cx| {
+        for (future, output) in pending.iter_mut().zip(outputs.iter_mut()) {
+            if let Some(fut) = future
+                && let Poll::Ready(value) = fut.as_mut().poll(cx)
+            {
+                *output = Some(value);
+                *future = None;
+            }
+        }
+        if pending.iter().all(Option::is_none) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+    outputs.into_iter().flatten().collect()
+}
+
+/// A routine's report as it runs.
+#[derive(Debug, Default)]
+struct Progress {
+    /// The steps that have ended
+    report: RoutineReport,
+    /// When the routine started
+    start: Duration,
+    /// Name and start time of the step running
+    running: Option<(String, Duration)>,
+    /// Names of the steps not reached yet
+    pending: Vec<String>,
+    /// Whether the routine ran to the end
+    finished: bool,
+}
+
+impl Progress {
+    /// Returns the routine's report.
+    ///
+    /// A routine cut short at `now` reports its running step as timed out
+    /// and the steps after it as skipped.
+    fn into_report(self, now: Duration, epoch: Duration) -> RoutineReport {
+        let mut report = self.report;
+        if self.finished {
+            return report;
+        }
+
+        if let Some((name, started)) = self.running {
+            report.steps.push(StepReport {
+                name,
+                outcome: StepOutcome::TimedOut,
+                started: started.saturating_sub(epoch),
+                elapsed: now.saturating_sub(started),
+                children: Vec::new(),
+            });
+        }
+        report
+            .steps
+            .extend(self.pending.into_iter().map(|name| StepReport {
+                name,
+                outcome: StepOutcome::Skipped,
+                started: now.saturating_sub(epoch),
+                elapsed: Duration::ZERO,
+                children: Vec::new(),
+            }));
+        report.aborted = true;
+        report.elapsed = now.saturating_sub(self.start);
+        report
+    }
+}
+
+/// Turns the progress of a group's routines into the group step's outcome.
+///
+/// A group that timed out may have left a motion running, so the chassis is
+/// stopped before the routine moves on.
+async fn group_outcome<M, G, C>(
+    chassis: &SharedChassis<'_, M, G, C>,
+    clock: &C,
+    epoch: Duration,
+    progress: Vec<RefCell<Progress>>,
+    finished: bool,
+) -> (StepOutcome, Vec<RoutineReport>)
+where
+    M: MotorOutput + Send + 'static,
+    G: Gyro + 'static,
+    C: Clock + 'static,
+{
+    let now = clock.now();
+    let reports: Vec<_> = progress
+        .into_iter()
+        .map(|progress| progress.into_inner().into_report(now, epoch))
+        .collect();
+
+    if !finished {
+        let stopped = chassis.borrow_mut().stop();
+        stopped.await;
+        (StepOutcome::TimedOut, reports)
+    } else if reports.iter().any(|report| report.aborted) {
+        (StepOutcome::Failed(StepError::Group), reports)
+    } else {
+        (StepOutcome::Completed(None), reports)
+    }
+}
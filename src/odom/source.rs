@@ -0,0 +1,120 @@
+//! Common interface for pose estimators.
+//!
+//! [`OdomChassis`](crate::OdomChassis) does not care how the robot's pose is
+//! estimated, only that something can report it. The [`PoseSource`] trait is
+//! that contract: [`TrackingRig`](crate::TrackingRig) implements it with pure
+//! dead reckoning, [`EkfRig`](crate::odom::ekf::EkfRig) with a Kalman
+//! filter that also accepts absolute measurements,
+//! [`Mcl`](crate::odom::mcl::Mcl) with a particle filter over distance sensor
+//! readings, and [`GpsRig`](crate::odom::gps::GpsRig) with the V5 GPS sensor.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let ekf = EkfRig::new(origin, [horizontal], [left, right], Some(&dt), Some(imu), EkfConfig::new());
+//! let chassis = OdomChassis::new(dt, imu, None).with_pose_source(ekf);
+//! ```
+
+use crate::odom::health::{TravelSource, WheelStatus};
+use crate::odom::pose::Pose;
+use crate::odom::wheel::HeadingSource;
+use std::rc::Rc;
+
+/// A continuously updated estimate of the robot's pose and velocity.
+///
+/// Poses follow the [`Pose`] convention: metres, with headings
+/// counter-clockwise positive.
+pub trait PoseSource {
+    /// Returns the latest pose estimate.
+    fn pose(&self) -> Pose;
+
+    /// Returns the latest forward velocity estimate in m/s.
+    fn linear_velocity(&self) -> f64;
+
+    /// Returns the latest angular velocity estimate in rad/s (counter-clockwise positive).
+    fn angular_velocity(&self) -> f64;
+
+    /// Moves the estimate to a new pose, for example after a wall reset.
+    fn set_pose(&self, pose: Pose);
+
+    /// Receives the drivetrain's own velocity estimate.
+    ///
+    /// [`OdomChassis`](crate::OdomChassis) reports it on every control loop
+    /// iteration. Sources that compare it against their own measurements, such
+    /// as [`TrackingRig`](crate::TrackingRig) for slip detection, override
+    /// this; the default ignores it.
+    ///
+    /// # Arguments
+    ///
+    /// * `linear` - Forward velocity in m/s
+    /// * `angular` - Turn rate in rad/s, counter-clockwise positive
+    fn observe_drive(&self, linear: f64, angular: f64) {
+        let _ = (linear, angular);
+    }
+
+    /// Returns the health of the source's tracking wheels.
+    ///
+    /// [`TrackingRig`](crate::TrackingRig) reports every wheel; sources that
+    /// do not monitor their wheels return an empty list.
+    fn wheel_status(&self) -> Vec<WheelStatus> {
+        Vec::new()
+    }
+
+    /// Returns the sensor the latest heading change was measured with.
+    ///
+    /// [`OdomChassis`](crate::OdomChassis) fails a motion with
+    /// [`DriveError::Imu`](crate::DriveError::Imu) once the heading has been
+    /// [`Unavailable`](HeadingSource::Unavailable) for a while. Sources that
+    /// do not report it return `None`.
+    fn heading_source(&self) -> Option<HeadingSource> {
+        None
+    }
+
+    /// Returns where the latest forward travel was taken from.
+    ///
+    /// [`OdomChassis`](crate::OdomChassis) fails a motion with
+    /// [`DriveError::TrackingWheel`](crate::DriveError::TrackingWheel), or
+    /// [`DriveError::DriveEncoders`](crate::DriveError::DriveEncoders) if
+    /// [`wheel_status`](Self::wheel_status) is empty, once travel has been
+    /// [`Unavailable`](TravelSource::Unavailable) for a while. Sources that
+    /// do not report it return `None`.
+    fn travel_source(&self) -> Option<TravelSource> {
+        None
+    }
+}
+
+/// Shares one estimator, so it can still be queried after it is handed to an
+/// [`OdomChassis`](crate::OdomChassis).
+impl<P: PoseSource + ?Sized> PoseSource for Rc<P> {
+    fn pose(&self) -> Pose {
+        P::pose(self)
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        P::linear_velocity(self)
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        P::angular_velocity(self)
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        P::set_pose(self, pose);
+    }
+
+    fn observe_drive(&self, linear: f64, angular: f64) {
+        P::observe_drive(self, linear, angular);
+    }
+
+    fn wheel_status(&self) -> Vec<WheelStatus> {
+        P::wheel_status(self)
+    }
+
+    fn heading_source(&self) -> Option<HeadingSource> {
+        P::heading_source(self)
+    }
+
+    fn travel_source(&self) -> Option<TravelSource> {
+        P::travel_source(self)
+    }
+}
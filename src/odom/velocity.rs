@@ -0,0 +1,151 @@
+//! Velocity smoothing for odometry.
+//!
+//! A velocity computed as the difference between two consecutive encoder
+//! readings is noisy: encoder ticks are quantized, and the time between
+//! updates varies when other tasks compete for the CPU. [`VelocityEstimator`]
+//! smooths these raw estimates with one of several [`VelocityFilter`]s.
+//!
+//! | Filter | Lag | Noise rejection |
+//! |--------|-----|-----------------|
+//! | [`Difference`](VelocityFilter::Difference) | None | None |
+//! | [`MovingAverage`](VelocityFilter::MovingAverage) | Half the window | Good |
+//! | [`LowPass`](VelocityFilter::LowPass) | About one time constant | Good, tunable |
+//! | [`Regression`](VelocityFilter::Regression) | Half the window | Best for quantization |
+//!
+//! Samples are weighted by the time they cover, so uneven update periods do
+//! not skew the result.
+//!
+//! # Example
+//!
+//! ```
+//! use kernelvex::odom::velocity::{VelocityEstimator, VelocityFilter};
+//!
+//! let mut velocity = VelocityEstimator::new(VelocityFilter::MovingAverage(2));
+//! velocity.update(1.0, 0.01);
+//! assert_eq!(velocity.update(2.0, 0.01), 1.5);
+//! ```
+
+use core::time::Duration;
+use std::collections::VecDeque;
+
+/// How a [`VelocityEstimator`] smooths raw velocity estimates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum VelocityFilter {
+    /// No smoothing: the latest raw estimate.
+    #[default]
+    Difference,
+    /// Mean velocity over the last `n` updates.
+    MovingAverage(usize),
+    /// First-order low-pass filter with the given time constant.
+    LowPass(Duration),
+    /// Slope of a least-squares line through the positions of the last `n`
+    /// updates.
+    Regression(usize),
+}
+
+/// Smooths a stream of raw velocity estimates.
+///
+/// Each raw estimate is the average velocity over the interval since the
+/// previous update. The estimator integrates them into a position trace,
+/// which the moving average and regression filters are computed over.
+#[derive(Debug, Clone)]
+pub struct VelocityEstimator {
+    filter: VelocityFilter,
+    /// Integrated (time in s, position) trace, newest last
+    samples: VecDeque<(f64, f64)>,
+    velocity: f64,
+}
+
+impl VelocityEstimator {
+    /// Creates an estimator reporting zero velocity.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The smoothing to apply
+    pub fn new(filter: VelocityFilter) -> Self {
+        let mut samples = VecDeque::new();
+        samples.push_back((0.0, 0.0));
+
+        Self {
+            filter,
+            samples,
+            velocity: 0.0,
+        }
+    }
+
+    /// Returns the smoothing applied.
+    pub fn filter(&self) -> VelocityFilter {
+        self.filter
+    }
+
+    /// Returns the latest smoothed velocity.
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    /// Forgets every sample and reports zero velocity.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.filter);
+    }
+
+    /// Adds a raw velocity estimate.
+    ///
+    /// # Arguments
+    ///
+    /// * `velocity` - Average velocity over the interval
+    /// * `dt` - Length of the interval in seconds. Non-positive intervals are
+    ///   ignored.
+    ///
+    /// # Returns
+    ///
+    /// The smoothed velocity.
+    pub fn update(&mut self, velocity: f64, dt: f64) -> f64 {
+        if dt <= 0.0 {
+            return self.velocity;
+        }
+
+        let (time, position) = self.samples.back().copied().unwrap_or_default();
+        self.samples
+            .push_back((time + dt, position + velocity * dt));
+        let window = match self.filter {
+            VelocityFilter::MovingAverage(n) => n.max(1) + 1,
+            VelocityFilter::Regression(n) => n.max(2),
+            _ => 2,
+        };
+        while self.samples.len() > window {
+            self.samples.pop_front();
+        }
+
+        self.velocity = match self.filter {
+            VelocityFilter::Difference => velocity,
+            VelocityFilter::LowPass(tau) => {
+                let alpha = dt / (tau.as_secs_f64() + dt);
+                self.velocity + alpha * (velocity - self.velocity)
+            }
+            VelocityFilter::MovingAverage(_) => {
+                let (first_time, first) = self.samples[0];
+                (position + velocity * dt - first) / (time + dt - first_time)
+            }
+            VelocityFilter::Regression(_) => {
+                let n = self.samples.len() as f64;
+                let mean_t = self.samples.iter().map(|(t, _)| t).sum::<f64>() / n;
+                let mean_p = self.samples.iter().map(|(_, p)| p).sum::<f64>() / n;
+                let (covariance, variance) =
+                    self.samples.iter().fold((0.0, 0.0), |(cov, var), (t, p)| {
+                        (
+                            cov + (t - mean_t) * (p - mean_p),
+                            var + (t - mean_t).powi(2),
+                        )
+                    });
+                covariance / variance
+            }
+        };
+        self.velocity
+    }
+}
+
+impl Default for VelocityEstimator {
+    fn default() -> Self {
+        Self::new(VelocityFilter::default())
+    }
+}
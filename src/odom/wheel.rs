@@ -16,6 +16,9 @@
 //! - [`OmniWheel`]: Enum of standard VEX wheel sizes
 //! - [`Encoder`]: Either ADI quadrature or V5 rotation sensor
 //!
+//! Tracking wheels accept any [`RotaryEncoder`] and the rig accepts any [`Gyro`],
+//! so odometry can be exercised against mock or simulated devices.
+//!
 //! # Tracking Rig
 //!
 //! The [`TrackingRig`] runs a background task that continuously updates the robot's
@@ -29,9 +32,9 @@
 //! ```no_run
 //! use kernelvex::odom::wheel::{Encoder, OmniWheel, TrackingWheel};
 //! use kernelvex::util::si::QLength;
-//! use vexide_devices::math::Direction;
-//! use vexide_devices::smart::SmartPort;
-//! use vexide_devices::smart::rotation::RotationSensor;
+//! use vexide::math::Direction;
+//! use vexide::smart::SmartPort;
+//! use vexide::smart::rotation::RotationSensor;
 //! use kernelvex::util::utils::TrackingWheelOrientation;
 //!
 //! let encoder = RotationSensor::new(unsafe {SmartPort::new(1)}, Direction::Forward);
@@ -43,31 +46,72 @@
 //!     Some(1.0), // gearing ratio
 //! );
 //!
-//! let distance = tracking_wheel.distance();
-//! println!("Distance traveled: {} inches", distance.as_inches());
+//! if let Ok(distance) = tracking_wheel.distance() {
+//!     println!("Distance traveled: {} inches", distance.as_inches());
+//! }
 //! ```
 
+use crate::hal::encoder::RotaryEncoder;
+use crate::hal::imu::Gyro;
+use crate::odom::contact::{
+    ContactConfig, ContactDetector, ContactEvent, ContactInput, ContactState,
+};
+use crate::odom::health::{
+    TravelSource, WheelHealth, WheelHealthConfig, WheelMonitor, WheelStatus,
+};
+use crate::odom::history::{PoseHistory, PoseSample};
 use crate::odom::pose::Pose;
-use crate::odom::wheel::Encoder::{Adi, Smart};
+use crate::odom::source::PoseSource;
+use crate::odom::velocity::{VelocityEstimator, VelocityFilter};
+use crate::util::clock::{Clock, LoopTiming, SystemClock};
 use crate::util::si::QLength;
 use crate::util::utils::{Orientation, TrackingWheelOrientation};
 use crate::{QAngle, Vec2};
 use heapless::Vec;
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use vexide::adi::encoder::AdiEncoder;
+use vexide::smart::PortError;
 use vexide::smart::imu::InertialSensor;
 use vexide::smart::rotation::RotationSensor;
 use vexide_async::task::{Task, spawn};
-use vexide_async::time::sleep;
 
+/// An encoder on a V5 robot: either an ADI quadrature encoder or a V5 rotation sensor.
+///
+/// This is the default encoder type of [`TrackingWheel`]. It allows wheels with
+/// different sensor types to share a single [`TrackingRig`].
 #[derive(Debug)]
 pub enum Encoder {
+    /// ADI quadrature encoder (360 ticks per revolution).
     Adi(AdiEncoder<360>),
+    /// V5 smart rotation sensor.
     Smart(RotationSensor),
 }
 
+impl RotaryEncoder for Encoder {
+    fn position(&self) -> Result<QAngle, PortError> {
+        match self {
+            Encoder::Adi(encoder) => RotaryEncoder::position(encoder),
+            Encoder::Smart(encoder) => RotaryEncoder::position(encoder),
+        }
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        match self {
+            Encoder::Adi(encoder) => RotaryEncoder::set_position(encoder, position),
+            Encoder::Smart(encoder) => RotaryEncoder::set_position(encoder, position),
+        }
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        match self {
+            Encoder::Adi(encoder) => RotaryEncoder::reset_position(encoder),
+            Encoder::Smart(encoder) => RotaryEncoder::reset_position(encoder),
+        }
+    }
+}
+
 /// Types of omni wheels available for tracking.
 ///
 /// Omni wheels come in different sizes (diameter). The size affects the
@@ -97,8 +141,7 @@ impl OmniWheel {
     /// # Returns
     ///
     /// The wheel diameter as [`QLength`].
-    #[allow(unused)]
-    pub(crate) fn size(&self) -> QLength {
+    pub fn size(&self) -> QLength {
         match *self {
             OmniWheel::Omni275 => QLength::from_inches(2.75),
             OmniWheel::Omni325 => QLength::from_inches(3.25),
@@ -114,17 +157,14 @@ impl OmniWheel {
 /// A tracking wheel implementation using an encoder.
 ///
 /// `TrackingWheel` converts encoder rotations into linear distance measurements
-/// using the wheel diameter and optional gearing ratio. This is the primary
-/// implementation of the [`Tracking`] trait for odometry calculations.
+/// using the wheel diameter and optional gearing ratio.
 ///
 /// # Type Parameters
 ///
-/// * `T` - The encoder type implementing the [`Encoder`] trait
-///
-
+/// * `E` - The encoder type implementing [`RotaryEncoder`]. Defaults to [`Encoder`].
 #[derive(Debug)]
-pub struct TrackingWheel {
-    encoder: Encoder,
+pub struct TrackingWheel<E: RotaryEncoder = Encoder> {
+    encoder: E,
     wheel: OmniWheel,
     dist: TrackingWheelOrientation,
     orientation: Orientation,
@@ -132,7 +172,7 @@ pub struct TrackingWheel {
     gearing: f64,
 }
 
-impl TrackingWheel {
+impl<E: RotaryEncoder> TrackingWheel<E> {
     /// Creates odom new tracking wheel.
     ///
     /// # Arguments
@@ -140,9 +180,9 @@ impl TrackingWheel {
     /// * `encoder` - The encoder used to measure wheel rotation
     /// * `wheel` - The type of omni wheel being used
     /// * `dist` - The perpendicular offset distance from the robot's center.
-    ///            Positive values indicate right side, negative indicates left side
+    ///   Positive values indicate right side, negative indicates left side
     /// * `gearing` - Optional gearing ratio. If `None`, assumes 1:1 gearing.
-    ///              A value of 2.0 means the encoder rotates twice per wheel rotation.
+    ///   A value of 2.0 means the encoder rotates twice per wheel rotation.
     ///
     /// # Returns
     ///
@@ -150,7 +190,7 @@ impl TrackingWheel {
     #[allow(unused)]
     #[inline]
     pub fn new(
-        encoder: Encoder,
+        encoder: E,
         wheel: OmniWheel,
         dist: TrackingWheelOrientation,
         ratio: Option<f64>,
@@ -199,37 +239,34 @@ impl TrackingWheel {
         }
     }
 
+    /// Returns the wheel type, which sets the diameter used for distances.
+    pub const fn wheel(&self) -> &OmniWheel {
+        &self.wheel
+    }
+
     /// Returns the total distance traveled by this wheel since the last reset.
     ///
     /// Calculates distance using the formula:
     /// ```text
-    /// distance = (circumference * rotations_in_radians) / gearing
+    /// distance = (circumference * rotations) / gearing
     /// ```
     ///
     /// Where:
     /// - `circumference = wheel_diameter * π`
-    /// - `rotations_in_radians` is the encoder position converted to radians
+    /// - `rotations` is the encoder position in full turns
     /// - `gearing` is the gear ratio between encoder and wheel
     ///
     /// # Returns
     ///
-    /// The total distance traveled as [`QLength`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the encoder position cannot be read.
-    pub fn distance(&self) -> QLength {
+    /// * `Ok(distance)` - The total distance traveled as [`QLength`]
+    /// * `Err(PortError)` - The encoder position could not be read, for example
+    ///   because its sensor is disconnected
+    pub fn distance(&self) -> Result<QLength, PortError> {
         let circumference = self.wheel.size() * std::f64::consts::PI;
 
-        let rotations = match &self.encoder {
-            Adi(encoder) => QAngle::from_turns(encoder.position().unwrap().as_turns()),
-            Smart(encoder) => QAngle::from_turns(encoder.position().unwrap().as_turns()),
-        };
-
-        let distance =
-            (circumference * std::f64::consts::PI * rotations.as_radians()) / self.gearing;
+        let rotations = self.encoder.position()?;
 
-        distance
+        Ok((circumference * rotations.as_turns()) / self.gearing)
     }
 
     /// Returns the distance traveled since the last call to `delta`.
@@ -240,17 +277,19 @@ impl TrackingWheel {
     ///
     /// # Returns
     ///
-    /// The distance traveled since the last call as [`QLength`].
+    /// The distance traveled since the last call as [`QLength`], or the
+    /// [`PortError`] if the encoder could not be read. A failed read leaves the
+    /// previous value unchanged.
     ///
     /// # Note
     ///
     /// The first call after construction or [`reset`](Self::reset) will return
     /// the total distance, as the previous value is initialized to zero.
-    pub fn delta(&mut self) -> QLength {
+    pub fn delta(&mut self) -> Result<QLength, PortError> {
         let previous = self.total;
-        let current = self.distance();
+        let current = self.distance()?;
         self.total = current;
-        current - previous
+        Ok(current - previous)
     }
 
     /// Resets the encoder position and internal tracking state to zero.
@@ -262,14 +301,7 @@ impl TrackingWheel {
     /// autonomous routine.
     pub fn reset(&mut self) {
         self.total = Default::default();
-        match &mut self.encoder {
-            Adi(encoder) => {
-                let _ = encoder.reset_position();
-            }
-            Smart(encoder) => {
-                let _ = encoder.reset_position();
-            }
-        }
+        let _ = self.encoder.reset_position();
     }
 
     /// Sets the encoder position to a specific angle.
@@ -281,14 +313,7 @@ impl TrackingWheel {
     ///
     /// * `position` - The angle to set the encoder position to.
     pub fn set(&mut self, position: QAngle) {
-        match &mut self.encoder {
-            Adi(encoder) => {
-                let _ = encoder.set_position(position.into());
-            }
-            Smart(encoder) => {
-                let _ = encoder.set_position(position.into());
-            }
-        }
+        let _ = self.encoder.set_position(position);
     }
 
     /// Returns the orientation of this tracking wheel.
@@ -329,7 +354,7 @@ impl TrackingWheel {
 /// # Architecture
 ///
 /// The tracking rig spawns an asynchronous task that runs at approximately 100Hz
-/// (10ms intervals). This task:
+/// (10ms intervals) by default. This task:
 /// 1. Reads encoder positions from all tracking wheels
 /// 2. Computes heading from IMU or wheel differential
 /// 3. Calculates local displacement using arc-based odometry
@@ -343,6 +368,51 @@ impl TrackingWheel {
 /// - The chord length formula `2 * sin(Δθ/2)` accounts for this arc
 /// - Local coordinates are rotated by the average heading to get field coordinates
 ///
+/// # Resetting
+///
+/// [`set_pose`](Self::set_pose), [`set_position`](Self::set_position),
+/// [`set_heading`](Self::set_heading) and [`tare`](Self::tare) re-seed the
+/// estimate at runtime, for example at the start of each autonomous routine.
+///
+/// # History
+///
+/// The rig keeps its recent estimates in a [`PoseHistory`];
+/// [`pose_at`](Self::pose_at) looks up where the robot was believed to be at
+/// a past time.
+///
+/// # Update Rate and Velocity Filtering
+///
+/// [`with_period`](Self::with_period) changes the time between updates, and
+/// [`with_velocity_filter`](Self::with_velocity_filter) smooths the velocity
+/// estimates, which by default are differences between consecutive updates.
+/// [`timing`](Self::timing) reports how regularly the task actually ran;
+/// missed deadlines point to other tasks holding the CPU.
+///
+/// # Contact Detection
+///
+/// Each update also checks for wheel slip, pushes and collisions with a
+/// [`ContactDetector`]; see [`contact`](Self::contact) and
+/// [`take_contact_events`](Self::take_contact_events).
+///
+/// # Heading Sensor Dropout
+///
+/// The heading sensor is read again on every update, so a sensor that stops
+/// responding is used again as soon as it recovers. While it cannot be read,
+/// heading changes come from two parallel forward wheels if the rig has them.
+/// [`heading_source`](Self::heading_source) and
+/// [`imu_failures`](Self::imu_failures) report what happened. To fuse several
+/// sensors, pass an [`ImuFusion`](crate::odom::heading::ImuFusion).
+///
+/// # Tracking Wheel Faults
+///
+/// Every wheel reading is checked before it is used. A wheel that cannot be
+/// read, stays frozen while the robot moves, or jumps further than the robot
+/// could roll is left out of the average, and the remaining wheels carry on.
+/// With no healthy vertical wheel left, forward travel comes from the
+/// drivetrain velocity passed to [`observe_drive`](Self::observe_drive).
+/// [`wheel_status`](Self::wheel_status) reports each wheel's
+/// [`WheelHealth`](crate::odom::health::WheelHealth).
+///
 /// # Example
 ///
 /// ```no_run
@@ -380,16 +450,19 @@ impl TrackingRig {
     ///
     /// * `origin` - The initial pose of the robot (position and heading)
     /// * `horizontal` - Array of horizontal tracking wheels (measure lateral movement).
-    ///                  Maximum of 2 wheels allowed.
+    ///   Maximum of 2 wheels allowed.
     /// * `vertical` - Array of vertical tracking wheels (measure forward/backward movement).
-    ///                Maximum of 2 wheels allowed.
+    ///   Maximum of 2 wheels allowed.
     /// * `imu` - Optional inertial sensor for heading. If `None`, two parallel forward
-    ///           wheels are required to compute heading from wheel differential.
+    ///   wheels are required to compute heading from wheel differential. Use
+    ///   [`without_imu`](Self::without_imu) to avoid naming the sensor type.
     ///
     /// # Type Parameters
     ///
     /// * `N` - Number of horizontal tracking wheels (0-2)
     /// * `U` - Number of vertical tracking wheels (0-2)
+    /// * `E` - Encoder type of the tracking wheels
+    /// * `G` - Heading sensor type
     ///
     /// # Panics
     ///
@@ -401,68 +474,141 @@ impl TrackingRig {
     ///
     /// A new `TrackingRig` that immediately begins updating pose estimates.
     #[inline]
+    pub fn new<const N: usize, const U: usize, E, G>(
+        origin: Pose,
+        horizontal: [TrackingWheel<E>; N],
+        vertical: [TrackingWheel<E>; U],
+        imu: Option<G>,
+    ) -> Self
+    where
+        E: RotaryEncoder + 'static,
+        G: Gyro + 'static,
+    {
+        Self::with_clock(origin, horizontal, vertical, imu, SystemClock::new())
+    }
 
-    pub fn new<const N: usize, const U: usize>(
+    /// Creates a new tracking rig whose background task runs on the given clock.
+    ///
+    /// The task waits on `clock` between updates and measures velocities over
+    /// the clock's elapsed time. With a [`ManualClock`](crate::util::clock::ManualClock),
+    /// the rig only updates when the clock is advanced.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The initial pose of the robot (position and heading)
+    /// * `horizontal` - Array of horizontal tracking wheels (maximum of 2)
+    /// * `vertical` - Array of vertical tracking wheels (maximum of 2)
+    /// * `imu` - Optional heading sensor
+    /// * `clock` - Time source for the update loop
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`TrackingRig::new`].
+    pub fn with_clock<const N: usize, const U: usize, E, G, C>(
         origin: Pose,
-        horizontal: [TrackingWheel; N],
-        vertical: [TrackingWheel; U],
-        imu: Option<InertialSensor>,
-    ) -> Self {
+        horizontal: [TrackingWheel<E>; N],
+        vertical: [TrackingWheel<E>; U],
+        imu: Option<G>,
+        clock: C,
+    ) -> Self
+    where
+        E: RotaryEncoder + 'static,
+        G: Gyro + 'static,
+        C: Clock + 'static,
+    {
         const {
             assert!(N <= 2 || U <= 2, "cannot have over 2 tracking wheels each");
         }
 
-        let mut h_wheels: Vec<TrackingWheel, 2> = Vec::from_array(horizontal);
+        let h_wheels: Vec<TrackingWheel<E>, 2> = Vec::from_array(horizontal);
 
-        let mut v_wheels: Vec<TrackingWheel, 2> = Vec::from_array(vertical);
+        let v_wheels: Vec<TrackingWheel<E>, 2> = Vec::from_array(vertical);
 
-        let parallel_indices = find_parallel_forward_indices(&h_wheels);
+        let parallel_indices = find_parallel_forward_indices(&v_wheels);
 
         assert!(
             imu.is_some() || parallel_indices.is_some(),
             "gyro or two parallel forward wheels are required to determine heading"
         );
 
-        let initial_heading =
-            compute_raw_heading(imu.as_ref(), parallel_indices.as_ref(), &mut h_wheels[..])
-                .unwrap_or_default();
-        let initial_forward: Vec<f64, 2> = v_wheels
-            .iter_mut()
-            .map(|wheel| wheel.distance().as_meters())
-            .collect();
-        let initial_sideways: f64 = h_wheels
-            .iter_mut()
-            .map(|wheel| wheel.distance().as_meters())
-            .sum();
-
-        let initial_forward_travel = if initial_forward.is_empty() {
-            0.0
+        // The first readings are the reference for the first update.
+        let config = WheelHealthConfig::new();
+        let monitors = |wheels: &Vec<TrackingWheel<E>, 2>| -> Vec<WheelMonitor, 2> {
+            wheels
+                .iter()
+                .map(|wheel| {
+                    let mut monitor = WheelMonitor::default();
+                    monitor.read(wheel.distance(), 0.0, &config);
+                    monitor
+                })
+                .collect()
+        };
+        let forward_monitors = monitors(&v_wheels);
+        let sideways_monitors = monitors(&h_wheels);
+
+        let initial_imu = imu.as_ref().and_then(imu_heading);
+        let initial_heading = initial_imu.unwrap_or_default();
+        let wheels_readable = parallel_indices.is_some_and(|(l, r)| {
+            [l, r]
+                .iter()
+                .all(|&i| forward_monitors[i].status().health == WheelHealth::Healthy)
+        });
+        let heading_source = if initial_imu.is_some() {
+            HeadingSource::Imu
+        } else if wheels_readable {
+            HeadingSource::Wheels
         } else {
-            initial_forward.iter().sum::<f64>() / initial_forward.len() as f64
+            HeadingSource::Unavailable
         };
+        let wheels = forward_monitors
+            .iter()
+            .chain(sideways_monitors.iter())
+            .map(WheelMonitor::status)
+            .collect();
+
+        let mut history = PoseHistory::new(DEFAULT_HISTORY);
+        history.push(PoseSample {
+            pose: origin,
+            linear_velocity: 0.0,
+            angular_velocity: 0.0,
+            time: clock.now(),
+        });
 
         let data = Rc::new(RefCell::new(TrackingData {
             pose: origin,
             raw_heading: initial_heading,
-            heading_offset: origin.heading(),
-            forward_travel: initial_forward_travel,
+            heading_offset: origin.heading() - initial_heading,
             linear_velocity: 0.0,
             angular_velocity: 0.0,
+            heading_source,
+            imu_failures: u32::from(imu.is_some() && initial_imu.is_none()),
+            history,
+            contact: ContactDetector::new(ContactConfig::new()),
+            drive: None,
+            drive_age: 0,
+            period: DEFAULT_PERIOD,
+            timing: LoopTiming::new(DEFAULT_PERIOD),
+            linear_filter: VelocityEstimator::default(),
+            angular_filter: VelocityEstimator::default(),
+            wheel_health: config,
+            wheels,
+            travel_source: TravelSource::Wheels,
         }));
 
         let task_data = Rc::clone(&data);
 
         let task = spawn(async move {
             Self::task(
-                &mut v_wheels[..],
-                &mut h_wheels[..],
+                &v_wheels[..],
+                &h_wheels[..],
                 imu,
                 task_data,
                 parallel_indices,
-                Vec::from_slice(&initial_forward).unwrap(),
-                Vec::from_slice(&[initial_sideways]).unwrap(),
+                forward_monitors,
+                sideways_monitors,
+                initial_imu,
                 initial_heading,
-                initial_forward_travel,
+                clock,
             )
             .await;
         });
@@ -470,6 +616,90 @@ impl TrackingRig {
         Self { data, _task: task }
     }
 
+    /// Creates a tracking rig that takes its heading from two parallel
+    /// forward wheels, and starts the background odometry task.
+    ///
+    /// See [`TrackingRig::new`] for the arguments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than 2 horizontal or vertical tracking wheels are
+    /// provided, or if no pair of parallel forward wheels exists.
+    #[inline]
+    pub fn without_imu<const N: usize, const U: usize, E>(
+        origin: Pose,
+        horizontal: [TrackingWheel<E>; N],
+        vertical: [TrackingWheel<E>; U],
+    ) -> Self
+    where
+        E: RotaryEncoder + 'static,
+    {
+        Self::new(origin, horizontal, vertical, None::<InertialSensor>)
+    }
+
+    /// Sets how many past estimates are kept for [`pose_at`](Self::pose_at).
+    ///
+    /// One estimate is recorded per update, so the default of 100 covers
+    /// about the last second at the default period.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of estimates kept. Zero disables recording.
+    pub fn with_history(self, capacity: usize) -> Self {
+        self.data.borrow_mut().history.set_capacity(capacity);
+        self
+    }
+
+    /// Sets the time between updates.
+    ///
+    /// Shorter periods track fast motion more closely but leave less CPU time
+    /// for other tasks. The timing statistics are reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - Time between updates, 10 ms by default
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero.
+    pub fn with_period(self, period: Duration) -> Self {
+        assert!(!period.is_zero(), "odometry period must be positive");
+        {
+            let mut state = self.data.borrow_mut();
+            state.period = period;
+            state.timing = LoopTiming::new(period);
+        }
+        self
+    }
+
+    /// Sets how the linear and angular velocity estimates are smoothed.
+    ///
+    /// Velocities recorded in the pose history are smoothed too. Contact
+    /// detection keeps using the unfiltered values, so it reacts immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The smoothing to apply, [`VelocityFilter::Difference`] by
+    ///   default
+    pub fn with_velocity_filter(self, filter: VelocityFilter) -> Self {
+        {
+            let mut state = self.data.borrow_mut();
+            state.linear_filter = VelocityEstimator::new(filter);
+            state.angular_filter = VelocityEstimator::new(filter);
+        }
+        self
+    }
+
+    /// Sets the thresholds used to flag slip, pushes and collisions.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Detection thresholds
+    pub fn with_contact(self, config: ContactConfig) -> Self {
+        self.data.borrow_mut().contact.set_config(config);
+        self
+    }
+
     /// Returns the latest pose estimate.
     ///
     /// The pose is continuously updated by the background task at approximately
@@ -484,7 +714,8 @@ impl TrackingRig {
 
     /// Returns the latest linear velocity estimate in meters per second.
     ///
-    /// Computed from the change in forward wheel travel over time.
+    /// Computed from the change in forward wheel travel over time, smoothed
+    /// by the rig's [`VelocityFilter`].
     ///
     /// # Returns
     ///
@@ -496,7 +727,8 @@ impl TrackingRig {
     /// Returns the latest angular velocity estimate in radians per second.
     ///
     /// If an IMU is available, uses the gyroscope's Z-axis rate directly.
-    /// Otherwise, computes from the change in heading over time.
+    /// Otherwise, computes from the change in heading over time. Either is
+    /// smoothed by the rig's [`VelocityFilter`].
     ///
     /// # Returns
     ///
@@ -505,9 +737,167 @@ impl TrackingRig {
         self.data.borrow().angular_velocity
     }
 
+    /// Returns the sensor the latest heading change was measured with.
+    ///
+    /// Anything other than [`HeadingSource::Imu`] on a rig built with a
+    /// heading sensor means the sensor could not be read.
+    pub fn heading_source(&self) -> HeadingSource {
+        self.data.borrow().heading_source
+    }
+
+    /// Returns the number of heading sensor reads that have failed.
+    pub fn imu_failures(&self) -> u32 {
+        self.data.borrow().imu_failures
+    }
+
+    /// Sets the thresholds used to detect failed tracking wheels.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Detection thresholds
+    pub fn with_wheel_health(self, config: WheelHealthConfig) -> Self {
+        self.data.borrow_mut().wheel_health = config;
+        self
+    }
+
+    /// Returns the health of each tracking wheel.
+    ///
+    /// Vertical wheels come first, then horizontal wheels, each in the order
+    /// they were passed to the constructor.
+    pub fn wheel_status(&self) -> std::vec::Vec<WheelStatus> {
+        self.data.borrow().wheels.to_vec()
+    }
+
+    /// Returns where the latest forward travel was measured.
+    ///
+    /// [`TravelSource::Drive`] means every vertical wheel had failed and the
+    /// drivetrain's reports were used instead.
+    pub fn travel_source(&self) -> TravelSource {
+        self.data.borrow().travel_source
+    }
+
+    /// Returns timing statistics for the background task's updates.
+    pub fn timing(&self) -> LoopTiming {
+        self.data.borrow().timing
+    }
+
+    /// Clears the timing statistics, for example after initialization has
+    /// finished.
+    pub fn reset_timing(&self) {
+        let mut state = self.data.borrow_mut();
+        state.timing = LoopTiming::new(state.period);
+    }
+
+    /// Returns whether the robot is currently slipping, being pushed or
+    /// colliding.
+    ///
+    /// Slip and most push detection need the drivetrain's velocity; see
+    /// [`observe_drive`](Self::observe_drive).
+    pub fn contact(&self) -> ContactState {
+        self.data.borrow().contact.state()
+    }
+
+    /// Removes and returns the contact events recorded since the last call,
+    /// oldest first.
+    pub fn take_contact_events(&self) -> std::vec::Vec<ContactEvent> {
+        self.data.borrow_mut().contact.take_events()
+    }
+
+    /// Reports the drivetrain's own velocity estimate for contact detection.
+    ///
+    /// Call this regularly with the values from the drivetrain's
+    /// [`Drivetrain`](crate::Drivetrain) impl; [`OdomChassis`](crate::OdomChassis)
+    /// does so in every motion. A report is used for five updates (50 ms by
+    /// default), after which slip detection pauses until the next one.
+    ///
+    /// # Arguments
+    ///
+    /// * `linear` - Forward velocity in m/s
+    /// * `angular` - Turn rate in rad/s, counter-clockwise positive
+    pub fn observe_drive(&self, linear: f64, angular: f64) {
+        let mut state = self.data.borrow_mut();
+        state.drive = Some((linear, angular));
+        state.drive_age = 0;
+    }
+
+    /// Returns the pose the rig estimated at a given time.
+    ///
+    /// Estimates between updates are interpolated. Past estimates are kept as
+    /// they were, so a [`set_pose`](Self::set_pose) does not rewrite history.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - Time on the rig's clock
+    ///
+    /// # Returns
+    ///
+    /// The estimated pose, or `None` if `time` is older than the recorded
+    /// history or newer than the latest update.
+    pub fn pose_at(&self, time: Duration) -> Option<Pose> {
+        self.data.borrow().history.pose_at(time)
+    }
+
+    /// Returns the pose and velocities the rig estimated at a given time.
+    ///
+    /// See [`pose_at`](Self::pose_at).
+    pub fn sample_at(&self, time: Duration) -> Option<PoseSample> {
+        self.data.borrow().history.sample_at(time)
+    }
+
+    /// Returns a copy of the recorded estimates.
+    pub fn history(&self) -> PoseHistory {
+        self.data.borrow().history.clone()
+    }
+
+    /// Moves the pose estimate to a new pose.
+    ///
+    /// The new pose takes effect at the instant of the latest odometry
+    /// update, whose wheel and heading readings remain the reference for the
+    /// next one. Motion measured after that update is then added on top of the
+    /// new pose, so the estimate continues smoothly from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pose` - The robot's new pose
+    pub fn set_pose(&self, pose: Pose) {
+        let mut state = self.data.borrow_mut();
+        state.heading_offset = pose.heading() - state.raw_heading;
+        state.pose = pose;
+    }
+
+    /// Moves the position estimate, keeping the current heading.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The robot's new position in meters
+    pub fn set_position(&self, position: Vec2<f64>) {
+        let heading = self.data.borrow().pose.heading();
+        self.set_pose(Pose::new(position, heading));
+    }
+
+    /// Changes the heading estimate, keeping the current position.
+    ///
+    /// Subsequent motion is integrated along the new heading.
+    ///
+    /// # Arguments
+    ///
+    /// * `heading` - The robot's new heading, counter-clockwise from the
+    ///   positive x-axis
+    pub fn set_heading(&self, heading: QAngle) {
+        let position = self.data.borrow().pose.position();
+        self.set_pose(Pose::new(position, heading));
+    }
+
+    /// Resets the pose estimate to the origin, facing the positive x-axis.
+    ///
+    /// Equivalent to `set_pose(Pose::default())`.
+    pub fn tare(&self) {
+        self.set_pose(Pose::default());
+    }
+
     /// Background odometry task that continuously updates the pose estimate.
     ///
-    /// This async task runs in a loop at approximately 100Hz and:
+    /// This async task runs in a loop, every 10 ms by default, and:
     /// 1. Reads current positions from all tracking wheels
     /// 2. Computes heading change from IMU or wheel differential
     /// 3. Calculates local displacement using arc-based odometry
@@ -523,8 +913,8 @@ impl TrackingRig {
     ///
     /// Local displacement is computed as:
     /// ```text
-    /// local_x = unit_chord * (Δforward / Δθ + offset)  // forward direction
-    /// local_y = unit_chord * (Δsideways / Δθ + offset) // lateral direction
+    /// local_x = unit_chord * (Δforward / Δθ - offset)  // forward direction
+    /// local_y = unit_chord * (Δsideways / Δθ - offset) // lateral direction
     /// ```
     ///
     /// When `Δθ ≈ 0`, the robot moved straight and the formula simplifies to
@@ -540,92 +930,179 @@ impl TrackingRig {
     ///
     /// Where `avg_heading` is the midpoint heading during the movement.
     #[allow(clippy::too_many_arguments)]
-    async fn task(
-        forward: &mut [TrackingWheel],
-        sideways: &mut [TrackingWheel],
-        mut imu: Option<InertialSensor>,
+    async fn task<E: RotaryEncoder, G: Gyro, C: Clock>(
+        forward: &[TrackingWheel<E>],
+        sideways: &[TrackingWheel<E>],
+        imu: Option<G>,
         data: Rc<RefCell<TrackingData>>,
         parallel_indices: Option<(usize, usize)>,
-        mut prev_forward: Vec<f64, 2>,
-        mut prev_sideways: Vec<f64, 2>,
+        mut forward_monitors: Vec<WheelMonitor, 2>,
+        mut sideways_monitors: Vec<WheelMonitor, 2>,
+        mut prev_imu: Option<QAngle>,
         mut prev_raw_heading: QAngle,
-        mut prev_forward_travel: f64,
+        clock: C,
     ) {
-        let mut prev_time = Instant::now();
+        let mut prev_time = clock.now();
+        let mut prev_wake = prev_time;
 
         loop {
-            sleep(Duration::from_millis(10)).await;
+            // Sleeping until a period after the previous wake-up keeps the
+            // update time from stretching the period.
+            let period = data.borrow().period;
+            let wake = prev_wake + period;
+            clock.sleep(wake.saturating_sub(clock.now())).await;
+            let woke = clock.now();
+            let step = woke.saturating_sub(prev_wake);
+            data.borrow_mut().timing.record(step);
+            prev_wake = woke;
+
+            let now = clock.now();
+            let dt = now.saturating_sub(prev_time).as_secs_f64();
+            let (config, drive) = {
+                let mut state = data.borrow_mut();
+                state.drive_age = state.drive_age.saturating_add(1);
+                let drive = state.drive.filter(|_| state.drive_age <= DRIVE_TIMEOUT);
+                (state.wheel_health, drive)
+            };
 
-            let forward_data: Vec<(f64, f64), 2> = forward
-                .iter_mut()
-                .map(|wheel| (wheel.distance().as_meters(), wheel.offset().as_meters()))
+            // Wheel travel since the last committed reading, or `None` for a
+            // wheel that failed a check.
+            let mut forward_data: Vec<(Option<f64>, f64), 2> = forward
+                .iter()
+                .zip(forward_monitors.iter_mut())
+                .map(|(wheel, monitor)| {
+                    let delta = monitor.read(wheel.distance(), dt, &config);
+                    (delta, wheel.offset().as_meters())
+                })
                 .collect();
-            let sideways_data: Vec<(f64, f64), 2> = sideways
-                .iter_mut()
-                .map(|wheel| (wheel.distance().as_meters(), wheel.offset().as_meters()))
+            let mut sideways_data: Vec<(Option<f64>, f64), 2> = sideways
+                .iter()
+                .zip(sideways_monitors.iter_mut())
+                .map(|(wheel, monitor)| {
+                    let delta = monitor.read(wheel.distance(), dt, &config);
+                    (delta, wheel.offset().as_meters())
+                })
                 .collect();
 
-            let raw_heading =
-                match compute_raw_heading(imu.as_ref(), parallel_indices.as_ref(), forward) {
-                    Ok(heading) => heading,
-                    Err(HeadingError::Imu(fallback)) => {
-                        imu = None;
-                        if let Some(fallback_heading) = fallback {
-                            fallback_heading
-                        } else {
-                            return;
-                        }
-                    }
-                    Err(HeadingError::Rotary) if imu.is_some() => {
-                        imu = None;
+            // The IMU is retried every update; a heading change needs two
+            // consecutive readings.
+            let imu_now = imu.as_ref().and_then(imu_heading);
+            let imu_change = prev_imu
+                .zip(imu_now)
+                .map(|(prev, now)| (now - prev).remainder(QAngle::TAU).as_radians());
+
+            // A frozen wheel is only stale if the drivetrain says it should
+            // turn and another sensor confirms the robot is moving, so a
+            // robot spinning its wheels against a wall keeps its tracking
+            // wheels.
+            if dt > 0.0 {
+                let threshold = config.motion_threshold * dt;
+                let turning = imu_change.is_some_and(|change| (change / dt).abs() > MIN_TURN_RATE);
+                let moved: Vec<bool, 4> = forward_data
+                    .iter()
+                    .chain(sideways_data.iter())
+                    .map(|(delta, _)| delta.is_some_and(|delta| delta.abs() > threshold))
+                    .collect();
+                let wheels = forward_data
+                    .iter_mut()
+                    .zip(forward_monitors.iter_mut())
+                    .map(|(wheel, monitor)| (wheel, monitor, true))
+                    .chain(
+                        sideways_data
+                            .iter_mut()
+                            .zip(sideways_monitors.iter_mut())
+                            .map(|(wheel, monitor)| (wheel, monitor, false)),
+                    );
+                for (i, ((delta, offset), monitor, vertical)) in wheels.enumerate() {
+                    let Some(travel) = *delta else {
                         continue;
+                    };
+                    let expected = drive.map_or(0.0, |(v, w)| {
+                        if vertical { v + w * *offset } else { w * *offset }
+                    });
+                    let confirmed =
+                        turning || moved.iter().enumerate().any(|(j, moved)| *moved && j != i);
+                    let should_move = expected.abs() > config.motion_threshold && confirmed;
+                    if !monitor.check_frozen(travel, should_move, step, &config) {
+                        *delta = None;
                     }
-                    Err(_) => continue,
-                };
+                }
+            }
+
+            let wheel_change =
+                parallel_indices.and_then(|(l, r)| wheel_heading(&forward_data, l, r));
+            let change = match (imu_change, wheel_change) {
+                (Some(change), _) => Some((change, HeadingSource::Imu)),
+                (None, Some(change)) => Some((change, HeadingSource::Wheels)),
+                _ => None,
+            };
+            prev_imu = imu_now;
+
+            {
+                let mut state = data.borrow_mut();
+                if imu.is_some() && imu_now.is_none() {
+                    state.imu_failures = state.imu_failures.saturating_add(1);
+                }
+                state.heading_source = change.map_or(HeadingSource::Unavailable, |(_, s)| s);
+                state.wheels = forward_monitors
+                    .iter()
+                    .chain(sideways_monitors.iter())
+                    .map(WheelMonitor::status)
+                    .collect();
+            }
+
+            // Without a heading change the wheel travel carries over to the
+            // next update.
+            let Some((change, _)) = change else {
+                continue;
+            };
 
-            let delta_heading = (raw_heading - prev_raw_heading).remainder(QAngle::TAU);
-            let avg_heading = raw_heading + delta_heading * 0.5 + data.borrow().heading_offset;
+            let delta_heading = QAngle::from_radians(change);
+            let raw_heading = prev_raw_heading + delta_heading;
+            let avg_heading = prev_raw_heading + delta_heading * 0.5 + data.borrow().heading_offset;
             prev_raw_heading = raw_heading;
 
-            let unit_chord = 2.0 * libm::sin(delta_heading.as_radians() / 2.0);
+            let unit_chord = 2.0 * libm::sin(change / 2.0);
+            let local = |delta: f64, offset: f64| {
+                if change == 0.0 {
+                    delta
+                } else {
+                    unit_chord * (delta / change - offset)
+                }
+            };
 
             let mut local_x_sum = 0.0;
             let mut local_y_sum = 0.0;
             let mut forward_count = 0.0;
             let mut sideways_count = 0.0;
-            let mut travel_sum = 0.0;
-
-            for (i, (travel, offset)) in forward_data.iter().enumerate() {
-                let delta = *travel - prev_forward[i];
-                travel_sum += *travel;
-                forward_count += 1.0;
-
-                local_x_sum += if delta_heading.as_radians() == 0.0 {
-                    delta
-                } else {
-                    unit_chord * (delta / delta_heading.as_radians() + offset)
-                };
 
-                prev_forward[i] = *travel;
+            for ((delta, offset), monitor) in forward_data.iter().zip(forward_monitors.iter_mut())
+            {
+                if let Some(delta) = *delta {
+                    local_x_sum += local(delta, *offset);
+                    forward_count += 1.0;
+                    monitor.commit(delta);
+                }
             }
 
-            for (i, (travel, offset)) in sideways_data.iter().enumerate() {
-                let delta = *travel - prev_sideways[i];
-                sideways_count += 1.0;
-
-                local_y_sum += if delta_heading.as_radians() == 0.0 {
-                    delta
-                } else {
-                    unit_chord * (delta / delta_heading.as_radians() + offset)
-                };
-
-                prev_sideways[i] = *travel;
+            for ((delta, offset), monitor) in
+                sideways_data.iter().zip(sideways_monitors.iter_mut())
+            {
+                if let Some(delta) = *delta {
+                    local_y_sum += local(delta, *offset);
+                    sideways_count += 1.0;
+                    monitor.commit(delta);
+                }
             }
 
-            let local_x = if forward_count > 0.0 {
-                local_x_sum / forward_count
+            // Without a healthy vertical wheel, forward travel comes from the
+            // drive motor encoders.
+            let (local_x, travel_source) = if forward_count > 0.0 {
+                (local_x_sum / forward_count, TravelSource::Wheels)
+            } else if let Some((v, _)) = drive {
+                (local(v * dt, 0.0), TravelSource::Drive)
             } else {
-                0.0
+                (0.0, TravelSource::Unavailable)
             };
             let local_y = if sideways_count > 0.0 {
                 local_y_sum / sideways_count
@@ -633,58 +1110,110 @@ impl TrackingRig {
                 0.0
             };
 
-            let dt = prev_time.elapsed().as_secs_f64();
-            prev_time = Instant::now();
+            prev_time = now;
 
-            let forward_travel = if forward_count > 0.0 {
-                travel_sum / forward_count
-            } else {
-                prev_forward_travel
-            };
+            let raw_linear = if dt > 0.0 { local_x / dt } else { 0.0 };
 
-            let linear_velocity = if dt > 0.0 {
-                (forward_travel - prev_forward_travel) / dt
-            } else {
-                0.0
-            };
-            prev_forward_travel = forward_travel;
-
-            let angular_velocity = if let Some(imu_ref) = imu.as_ref() {
-                imu_ref
-                    .gyro_rate()
-                    .ok()
-                    .map(|v| v.z.to_radians())
-                    .unwrap_or(0.0)
+            let gyro_rate = imu
+                .as_ref()
+                .filter(|_| imu_now.is_some())
+                .and_then(|imu_ref| imu_ref.gyro_rate().ok());
+            let raw_angular = if let Some(rate) = gyro_rate {
+                rate.to_radians()
             } else if dt > 0.0 {
-                delta_heading.as_radians() / dt
+                change / dt
             } else {
                 0.0
             };
 
+            let acceleration = imu
+                .as_ref()
+                .filter(|_| imu_now.is_some())
+                .and_then(|imu_ref| imu_ref.acceleration().ok());
+            let contact = (dt > 0.0).then(|| ContactInput {
+                linear_velocity: local_x / dt,
+                lateral_velocity: (sideways_count > 0.0).then(|| local_y / dt),
+                angular_velocity: raw_angular,
+                acceleration,
+                drive,
+            });
+
             let dx_field = local_x * libm::cos(avg_heading.as_radians())
                 - local_y * libm::sin(avg_heading.as_radians());
             let dy_field = local_x * libm::sin(avg_heading.as_radians())
                 + local_y * libm::cos(avg_heading.as_radians());
 
             let mut state = data.borrow_mut();
+            let linear_velocity = state.linear_filter.update(raw_linear, dt);
+            let angular_velocity = state.angular_filter.update(raw_angular, dt);
             let (x, y) = (state.pose.position().x, state.pose.position().y);
             state.pose = Pose::new(
                 Vec2::<f64>::new(x + dx_field, y + dy_field),
                 raw_heading + state.heading_offset,
             );
             state.raw_heading = raw_heading;
-            state.forward_travel = forward_travel;
+            state.travel_source = travel_source;
+            state.wheels = forward_monitors
+                .iter()
+                .chain(sideways_monitors.iter())
+                .map(WheelMonitor::status)
+                .collect();
             state.linear_velocity = linear_velocity;
             state.angular_velocity = angular_velocity;
+            let sample = PoseSample {
+                pose: state.pose,
+                linear_velocity,
+                angular_velocity,
+                time: now,
+            };
+            state.history.push(sample);
+            if let Some(input) = contact {
+                let pose = state.pose;
+                state.contact.update(input, pose, now);
+            }
         }
     }
 }
 
+impl PoseSource for TrackingRig {
+    fn pose(&self) -> Pose {
+        TrackingRig::pose(self)
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        TrackingRig::linear_velocity(self)
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        TrackingRig::angular_velocity(self)
+    }
+
+    fn set_pose(&self, pose: Pose) {
+        TrackingRig::set_pose(self, pose);
+    }
+
+    fn observe_drive(&self, linear: f64, angular: f64) {
+        TrackingRig::observe_drive(self, linear, angular);
+    }
+
+    fn wheel_status(&self) -> std::vec::Vec<WheelStatus> {
+        TrackingRig::wheel_status(self)
+    }
+
+    fn heading_source(&self) -> Option<HeadingSource> {
+        Some(TrackingRig::heading_source(self))
+    }
+
+    fn travel_source(&self) -> Option<TravelSource> {
+        Some(TrackingRig::travel_source(self))
+    }
+}
+
 /// Internal state for the tracking rig's background task.
 ///
 /// This structure is shared between the main thread and the odometry task
 /// via `Rc<RefCell<TrackingData>>`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 struct TrackingData {
     /// Current pose estimate (position and heading)
     pose: Pose,
@@ -692,23 +1221,62 @@ struct TrackingData {
     raw_heading: QAngle,
     /// Offset applied to raw heading to get final heading
     heading_offset: QAngle,
-    /// Cumulative forward travel distance (meters)
-    forward_travel: f64,
     /// Current linear velocity (m/s)
     linear_velocity: f64,
     /// Current angular velocity (rad/s)
     angular_velocity: f64,
+    /// Sensor the latest heading change came from
+    heading_source: HeadingSource,
+    /// Number of failed IMU reads
+    imu_failures: u32,
+    /// Recent estimates for time-based lookup
+    history: PoseHistory,
+    /// Slip, push and collision flags
+    contact: ContactDetector,
+    /// Latest drivetrain velocity report (m/s, rad/s)
+    drive: Option<(f64, f64)>,
+    /// Updates since the latest drivetrain velocity report
+    drive_age: u32,
+    /// Time between updates
+    period: Duration,
+    /// Measured update timing
+    timing: LoopTiming,
+    /// Smoothing for the linear velocity
+    linear_filter: VelocityEstimator,
+    /// Smoothing for the angular velocity
+    angular_filter: VelocityEstimator,
+    /// Thresholds for tracking wheel fault detection
+    wheel_health: WheelHealthConfig,
+    /// Health of each wheel, vertical wheels first
+    wheels: Vec<WheelStatus, 4>,
+    /// Where the latest forward travel came from
+    travel_source: TravelSource,
 }
 
-/// Error type for heading computation failures.
-///
-/// Used internally to handle fallback from IMU to wheel-based heading.
-#[derive(Debug, Clone, Copy)]
-enum HeadingError {
-    /// IMU read failed; contains optional fallback heading from wheels
-    Imu(Option<QAngle>),
-    /// Rotation sensor read failed
-    Rotary,
+/// Number of estimates a rig keeps by default, about one second of updates.
+const DEFAULT_HISTORY: usize = 100;
+
+/// Default time between updates.
+const DEFAULT_PERIOD: Duration = Duration::from_millis(10);
+
+/// Turn rate (rad/s) above which the heading sensor confirms the robot is
+/// moving, for stale wheel detection.
+const MIN_TURN_RATE: f64 = 0.2;
+
+/// Number of updates a drivetrain velocity report stays valid for.
+const DRIVE_TIMEOUT: u32 = 5;
+
+/// Sensor a [`TrackingRig`] measured its latest heading change with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingSource {
+    /// The heading sensor
+    Imu,
+    /// The difference between two parallel forward wheels (the drive wheels
+    /// for an [`ImeRig`](crate::odom::ime::ImeRig)), used when there is no
+    /// heading sensor or it could not be read
+    Wheels,
+    /// Neither could be read, so the pose was not updated
+    Unavailable,
 }
 
 /// Finds indices of two parallel forward wheels suitable for heading calculation.
@@ -729,7 +1297,9 @@ enum HeadingError {
 /// # Tolerance
 ///
 /// Uses a tolerance of 0.5 meters for symmetry check.
-fn find_parallel_forward_indices(forward: &Vec<TrackingWheel, 2>) -> Option<(usize, usize)> {
+pub(crate) fn find_parallel_forward_indices<E: RotaryEncoder>(
+    forward: &Vec<TrackingWheel<E>, 2>,
+) -> Option<(usize, usize)> {
     const OFFSET_TOLERANCE: f64 = 0.5;
     let n = forward.len();
     if n < 2 {
@@ -749,90 +1319,46 @@ fn find_parallel_forward_indices(forward: &Vec<TrackingWheel, 2>) -> Option<(usi
     None
 }
 
-/// Computes the robot's raw heading from available sensors.
-///
-/// Attempts to read heading in the following priority order:
-/// 1. IMU heading (if available and working)
-/// 2. Wheel differential heading (if parallel wheels exist)
-///
-/// # Arguments
-///
-/// * `imu` - Optional reference to an inertial sensor
-/// * `parallel_indices` - Optional indices of parallel forward wheels
-/// * `forward` - Mutable slice of forward tracking wheels
-///
-/// # Returns
+/// Reads a heading sensor as a counter-clockwise angle.
 ///
-/// * `Ok(heading)` - Successfully computed heading
-/// * `Err(HeadingError::Imu(fallback))` - IMU failed; contains optional wheel-based fallback
-/// * `Err(HeadingError::Rotary)` - Rotation sensor failed
-///
-/// # Note
-///
-/// IMU heading is negated to match the convention (positive = counter-clockwise).
-fn compute_raw_heading(
-    imu: Option<&InertialSensor>,
-    parallel_indices: Option<&(usize, usize)>,
-    forward: &mut [TrackingWheel],
-) -> Result<QAngle, HeadingError> {
-    if let Some(imu_ref) = imu {
-        return match imu_ref.heading() {
-            Ok(heading) => Ok(QAngle::from_degrees(heading.as_degrees() * -1.0)),
-            Err(_) => Err(HeadingError::Imu(
-                parallel_indices.and_then(|(l, r)| wheel_heading(forward, *l, *r)),
-            )),
-        };
-    }
-
-    if let Some((left, right)) = parallel_indices {
-        if let Some(heading) = wheel_heading(forward, *left, *right) {
-            return Ok(heading);
-        }
-        return Err(HeadingError::Rotary);
-    }
-
-    Err(HeadingError::Imu(None))
+/// Returns `None` if the sensor could not be read.
+pub(crate) fn imu_heading<G: Gyro>(imu: &G) -> Option<QAngle> {
+    imu.heading()
+        .ok()
+        .map(|heading| QAngle::from_degrees(-heading.as_degrees()))
 }
 
-/// Computes heading from the differential of two parallel tracking wheels.
+/// Computes the heading change from the travel of two parallel tracking wheels.
 ///
 /// Uses the formula:
 /// ```text
-/// heading = (right_travel - left_travel) / track_width
+/// Δheading = (Δright - Δleft) / track_width
 /// ```
 ///
 /// Where `track_width` is the distance between the two wheels.
 ///
 /// # Arguments
 ///
-/// * `forward` - Mutable slice of forward tracking wheels
+/// * `forward` - Travel since the previous update (`None` for a wheel that
+///   cannot be used) and offset of each forward wheel, in meters
 /// * `left_index` - Index of the left wheel (negative offset)
 /// * `right_index` - Index of the right wheel (positive offset)
 ///
 /// # Returns
 ///
-/// `Some(heading)` if computation succeeds, `None` if indices are invalid
-/// or track width is zero.
+/// `Some(change)` in radians if both wheels can be used, `None` if either
+/// cannot, the indices are invalid or the track width is zero.
 fn wheel_heading(
-    forward: &mut [TrackingWheel],
+    forward: &[(Option<f64>, f64)],
     left_index: usize,
     right_index: usize,
-) -> Option<QAngle> {
-    if left_index >= forward.len() || right_index >= forward.len() {
-        return None;
-    }
-
-    let left_offset = forward[left_index].offset();
-    let right_offset = forward[right_index].offset();
-    let track_width = (right_offset - left_offset).as_meters().abs();
+) -> Option<f64> {
+    let (left_travel, left_offset) = *forward.get(left_index)?;
+    let (right_travel, right_offset) = *forward.get(right_index)?;
+    let track_width = (right_offset - left_offset).abs();
     if track_width == 0.0 {
         return None;
     }
 
-    let left_travel = forward[left_index].distance().as_meters();
-    let right_travel = forward[right_index].distance().as_meters();
-
-    Some(QAngle::from_radians(
-        (right_travel - left_travel) / track_width,
-    ))
+    Some((right_travel? - left_travel?) / track_width)
 }
@@ -0,0 +1,855 @@
+//! Simulated robot and devices.
+//!
+//! This module provides [`SimRobot`], a shared handle to a simulated
+//! [`DrivePlant`], and the simulated devices it hands out:
+//!
+//! - [`SimMotor`]: a drive motor implementing [`MotorOutput`]
+//! - [`SimEncoder`]: a tracking wheel encoder implementing [`RotaryEncoder`]
+//! - [`SimImu`]: an inertial sensor implementing [`Gyro`]
+//! - [`SimDistanceSensor`]: a distance sensor implementing [`RangeFinder`]
+//! - [`SimGps`]: a GPS sensor implementing [`Gps`]
+//!
+//! Sensor readings are derived from the true simulated pose and can be
+//! corrupted with [`SensorNoise`]. Every device shares the same underlying
+//! state, so motors commanded through a [`DifferentialDrive`](crate::DifferentialDrive)
+//! move the robot seen by a [`TrackingRig`](crate::TrackingRig) built from the
+//! same `SimRobot`.
+//!
+//! # Time
+//!
+//! The simulation only advances when [`SimRobot::step`] is called. On the host,
+//! [`SimRobot::spawn`] starts a background task that steps the simulation in
+//! real time alongside the chassis and odometry tasks.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use kernelvex::sim::devices::SimRobot;
+//! use kernelvex::sim::plant::PlantConfig;
+//! use kernelvex::util::utils::TrackingWheelOrientation;
+//! use kernelvex::{DifferentialDrive, ExpoDrive, MotorGroup, OmniWheel, Pose, QLength, TrackingRig};
+//! use vexide::smart::motor::Gearset;
+//!
+//! let config = PlantConfig::new(QLength::from_inches(12.0), OmniWheel::Omni325, 0.75, Gearset::Blue);
+//! let robot = SimRobot::new(config, Pose::default());
+//!
+//! let dt = DifferentialDrive::new(
+//!     MotorGroup::new([robot.left_motor(), robot.left_motor()]),
+//!     MotorGroup::new([robot.right_motor(), robot.right_motor()]),
+//!     ExpoDrive::new(0.0, 1.0, None),
+//!     OmniWheel::Omni325,
+//!     QLength::from_inches(12.0),
+//!     0.75,
+//! );
+//!
+//! let rig = TrackingRig::new(
+//!     Pose::default(),
+//!     [robot.tracking_wheel(OmniWheel::Omni275, TrackingWheelOrientation::Horizontal(QLength::from_inches(-2.0)), None)],
+//!     [robot.tracking_wheel(OmniWheel::Omni275, TrackingWheelOrientation::Vertical(QLength::from_inches(0.5)), None)],
+//!     Some(robot.imu()),
+//! );
+//!
+//! let _physics = robot.spawn();
+//! ```
+
+use crate::hal::distance::RangeFinder;
+use crate::hal::encoder::RotaryEncoder;
+use crate::hal::gps::Gps;
+use crate::hal::imu::Gyro;
+use crate::hal::motor::MotorOutput;
+use crate::odom::dist::{Field, sensor_pose};
+use crate::odom::pose::Pose;
+use crate::odom::wheel::{OmniWheel, TrackingWheel};
+use crate::sim::plant::{DrivePlant, MotorCommand, PlantConfig, wrap_positive};
+use crate::util::clock::{Clock, ManualClock, SystemClock};
+use crate::util::rng::Rng;
+use crate::util::si::{QAngle, QLength, Vec2};
+use crate::util::utils::TrackingWheelOrientation;
+use core::f64::consts::PI;
+use core::future::{Future, poll_fn};
+use core::task::Poll;
+use core::time::Duration;
+use std::sync::{Arc, Mutex, MutexGuard};
+use vexide::smart::PortError;
+use vexide::smart::distance::DistanceObjectError;
+use vexide::smart::imu::InertialError;
+use vexide::smart::motor::BrakeMode;
+use vexide_async::task::{Task, spawn};
+
+/// Sensor imperfections applied to simulated readings.
+///
+/// All fields default to zero, which produces perfect sensors.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SensorNoise {
+    /// Standard deviation of tracking wheel noise, as a fraction of the
+    /// distance travelled in each step.
+    pub encoder: f64,
+    /// Maximum fraction of travel lost to tracking wheel slip in each step.
+    /// The lost fraction is drawn uniformly from `[0, slip]`.
+    pub slip: f64,
+    /// Standard deviation of white noise on IMU heading readings (degrees).
+    pub imu_heading: f64,
+    /// Standard deviation of white noise on IMU yaw rate readings (deg/s).
+    pub imu_rate: f64,
+    /// Constant gyro bias that accumulates into heading drift (deg/s).
+    pub imu_drift: f64,
+    /// Standard deviation of white noise on distance sensor readings (m).
+    pub distance: f64,
+    /// Standard deviation of white noise on each GPS position axis (m).
+    pub gps_position: f64,
+    /// Standard deviation of white noise on GPS heading readings (degrees).
+    pub gps_heading: f64,
+}
+
+/// State of one simulated tracking wheel encoder.
+#[derive(Debug, Clone, Copy)]
+struct EncoderState {
+    orientation: TrackingWheelOrientation,
+    circumference: f64,
+    gearing: f64,
+    /// Measured ground travel of the wheel (m), including noise and slip.
+    travel: f64,
+    /// Encoder reading (turns) that corresponds to zero.
+    zero: f64,
+    /// Spurious counts (turns) added to every reading.
+    glitch: f64,
+    /// Raw rotation (turns) the reading is stuck at.
+    frozen: Option<f64>,
+    connected: bool,
+}
+
+impl EncoderState {
+    /// Returns the raw encoder rotation in turns.
+    fn turns(&self) -> f64 {
+        self.travel / self.circumference * self.gearing
+    }
+
+    /// Returns the rotation the encoder reports in turns, including faults.
+    fn reading(&self) -> f64 {
+        self.frozen.unwrap_or_else(|| self.turns()) + self.glitch
+    }
+}
+
+/// Shared simulation state.
+#[derive(Debug)]
+struct World {
+    plant: DrivePlant,
+    encoders: Vec<EncoderState>,
+    noise: SensorNoise,
+    rng: Rng,
+    /// Accumulated gyro drift (rad, counter-clockwise positive).
+    imu_drift: f64,
+    /// True heading (rad) at which every IMU starts reading zero.
+    imu_origin: f64,
+    imus: Vec<ImuState>,
+    /// Whether the robot is pinned in place while its drive wheels turn.
+    held: bool,
+    /// External velocity (m/s, field frame) that moves the robot without
+    /// turning its drive wheels.
+    push: Vec2<f64>,
+    /// Ground velocity (m/s) forward and leftward at the end of the last step.
+    ground: (f64, f64),
+    /// Filtered horizontal acceleration (m/s²) reported by every IMU.
+    acceleration: f64,
+}
+
+/// State of one simulated inertial sensor.
+#[derive(Debug, Clone, Copy)]
+struct ImuState {
+    /// Degrees reported per degree turned.
+    scale: f64,
+    /// Raw clockwise heading (rad) that corresponds to zero.
+    zero: f64,
+    /// Raw heading (rad) the sensor is stuck at, if it is frozen.
+    frozen: Option<f64>,
+    connected: bool,
+}
+
+impl ImuState {
+    const fn new() -> Self {
+        Self {
+            scale: 1.0,
+            zero: 0.0,
+            frozen: None,
+            connected: true,
+        }
+    }
+}
+
+impl World {
+    /// Returns the raw clockwise heading (rad) of an IMU before zeroing.
+    fn imu_raw(&self, index: usize) -> f64 {
+        if let Some(raw) = self.imus[index].frozen {
+            return raw;
+        }
+        let turned = self.plant.theta() - self.imu_origin + self.imu_drift;
+        -self.imus[index].scale * turned
+    }
+
+    fn step(&mut self, dt: Duration) {
+        let mut remaining = dt;
+        while !remaining.is_zero() {
+            let h = remaining.min(DrivePlant::MAX_STEP);
+            let pinned = self.held.then(|| {
+                let position = self.plant.pose().position();
+                (position.x, position.y, self.plant.theta())
+            });
+            self.plant.step(h);
+            if let Some((x, y, theta)) = pinned {
+                self.plant.place(x, y, theta);
+            }
+            let shift = self.push * h.as_secs_f64();
+            if shift.x != 0.0 || shift.y != 0.0 {
+                let position = self.plant.pose().position() + shift;
+                let theta = self.plant.theta();
+                self.plant.place(position.x, position.y, theta);
+            }
+            self.integrate_sensors(h.as_secs_f64());
+            remaining -= h;
+        }
+    }
+
+    fn integrate_sensors(&mut self, h: f64) {
+        let (mut v, w) = if self.held {
+            (0.0, 0.0)
+        } else {
+            (self.plant.linear_velocity(), self.plant.angular_velocity())
+        };
+        let (sin, cos) = libm::sincos(self.plant.theta());
+        v += self.push.x * cos + self.push.y * sin;
+        let lateral = self.push.y * cos - self.push.x * sin;
+        let noise = self.noise;
+
+        for encoder in self.encoders.iter_mut() {
+            // Ground velocity of the wheel's contact point along its rolling axis.
+            let speed = match encoder.orientation {
+                TrackingWheelOrientation::Vertical(offset) => v + w * offset.as_meters(),
+                TrackingWheelOrientation::Horizontal(offset) => lateral + w * offset.as_meters(),
+            };
+            let ds = speed * h;
+            let slip = if noise.slip > 0.0 {
+                self.rng.range(0.0, noise.slip)
+            } else {
+                0.0
+            };
+            let error = self.rng.gaussian(0.0, noise.encoder * ds.abs());
+
+            encoder.travel += ds * (1.0 - slip) + error;
+        }
+
+        self.imu_drift += noise.imu_drift.to_radians() * h;
+
+        // Body-frame acceleration, smoothed like the sensor's own filtering.
+        let (previous_v, previous_lateral) = self.ground;
+        let forward_accel = (v - previous_v) / h;
+        let lateral_accel = (lateral - previous_lateral) / h + v * w;
+        let accel = libm::hypot(forward_accel, lateral_accel);
+        self.acceleration += (accel - self.acceleration) * h / (ACCEL_FILTER + h);
+        self.ground = (v, lateral);
+    }
+}
+
+/// Time constant (s) of the simulated accelerometer's low-pass filter.
+const ACCEL_FILTER: f64 = 0.01;
+
+/// A shared handle to a simulated robot.
+///
+/// Cloning the handle shares the same simulation. Devices created from the
+/// handle read from and write to that simulation.
+#[derive(Debug, Clone)]
+pub struct SimRobot {
+    world: Arc<Mutex<World>>,
+}
+
+impl SimRobot {
+    /// Creates a stationary simulated robot at the given pose.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Physical parameters of the drivetrain
+    /// * `origin` - Initial true pose
+    pub fn new(config: PlantConfig, origin: Pose) -> Self {
+        Self {
+            world: Arc::new(Mutex::new(World {
+                plant: DrivePlant::new(config, origin),
+                encoders: Vec::new(),
+                noise: SensorNoise::default(),
+                rng: Rng::new(0),
+                imu_drift: 0.0,
+                imu_origin: origin.heading().as_radians(),
+                imus: vec![ImuState::new()],
+                held: false,
+                push: Vec2::new(0.0, 0.0),
+                ground: (0.0, 0.0),
+                acceleration: 0.0,
+            })),
+        }
+    }
+
+    /// Sets the sensor noise model.
+    pub fn with_noise(self, noise: SensorNoise) -> Self {
+        self.world().noise = noise;
+        self
+    }
+
+    /// Seeds the random number generator used for sensor noise.
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.world().rng = Rng::new(seed);
+        self
+    }
+
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Attaches a new motor to the left side of the drivetrain.
+    pub fn left_motor(&self) -> SimMotor {
+        self.motor(false)
+    }
+
+    /// Attaches a new motor to the right side of the drivetrain.
+    pub fn right_motor(&self) -> SimMotor {
+        self.motor(true)
+    }
+
+    fn motor(&self, right: bool) -> SimMotor {
+        let index = self.world().plant.add_motor(right);
+        SimMotor {
+            world: Arc::clone(&self.world),
+            right,
+            index,
+            zero: 0.0,
+        }
+    }
+
+    /// Creates a tracking wheel encoder mounted on the robot.
+    ///
+    /// # Arguments
+    ///
+    /// * `wheel` - The tracking wheel the encoder measures
+    /// * `orientation` - Mounting direction and offset, as passed to [`TrackingWheel::new`]
+    /// * `gearing` - Encoder rotations per wheel rotation. `None` means 1:1.
+    pub fn encoder(
+        &self,
+        wheel: OmniWheel,
+        orientation: TrackingWheelOrientation,
+        gearing: Option<f64>,
+    ) -> SimEncoder {
+        let mut world = self.world();
+        world.encoders.push(EncoderState {
+            orientation,
+            circumference: PI * wheel.size().as_meters(),
+            gearing: gearing.unwrap_or(1.0),
+            travel: 0.0,
+            zero: 0.0,
+            glitch: 0.0,
+            frozen: None,
+            connected: true,
+        });
+
+        SimEncoder {
+            world: Arc::clone(&self.world),
+            index: world.encoders.len() - 1,
+        }
+    }
+
+    /// Creates a [`TrackingWheel`] backed by a simulated encoder.
+    ///
+    /// The wheel and encoder share the same geometry, so distances reported by
+    /// the wheel match the simulated ground truth (before noise).
+    ///
+    /// # Arguments
+    ///
+    /// * `wheel` - The tracking wheel type
+    /// * `orientation` - Mounting direction and offset
+    /// * `gearing` - Encoder rotations per wheel rotation. `None` means 1:1.
+    pub fn tracking_wheel(
+        &self,
+        wheel: OmniWheel,
+        orientation: TrackingWheelOrientation,
+        gearing: Option<f64>,
+    ) -> TrackingWheel<SimEncoder> {
+        TrackingWheel::new(
+            self.encoder(wheel, orientation, gearing),
+            wheel,
+            orientation,
+            gearing,
+        )
+    }
+
+    /// Returns a handle to the robot's simulated inertial sensor.
+    ///
+    /// Every call returns the same sensor, so zeroing it through one handle
+    /// affects them all. Use [`add_imu`](Self::add_imu) for a second sensor.
+    pub fn imu(&self) -> SimImu {
+        SimImu {
+            world: Arc::clone(&self.world),
+            index: 0,
+        }
+    }
+
+    /// Mounts another simulated inertial sensor.
+    ///
+    /// The new sensor has its own zero, scale and connection state. All IMUs
+    /// on the same robot share one drift state.
+    pub fn add_imu(&self) -> SimImu {
+        let mut world = self.world();
+        world.imus.push(ImuState::new());
+
+        SimImu {
+            world: Arc::clone(&self.world),
+            index: world.imus.len() - 1,
+        }
+    }
+
+    /// Creates a simulated distance sensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `mount` - Sensor position and beam direction relative to the robot
+    ///   centre (x forward, y left, heading counter-clockwise from forward)
+    /// * `field` - The surfaces the beam can hit
+    pub fn distance_sensor(&self, mount: Pose, field: Field) -> SimDistanceSensor {
+        SimDistanceSensor {
+            world: Arc::clone(&self.world),
+            mount,
+            field,
+        }
+    }
+
+    /// Creates a simulated GPS sensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `mount` - Sensor position and facing relative to the robot centre
+    ///   (x forward, y left, heading counter-clockwise from forward)
+    pub fn gps(&self, mount: Pose) -> SimGps {
+        SimGps {
+            world: Arc::clone(&self.world),
+            mount,
+        }
+    }
+
+    /// Advances the simulation by `dt`.
+    pub fn step(&self, dt: Duration) {
+        self.world().step(dt);
+    }
+
+    /// Spawns a background task that advances the simulation in real time.
+    ///
+    /// The task steps the simulation every millisecond by the time actually
+    /// elapsed. The simulation stops when the returned task is dropped.
+    pub fn spawn(&self) -> Task<()> {
+        let robot = self.clone();
+        let clock = SystemClock::new();
+        spawn(async move {
+            let mut last = clock.now();
+            loop {
+                clock.sleep(Duration::from_millis(1)).await;
+                let now = clock.now();
+                robot.step(now.saturating_sub(last));
+                last = now;
+            }
+        })
+    }
+
+    /// Spawns a background task that advances the simulation on a manual clock.
+    ///
+    /// Each time the executor polls the task, it moves `clock` forward by one
+    /// millisecond and steps the simulation by the same amount. Controllers and
+    /// tracking rigs that share `clock` then see simulated time only, so runs
+    /// are repeatable and not limited to real time.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock to advance alongside the simulation
+    pub fn spawn_with_clock(&self, clock: ManualClock) -> Task<()> {
+        let robot = self.clone();
+        spawn(async move {
+            loop {
+                yield_now().await;
+                clock.advance(DrivePlant::MAX_STEP);
+                robot.step(DrivePlant::MAX_STEP);
+            }
+        })
+    }
+
+    /// Pins the robot in place, as when it is wedged against a wall or
+    /// another robot.
+    ///
+    /// The drive wheels keep turning as if on ice, so motor encoders see
+    /// motion that tracking wheels and IMUs do not.
+    pub fn set_held(&self, held: bool) {
+        self.world().held = held;
+    }
+
+    /// Moves the robot with an external velocity, as when another robot
+    /// shoves it.
+    ///
+    /// The drive wheels skid rather than turn, so only tracking wheels and
+    /// IMUs see the motion.
+    ///
+    /// # Arguments
+    ///
+    /// * `velocity` - Velocity in field coordinates (m/s); zero stops pushing
+    pub fn set_push(&self, velocity: Vec2<f64>) {
+        self.world().push = velocity;
+    }
+
+    /// Returns the true pose of the robot.
+    pub fn pose(&self) -> Pose {
+        self.world().plant.pose()
+    }
+
+    /// Teleports the robot to a pose and brings it to rest.
+    pub fn set_pose(&self, pose: Pose) {
+        self.world().plant.set_pose(pose);
+    }
+
+    /// Returns the true linear velocity in m/s.
+    pub fn linear_velocity(&self) -> f64 {
+        self.world().plant.linear_velocity()
+    }
+
+    /// Returns the true angular velocity in rad/s (counter-clockwise positive).
+    pub fn angular_velocity(&self) -> f64 {
+        self.world().plant.angular_velocity()
+    }
+
+    /// Returns the total simulated time.
+    pub fn time(&self) -> Duration {
+        self.world().plant.time()
+    }
+}
+
+/// A simulated drive motor.
+///
+/// Created with [`SimRobot::left_motor`] or [`SimRobot::right_motor`].
+/// Positive voltage drives the robot forward regardless of side.
+///
+/// [`BrakeMode::Coast`] removes all torque. [`BrakeMode::Brake`] and
+/// [`BrakeMode::Hold`] short the motor so it resists motion with its back-EMF.
+#[derive(Debug)]
+pub struct SimMotor {
+    world: Arc<Mutex<World>>,
+    right: bool,
+    index: usize,
+    /// Motor rotation (turns) that corresponds to zero.
+    zero: f64,
+}
+
+impl SimMotor {
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn raw_turns(&self) -> f64 {
+        let world = self.world();
+        let (left, right) = world.plant.wheel_travel();
+        world
+            .plant
+            .motor_turns(if self.right { right } else { left })
+    }
+}
+
+impl MotorOutput for SimMotor {
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+        self.world()
+            .plant
+            .command(self.right, self.index, MotorCommand::Voltage(volts));
+        Ok(())
+    }
+
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError> {
+        self.world()
+            .plant
+            .command(self.right, self.index, MotorCommand::Brake(mode));
+        Ok(())
+    }
+
+    fn velocity(&self) -> Result<f64, PortError> {
+        let world = self.world();
+        let (left, right) = world.plant.wheel_velocities();
+        Ok(world.plant.motor_rpm(if self.right { right } else { left }))
+    }
+
+    fn position(&self) -> Result<QAngle, PortError> {
+        Ok(QAngle::from_turns(self.raw_turns() - self.zero))
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        self.zero = self.raw_turns() - position.as_turns();
+        Ok(())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.set_position(QAngle::from_turns(0.0))
+    }
+}
+
+/// A simulated tracking wheel encoder.
+///
+/// Created with [`SimRobot::encoder`] or [`SimRobot::tracking_wheel`].
+///
+/// Clones are handles to the same encoder, so a test can keep one to inject
+/// faults after passing the other to a [`TrackingWheel`].
+#[derive(Debug, Clone)]
+pub struct SimEncoder {
+    world: Arc<Mutex<World>>,
+    index: usize,
+}
+
+impl SimEncoder {
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Connects or disconnects the encoder. A disconnected encoder fails every
+    /// read with [`PortError::Disconnected`] on port 0.
+    pub fn set_connected(&self, connected: bool) {
+        self.world().encoders[self.index].connected = connected;
+    }
+
+    /// Freezes the reading at its current value, as an unplugged ADI encoder
+    /// does, or lets it follow the wheel again.
+    pub fn set_frozen(&self, frozen: bool) {
+        let mut world = self.world();
+        let encoder = &mut world.encoders[self.index];
+        encoder.frozen = frozen.then(|| encoder.turns());
+    }
+
+    /// Adds spurious counts to the reading, as electrical noise on a damaged
+    /// cable does.
+    ///
+    /// # Arguments
+    ///
+    /// * `turns` - Encoder rotations added to every later reading
+    pub fn glitch(&self, turns: f64) {
+        self.world().encoders[self.index].glitch += turns;
+    }
+}
+
+impl RotaryEncoder for SimEncoder {
+    fn position(&self) -> Result<QAngle, PortError> {
+        let world = self.world();
+        let encoder = &world.encoders[self.index];
+        if !encoder.connected {
+            return Err(PortError::Disconnected { port: 0 });
+        }
+        Ok(QAngle::from_turns(encoder.reading() - encoder.zero))
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        let mut world = self.world();
+        let encoder = &mut world.encoders[self.index];
+        if !encoder.connected {
+            return Err(PortError::Disconnected { port: 0 });
+        }
+        encoder.zero = encoder.reading() - position.as_turns();
+        Ok(())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.set_position(QAngle::from_turns(0.0))
+    }
+}
+
+/// A simulated inertial sensor.
+///
+/// Created with [`SimRobot::imu`]. Readings follow the V5 convention of the
+/// [`Gyro`] trait: heading is clockwise in `[0, 360)` degrees. Like a freshly
+/// calibrated V5 IMU, it reads zero at the robot's starting heading.
+///
+/// Clones are handles to the same sensor.
+#[derive(Debug, Clone)]
+pub struct SimImu {
+    world: Arc<Mutex<World>>,
+    index: usize,
+}
+
+impl SimImu {
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Sets the current heading reading, as [`InertialSensor::set_heading`](vexide::smart::imu::InertialSensor::set_heading) does.
+    ///
+    /// # Arguments
+    ///
+    /// * `heading` - The clockwise heading to report from now on
+    pub fn set_heading(&mut self, heading: QAngle) {
+        let mut world = self.world();
+        let raw = world.imu_raw(self.index);
+        world.imus[self.index].zero = raw - heading.as_radians();
+    }
+
+    /// Sets the sensor's scale error.
+    ///
+    /// # Arguments
+    ///
+    /// * `scale` - Degrees reported per degree actually turned, for example
+    ///   `357.0 / 360.0` for a sensor that under-reads by 3° per turn
+    pub fn set_scale(&self, scale: f64) {
+        self.world().imus[self.index].scale = scale;
+    }
+
+    /// Connects or disconnects the sensor. A disconnected sensor fails every
+    /// read with [`PortError::Disconnected`] on port 0.
+    pub fn set_connected(&self, connected: bool) {
+        self.world().imus[self.index].connected = connected;
+    }
+
+    /// Freezes the heading at its current value with a zero turn rate, as a
+    /// sensor that has locked up does, or lets it follow the robot again.
+    pub fn set_frozen(&self, frozen: bool) {
+        let mut world = self.world();
+        let raw = world.imu_raw(self.index);
+        world.imus[self.index].frozen = frozen.then_some(raw);
+    }
+}
+
+impl Gyro for SimImu {
+    fn heading(&self) -> Result<QAngle, InertialError> {
+        let mut world = self.world();
+        if !world.imus[self.index].connected {
+            return Err(InertialError::Port {
+                source: PortError::Disconnected { port: 0 },
+            });
+        }
+        let stddev = world.noise.imu_heading;
+        let error = world.rng.gaussian(0.0, stddev).to_radians();
+        let cw = world.imu_raw(self.index) - world.imus[self.index].zero + error;
+        Ok(QAngle::from_radians(wrap_positive(cw)))
+    }
+
+    fn gyro_rate(&self) -> Result<f64, PortError> {
+        let mut world = self.world();
+        let imu = world.imus[self.index];
+        if !imu.connected {
+            return Err(PortError::Disconnected { port: 0 });
+        }
+        let stddev = world.noise.imu_rate;
+        let error = world.rng.gaussian(0.0, stddev);
+        if imu.frozen.is_some() {
+            return Ok(error);
+        }
+        let rate = world.plant.angular_velocity().to_degrees() + world.noise.imu_drift;
+        Ok(imu.scale * rate + error)
+    }
+
+    fn acceleration(&self) -> Result<f64, PortError> {
+        let world = self.world();
+        if !world.imus[self.index].connected {
+            return Err(PortError::Disconnected { port: 0 });
+        }
+        Ok(world.acceleration)
+    }
+}
+
+/// A simulated distance sensor.
+///
+/// Created with [`SimRobot::distance_sensor`]. Readings are ray cast from the
+/// true robot pose against a [`Field`] map. Like the V5 sensor, nothing is
+/// reported beyond [`SimDistanceSensor::MAX_RANGE`].
+#[derive(Debug)]
+pub struct SimDistanceSensor {
+    world: Arc<Mutex<World>>,
+    mount: Pose,
+    field: Field,
+}
+
+impl SimDistanceSensor {
+    /// Maximum range of the V5 distance sensor.
+    pub const MAX_RANGE: QLength = QLength::from_meters(2.0);
+
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl RangeFinder for SimDistanceSensor {
+    fn distance(&self) -> Result<Option<QLength>, DistanceObjectError> {
+        let mut world = self.world();
+        let robot = world.plant.pose();
+
+        let Some(hit) = self.field.expected_reading(robot, self.mount) else {
+            return Ok(None);
+        };
+        if hit.as_meters() > Self::MAX_RANGE.as_meters() {
+            return Ok(None);
+        }
+
+        let stddev = world.noise.distance;
+        let error = world.rng.gaussian(0.0, stddev);
+        Ok(Some(QLength::from_meters((hit.as_meters() + error).max(0.0))))
+    }
+}
+
+/// A simulated GPS sensor.
+///
+/// Created with [`SimRobot::gps`]. Readings follow the V5 convention of the
+/// [`Gps`] trait and describe the sensor itself, so they include its mounting
+/// offset. The simulation frame is used as the GPS field frame. The reported
+/// error is the configured position noise.
+#[derive(Debug)]
+pub struct SimGps {
+    world: Arc<Mutex<World>>,
+    mount: Pose,
+}
+
+impl SimGps {
+    fn world(&self) -> MutexGuard<'_, World> {
+        self.world
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Gps for SimGps {
+    fn position(&self) -> Result<Vec2<f64>, PortError> {
+        let mut world = self.world();
+        let sensor = sensor_pose(world.plant.pose(), self.mount).position();
+        let stddev = world.noise.gps_position;
+        Ok(Vec2::new(
+            world.rng.gaussian(sensor.x, stddev),
+            world.rng.gaussian(sensor.y, stddev),
+        ))
+    }
+
+    fn heading(&self) -> Result<QAngle, PortError> {
+        let mut world = self.world();
+        let sensor = sensor_pose(world.plant.pose(), self.mount).heading();
+        let stddev = world.noise.gps_heading;
+        let error = world.rng.gaussian(0.0, stddev).to_radians();
+        let cw = PI / 2.0 - sensor.as_radians() + error;
+        Ok(QAngle::from_radians(wrap_positive(cw)))
+    }
+
+    fn error(&self) -> Result<QLength, PortError> {
+        Ok(QLength::from_meters(self.world().noise.gps_position))
+    }
+}
+
+/// Returns control to the executor once before completing.
+fn yield_now() -> impl Future<Output = ()> {
+    let mut yielded = false;
+    poll_fn(move |cx| {
+        if yielded {
+            Poll::Ready(())
+        } else {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+}
@@ -0,0 +1,2 @@
+pub mod devices;
+pub mod plant;
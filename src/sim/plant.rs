@@ -0,0 +1,441 @@
+//! Rigid-body physics model of a differential drivetrain.
+//!
+//! This module provides [`DrivePlant`], a simulated robot chassis driven by
+//! DC motors on its left and right sides, and the configuration types used to
+//! build it.
+//!
+//! # Model
+//!
+//! Each motor follows a linear DC motor curve scaled to its gearset:
+//!
+//! ```text
+//! torque = stall_torque * (volts / 12 - rpm / free_rpm)
+//! ```
+//!
+//! Motor torque is converted to force at the ground through the external gear
+//! ratio and the wheel radius. The chassis is modelled as a rigid body with
+//! mass `m` and moment of inertia `I`, with no lateral slip at the drive wheels:
+//!
+//! ```text
+//! m * dv/dt = F_left + F_right - linear_damping * v
+//! I * dω/dt = (F_right - F_left) * track_width / 2 - angular_damping * ω
+//! ```
+//!
+//! Heading follows the library convention: counter-clockwise positive, in
+//! radians, with `0` facing the positive x-axis.
+//!
+//! The plant is integrated with semi-implicit Euler steps of at most
+//! [`DrivePlant::MAX_STEP`].
+
+use crate::OmniWheel;
+use crate::odom::pose::Pose;
+use crate::util::si::{QAngle, QLength, Vec2};
+use core::f64::consts::{PI, TAU};
+use core::time::Duration;
+use vexide::smart::motor::{BrakeMode, Gearset};
+
+/// A linear DC motor torque-speed curve.
+///
+/// Defaults describe the V5 smart motor, whose 11 W motor produces 2.1 N·m at
+/// stall behind the 36:1 cartridge and scales inversely with cartridge ratio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotorCurve {
+    /// Output shaft speed at nominal voltage with no load (RPM).
+    pub free_rpm: f64,
+    /// Output shaft torque at nominal voltage and zero speed (N·m).
+    pub stall_torque: f64,
+    /// Voltage at which `free_rpm` and `stall_torque` are rated (V).
+    pub nominal_voltage: f64,
+}
+
+impl MotorCurve {
+    /// Returns the curve of a V5 smart motor fitted with the given cartridge.
+    ///
+    /// # Arguments
+    ///
+    /// * `gearset` - The motor cartridge (red, green or blue)
+    pub const fn v5(gearset: Gearset) -> Self {
+        let (free_rpm, stall_torque) = match gearset {
+            Gearset::Red => (100.0, 2.1),
+            Gearset::Green => (200.0, 1.05),
+            Gearset::Blue => (600.0, 0.35),
+        };
+
+        Self {
+            free_rpm,
+            stall_torque,
+            nominal_voltage: 12.0,
+        }
+    }
+
+    /// Returns the output torque for a commanded voltage and shaft speed.
+    ///
+    /// # Arguments
+    ///
+    /// * `volts` - Applied voltage, clamped to the nominal voltage
+    /// * `rpm` - Current output shaft speed in RPM
+    ///
+    /// # Returns
+    ///
+    /// Output shaft torque in N·m.
+    pub fn torque(&self, volts: f64, rpm: f64) -> f64 {
+        let volts = volts.clamp(-self.nominal_voltage, self.nominal_voltage);
+        self.stall_torque * (volts / self.nominal_voltage - rpm / self.free_rpm)
+    }
+}
+
+/// Physical parameters of a simulated differential drivetrain.
+///
+/// Use [`PlantConfig::new`] (or [`PlantConfig::from_drivetrain`]) and the
+/// `with_*` methods to build a configuration.
+///
+/// # Example
+///
+/// ```
+/// use kernelvex::sim::plant::PlantConfig;
+/// use kernelvex::{OmniWheel, QLength};
+/// use vexide::smart::motor::Gearset;
+///
+/// let config = PlantConfig::new(QLength::from_inches(12.0), OmniWheel::Omni325, 0.75, Gearset::Blue)
+///     .with_mass(7.0)
+///     .with_moment_of_inertia(0.12);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PlantConfig {
+    /// Robot mass (kg).
+    pub mass: f64,
+    /// Moment of inertia about the vertical axis (kg·m²).
+    pub moment_of_inertia: f64,
+    /// Distance between left and right wheel contact points.
+    pub track_width: QLength,
+    /// Drive wheel type.
+    pub wheel: OmniWheel,
+    /// External gear ratio (wheel rotations per motor rotation), matching
+    /// [`DifferentialDrive`](crate::DifferentialDrive).
+    pub ratio: f64,
+    /// Torque-speed curve of each drive motor.
+    pub motor: MotorCurve,
+    /// Viscous resistance to forward motion (N per m/s).
+    pub linear_damping: f64,
+    /// Viscous resistance to rotation (N·m per rad/s).
+    pub angular_damping: f64,
+}
+
+impl PlantConfig {
+    /// Creates a configuration with default mass and inertia.
+    ///
+    /// The defaults describe a typical 15 lb (6.8 kg) competition robot with
+    /// its mass spread over a square footprint as wide as the track.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_width` - Distance between left and right wheels
+    /// * `wheel` - Drive wheel type
+    /// * `ratio` - Wheel rotations per motor rotation
+    /// * `gearset` - Motor cartridge, used to select the motor curve
+    pub fn new(track_width: QLength, wheel: OmniWheel, ratio: f64, gearset: Gearset) -> Self {
+        const DEFAULT_MASS: f64 = 6.8;
+        let width = track_width.as_meters();
+
+        Self {
+            mass: DEFAULT_MASS,
+            moment_of_inertia: DEFAULT_MASS * width * width / 6.0,
+            track_width,
+            wheel,
+            ratio,
+            motor: MotorCurve::v5(gearset),
+            linear_damping: 1.0,
+            angular_damping: 0.05,
+        }
+    }
+
+    /// Creates a configuration matching an existing drivetrain's geometry.
+    ///
+    /// Track width, wheel size and gear ratio are read from the drivetrain.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - The drivetrain to mirror
+    /// * `gearset` - Motor cartridge fitted to the drive motors
+    pub fn from_drivetrain<M: crate::MotorOutput>(
+        dt: &crate::DifferentialDrive<M>,
+        gearset: Gearset,
+    ) -> Self {
+        Self::new(dt.width(), *dt.wheel(), dt.ratio(), gearset)
+    }
+
+    /// Sets the robot mass in kilograms.
+    pub fn with_mass(mut self, mass: f64) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets the moment of inertia in kg·m².
+    pub fn with_moment_of_inertia(mut self, moment_of_inertia: f64) -> Self {
+        self.moment_of_inertia = moment_of_inertia;
+        self
+    }
+
+    /// Replaces the motor curve.
+    pub fn with_motor(mut self, motor: MotorCurve) -> Self {
+        self.motor = motor;
+        self
+    }
+
+    /// Sets the linear and angular viscous damping coefficients.
+    ///
+    /// # Arguments
+    ///
+    /// * `linear` - Resistance to forward motion (N per m/s)
+    /// * `angular` - Resistance to rotation (N·m per rad/s)
+    pub fn with_damping(mut self, linear: f64, angular: f64) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
+    /// Returns the theoretical top speed of the chassis in m/s.
+    ///
+    /// This is the ground speed at the motor's free speed, ignoring damping.
+    pub fn max_velocity(&self) -> f64 {
+        self.motor.free_rpm * self.ratio * PI * self.wheel.size().as_meters() / 60.0
+    }
+}
+
+/// Command state of a single simulated motor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum MotorCommand {
+    /// A voltage is applied across the motor.
+    Voltage(f64),
+    /// The motor is stopped with the given brake mode.
+    Brake(BrakeMode),
+}
+
+impl MotorCommand {
+    /// Returns the output torque of a motor under this command.
+    fn torque(&self, curve: &MotorCurve, rpm: f64) -> f64 {
+        match *self {
+            MotorCommand::Voltage(volts) => curve.torque(volts, rpm),
+            MotorCommand::Brake(BrakeMode::Coast) => 0.0,
+            // Shorting the windings brakes the motor with its own back-EMF.
+            MotorCommand::Brake(_) => curve.torque(0.0, rpm),
+        }
+    }
+}
+
+/// Simulated differential drivetrain physics.
+///
+/// The plant owns the true state of the robot: its pose, its body velocities
+/// and the cumulative travel of each side's wheels. Motors are attached to a
+/// side with [`add_motor`](Self::add_motor) and commanded by index.
+#[derive(Debug, Clone)]
+pub struct DrivePlant {
+    config: PlantConfig,
+    x: f64,
+    y: f64,
+    theta: f64,
+    linear_velocity: f64,
+    angular_velocity: f64,
+    left_travel: f64,
+    right_travel: f64,
+    left: Vec<MotorCommand>,
+    right: Vec<MotorCommand>,
+    time: Duration,
+}
+
+impl DrivePlant {
+    /// Largest integration step; longer steps are subdivided.
+    pub const MAX_STEP: Duration = Duration::from_millis(1);
+
+    /// Creates a stationary plant at the given pose with no motors attached.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Physical parameters of the robot
+    /// * `origin` - Initial true pose of the robot
+    pub fn new(config: PlantConfig, origin: Pose) -> Self {
+        Self {
+            config,
+            x: origin.position().x,
+            y: origin.position().y,
+            theta: origin.heading().as_radians(),
+            linear_velocity: 0.0,
+            angular_velocity: 0.0,
+            left_travel: 0.0,
+            right_travel: 0.0,
+            left: Vec::new(),
+            right: Vec::new(),
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Returns the physical configuration of the plant.
+    pub fn config(&self) -> &PlantConfig {
+        &self.config
+    }
+
+    /// Attaches a new motor to one side of the drivetrain.
+    ///
+    /// # Arguments
+    ///
+    /// * `right` - `true` for the right side, `false` for the left
+    ///
+    /// # Returns
+    ///
+    /// The index of the motor on that side.
+    pub(crate) fn add_motor(&mut self, right: bool) -> usize {
+        let side = if right {
+            &mut self.right
+        } else {
+            &mut self.left
+        };
+        side.push(MotorCommand::Brake(BrakeMode::Coast));
+        side.len() - 1
+    }
+
+    /// Updates the command of an attached motor.
+    pub(crate) fn command(&mut self, right: bool, index: usize, command: MotorCommand) {
+        let side = if right {
+            &mut self.right
+        } else {
+            &mut self.left
+        };
+        side[index] = command;
+    }
+
+    /// Returns the true pose of the robot.
+    pub fn pose(&self) -> Pose {
+        Pose::new(Vec2::new(self.x, self.y), QAngle::from_radians(self.theta))
+    }
+
+    /// Returns the true heading in radians, counter-clockwise positive and unwrapped.
+    pub fn theta(&self) -> f64 {
+        self.theta
+    }
+
+    /// Returns the true linear velocity in m/s.
+    pub fn linear_velocity(&self) -> f64 {
+        self.linear_velocity
+    }
+
+    /// Returns the true angular velocity in rad/s (counter-clockwise positive).
+    pub fn angular_velocity(&self) -> f64 {
+        self.angular_velocity
+    }
+
+    /// Returns the total simulated time.
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    /// Returns the cumulative ground travel of the left and right wheels in metres.
+    pub fn wheel_travel(&self) -> (f64, f64) {
+        (self.left_travel, self.right_travel)
+    }
+
+    /// Returns the ground speed of the left and right wheels in m/s.
+    pub fn wheel_velocities(&self) -> (f64, f64) {
+        let half_width = self.config.track_width.as_meters() * 0.5;
+        (
+            self.linear_velocity - self.angular_velocity * half_width,
+            self.linear_velocity + self.angular_velocity * half_width,
+        )
+    }
+
+    /// Converts a wheel ground speed (m/s) into motor shaft speed (RPM).
+    pub(crate) fn motor_rpm(&self, wheel_velocity: f64) -> f64 {
+        let circumference = PI * self.config.wheel.size().as_meters();
+        wheel_velocity / circumference * 60.0 / self.config.ratio
+    }
+
+    /// Converts wheel ground travel (m) into motor shaft rotation (turns).
+    pub(crate) fn motor_turns(&self, wheel_travel: f64) -> f64 {
+        let circumference = PI * self.config.wheel.size().as_meters();
+        wheel_travel / circumference / self.config.ratio
+    }
+
+    /// Teleports the robot to a pose and brings it to rest.
+    ///
+    /// Wheel travel is left untouched so attached encoders do not jump.
+    pub fn set_pose(&mut self, pose: Pose) {
+        self.x = pose.position().x;
+        self.y = pose.position().y;
+        self.theta = pose.heading().as_radians();
+        self.linear_velocity = 0.0;
+        self.angular_velocity = 0.0;
+    }
+
+    /// Moves the robot without changing its velocities or wheel travel.
+    ///
+    /// `theta` is unwrapped, as returned by [`theta`](Self::theta).
+    pub(crate) fn place(&mut self, x: f64, y: f64, theta: f64) {
+        self.x = x;
+        self.y = y;
+        self.theta = theta;
+    }
+
+    /// Advances the simulation by `dt`.
+    ///
+    /// Steps longer than [`MAX_STEP`](Self::MAX_STEP) are subdivided.
+    ///
+    /// # Arguments
+    ///
+    /// * `dt` - Time to advance
+    pub fn step(&mut self, dt: Duration) {
+        let mut remaining = dt;
+        while !remaining.is_zero() {
+            let h = remaining.min(Self::MAX_STEP);
+            self.integrate(h.as_secs_f64());
+            remaining -= h;
+        }
+        self.time += dt;
+    }
+
+    /// Integrates a single semi-implicit Euler step of `h` seconds.
+    fn integrate(&mut self, h: f64) {
+        let cfg = self.config;
+        let radius = cfg.wheel.size().as_meters() * 0.5;
+        let half_width = cfg.track_width.as_meters() * 0.5;
+        let (left_v, right_v) = self.wheel_velocities();
+
+        let side_force = |commands: &[MotorCommand], rpm: f64| -> f64 {
+            commands
+                .iter()
+                .map(|command| command.torque(&cfg.motor, rpm))
+                .sum::<f64>()
+                / cfg.ratio
+                / radius
+        };
+
+        let left_force = side_force(&self.left, self.motor_rpm(left_v));
+        let right_force = side_force(&self.right, self.motor_rpm(right_v));
+
+        let accel =
+            (left_force + right_force - cfg.linear_damping * self.linear_velocity) / cfg.mass;
+        let alpha = ((right_force - left_force) * half_width
+            - cfg.angular_damping * self.angular_velocity)
+            / cfg.moment_of_inertia;
+
+        self.linear_velocity += accel * h;
+        self.angular_velocity += alpha * h;
+
+        let mid_theta = self.theta + self.angular_velocity * h * 0.5;
+        self.x += self.linear_velocity * libm::cos(mid_theta) * h;
+        self.y += self.linear_velocity * libm::sin(mid_theta) * h;
+        self.theta += self.angular_velocity * h;
+
+        let (left_v, right_v) = self.wheel_velocities();
+        self.left_travel += left_v * h;
+        self.right_travel += right_v * h;
+    }
+}
+
+/// Wraps an angle in radians to `[0, 2π)`.
+pub(crate) fn wrap_positive(radians: f64) -> f64 {
+    let wrapped = radians % TAU;
+    if wrapped < 0.0 {
+        wrapped + TAU
+    } else {
+        wrapped
+    }
+}
@@ -0,0 +1,223 @@
+//! Time sources for controllers and background tasks.
+//!
+//! This module defines the [`Clock`] trait, which abstracts how the library
+//! reads the current time and waits between control loop iterations.
+//!
+//! - [`SystemClock`]: wall-clock time, used by default everywhere
+//! - [`ManualClock`]: a clock that only moves when [`ManualClock::advance`] is
+//!   called, for deterministic tests and simulation
+//!
+//! [`Pid`](crate::Pid), [`AngularPid`](crate::AngularPid),
+//! [`TrackingRig`](crate::TrackingRig) and [`OdomChassis`](crate::OdomChassis)
+//! all accept a clock. With a `ManualClock`, controller outputs depend only on
+//! the time steps the test chooses, not on how fast the host runs.
+//!
+//! # Example
+//!
+//! ```
+//! use core::time::Duration;
+//! use kernelvex::util::clock::{Clock, ManualClock};
+//! use kernelvex::Pid;
+//!
+//! let clock = ManualClock::new();
+//! let mut pid = Pid::new().set_gains(0.0, 1.0, 0.0).with_clock(clock.clone());
+//!
+//! clock.advance(Duration::from_millis(500));
+//! assert_eq!(pid.calculate(2.0, 0.0), 1.0);
+//! ```
+
+use core::future::{Future, poll_fn};
+use core::task::Poll;
+use core::time::Duration;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// A source of time that can also suspend the current task.
+///
+/// Clocks are cheap to clone; clones observe the same time.
+pub trait Clock: Clone {
+    /// Returns the time elapsed since the clock's epoch.
+    fn now(&self) -> Duration;
+
+    /// Waits until `duration` has elapsed on this clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long to wait
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()>;
+}
+
+/// Wall-clock time backed by [`Instant`].
+///
+/// The epoch is the moment the clock was created. Sleeping uses the async
+/// runtime's timer.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    epoch: Instant,
+}
+
+impl SystemClock {
+    /// Creates a clock whose epoch is the current instant.
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.epoch.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> {
+        vexide_async::time::sleep(duration)
+    }
+}
+
+/// A clock that only advances when told to.
+///
+/// Time starts at zero and moves forward with [`advance`](Self::advance).
+/// All clones share the same time, so a test (or a simulator) can hold one
+/// clone and drive every controller that holds another.
+///
+/// Sleeping on a `ManualClock` yields to the executor until another task has
+/// advanced the clock past the deadline.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    nanos: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Creates a clock at time zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How far to advance
+    pub fn advance(&self, duration: Duration) {
+        self.nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+
+    /// Sets the clock to an absolute time since its epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The new current time
+    pub fn set(&self, time: Duration) {
+        self.nanos.store(time.as_nanos() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::SeqCst))
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> {
+        let deadline = self.now() + duration;
+        let clock = self.clone();
+
+        poll_fn(move |cx| {
+            if clock.now() >= deadline {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+    }
+}
+
+/// Timing statistics for a periodic loop.
+///
+/// A background task calls [`record`](Self::record) each time it wakes. An
+/// update that starts more than half a period late counts as a missed
+/// deadline, which usually means other tasks are holding the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopTiming {
+    period: Duration,
+    updates: u32,
+    total: Duration,
+    max_period: Duration,
+    max_jitter: Duration,
+    missed_deadlines: u32,
+}
+
+impl LoopTiming {
+    /// Creates empty statistics for a loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The intended time between updates
+    pub const fn new(period: Duration) -> Self {
+        Self {
+            period,
+            updates: 0,
+            total: Duration::ZERO,
+            max_period: Duration::ZERO,
+            max_jitter: Duration::ZERO,
+            missed_deadlines: 0,
+        }
+    }
+
+    /// Records the time since the previous update.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - Measured time between the previous update and this one
+    pub fn record(&mut self, elapsed: Duration) {
+        self.updates = self.updates.saturating_add(1);
+        self.total = self.total.saturating_add(elapsed);
+        self.max_period = self.max_period.max(elapsed);
+        self.max_jitter = self.max_jitter.max(elapsed.abs_diff(self.period));
+        if elapsed > self.period + self.period / 2 {
+            self.missed_deadlines = self.missed_deadlines.saturating_add(1);
+        }
+    }
+
+    /// Returns the intended time between updates.
+    pub const fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns the number of updates recorded.
+    pub const fn updates(&self) -> u32 {
+        self.updates
+    }
+
+    /// Returns the mean measured time between updates, or zero before the
+    /// first update.
+    pub fn mean_period(&self) -> Duration {
+        self.total.checked_div(self.updates).unwrap_or_default()
+    }
+
+    /// Returns the longest measured time between updates.
+    pub const fn max_period(&self) -> Duration {
+        self.max_period
+    }
+
+    /// Returns the largest difference between a measured and the intended
+    /// time between updates.
+    pub const fn max_jitter(&self) -> Duration {
+        self.max_jitter
+    }
+
+    /// Returns the number of updates that started more than half a period
+    /// late.
+    pub const fn missed_deadlines(&self) -> u32 {
+        self.missed_deadlines
+    }
+}
@@ -1,4 +1,11 @@
+pub mod clock;
 pub mod logger;
+// Without the simulator, only the particle filter samples with the
+// generator, so it stays out of the public API.
+#[cfg(feature = "sim")]
+pub mod rng;
+#[cfg(not(feature = "sim"))]
+pub(crate) mod rng;
 pub mod si;
 pub mod solenoidgroup;
 pub mod utils;
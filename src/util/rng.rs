@@ -0,0 +1,99 @@
+//! Small deterministic pseudo-random number generator.
+//!
+//! This module provides [`Rng`], a seedable xorshift generator used where the
+//! library needs randomness without pulling in an external crate: sensor noise
+//! in the simulator and particle sampling in localization.
+//!
+//! The generator is **not** cryptographically secure. Its purpose is speed and
+//! reproducibility: two generators created with the same seed always produce
+//! the same sequence, which keeps simulated runs repeatable in tests.
+//!
+//! # Example
+//!
+//! ```
+//! use kernelvex::util::rng::Rng;
+//!
+//! let mut rng = Rng::new(42);
+//! let u = rng.uniform();
+//! assert!((0.0..1.0).contains(&u));
+//!
+//! let noise = rng.gaussian(0.0, 0.1);
+//! assert!(noise.is_finite());
+//! ```
+
+/// A seedable xorshift64* pseudo-random number generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    /// Internal generator state (never zero).
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed.
+    ///
+    /// A seed of zero is remapped to a fixed non-zero constant, since the
+    /// xorshift state must never be zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Initial seed value
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// Returns the next raw 64-bit value.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a uniformly distributed value in `[0, 1)`.
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a uniformly distributed value in `[min, max)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - Lower bound (inclusive)
+    /// * `max` - Upper bound (exclusive)
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.uniform()
+    }
+
+    /// Returns a normally distributed value using the Box-Muller transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `mean` - Mean of the distribution
+    /// * `stddev` - Standard deviation. A value of zero returns `mean` exactly.
+    pub fn gaussian(&mut self, mean: f64, stddev: f64) -> f64 {
+        if stddev == 0.0 {
+            return mean;
+        }
+
+        // Shift into (0, 1] so the logarithm is always finite.
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        let z = libm::sqrt(-2.0 * libm::log(u1)) * libm::cos(core::f64::consts::TAU * u2);
+
+        mean + stddev * z
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
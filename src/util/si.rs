@@ -746,7 +746,6 @@ impl QAngle {
     /// # Returns
     ///
     /// A `QAngle` representing the given angle.
-
     #[allow(dead_code)]
     #[inline]
     pub const fn from_radians(rad: f64) -> Self {
@@ -6,6 +6,7 @@
 //! - [`TrackingWheelOrientation`]: Vertical or horizontal wheel mounting with offset
 //! - [`shared_motor!`]: Macro for creating shared motor arrays
 //! - [`GroupErrors`]: Type alias for collections of port errors
+//! - `wrap_angle`: Crate-internal wrapping of radian angles to `[-π, π]`
 use crate::util::si::QLength;
 
 use vexide::smart::PortError;
@@ -121,3 +122,8 @@ macro_rules! shared_motor {
 /// }
 /// ```
 pub type GroupErrors = Vec<PortError>;
+
+/// Wraps an angle in radians to `[-π, π]`.
+pub(crate) fn wrap_angle(radians: f64) -> f64 {
+    libm::remainder(radians, core::f64::consts::TAU)
+}
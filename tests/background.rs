@@ -0,0 +1,149 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    BackgroundChassis, Clock, DriveError, ExitConditions, ManualClock, Pose, QAngle, QLength,
+};
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+#[test]
+fn test_wait_until_distance() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = BackgroundChassis::new(chassis(&robot, &clock));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let drive = chassis.shoot(QLength::from_meters(1.0));
+        assert!(!drive.is_done());
+        assert!(!chassis.is_idle());
+
+        drive.wait_until_distance(QLength::from_meters(0.5)).await;
+        let x = robot.pose().position().x;
+        assert!((0.48..0.6).contains(&x), "x = {x}");
+        assert!(!drive.is_done());
+
+        drive.wait_until_progress(0.9).await;
+        assert!(drive.progress() >= 0.9);
+        assert!(robot.pose().position().x > 0.85);
+
+        drive.await.unwrap();
+        assert!(chassis.is_idle());
+        let x = robot.pose().position().x;
+        assert!((x - 1.0).abs() < 0.05, "x = {x}");
+        assert!((chassis.pose().position().x - x).abs() < 0.02);
+    });
+}
+
+#[test]
+fn test_cancel_stops_drivetrain() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = BackgroundChassis::new(chassis(&robot, &clock));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let drive = chassis.shoot(QLength::from_meters(1.5));
+        drive.wait_until_progress(0.3).await;
+        drive.cancel();
+        let start = clock.now();
+        let result = drive.await;
+
+        assert!(matches!(result, Err(DriveError::Cancelled)), "{result:?}");
+        assert!(clock.now() - start <= Duration::from_millis(20));
+
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.linear_velocity().abs() < 0.05);
+        assert!(robot.pose().position().x < 1.0);
+    });
+}
+
+#[test]
+fn test_new_motion_preempts_running_one() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = BackgroundChassis::new(chassis(&robot, &clock));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let drive = chassis.shoot(QLength::from_meters(1.5));
+        drive.wait_until_distance(QLength::from_meters(0.3)).await;
+        let turn = chassis.turn(QAngle::from_degrees(90.0));
+
+        assert!(matches!(drive.await, Err(DriveError::Cancelled)));
+        turn.await.unwrap();
+
+        let heading = robot.pose().heading().as_degrees();
+        assert!((heading - 90.0).abs() < 2.0, "heading = {heading}");
+        assert!(robot.pose().position().x < 1.0);
+
+        // The chassis can be taken back and driven directly.
+        let mut chassis = chassis.into_inner().await;
+        chassis.turn(QAngle::from_degrees(0.0)).await.unwrap();
+        assert!(robot.pose().heading().as_degrees().abs() < 2.0);
+    });
+}
+
+#[test]
+fn test_failed_motion_stops_drivetrain() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = BackgroundChassis::new(chassis(&robot, &clock));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The chained drive leaves the drivetrain running, then the motion
+        // fails before anything stops it.
+        let exit = ExitConditions::linear().with_chain(0.3, 0.8);
+        let mut drive = chassis.run(async move |chassis| {
+            chassis.shoot_with(QLength::from_meters(0.8), exit).await?;
+            Err(DriveError::Timeout)
+        });
+        let result = (&mut drive).await;
+        assert!(matches!(result, Err(DriveError::Timeout)), "{result:?}");
+
+        // The result is only handed out once.
+        let again = poll_fn(|cx| Poll::Ready(Pin::new(&mut drive).poll(cx))).await;
+        assert!(matches!(again, Poll::Ready(Err(DriveError::Cancelled))));
+
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.linear_velocity().abs() < 0.05);
+        assert!(robot.pose().position().x < 1.0);
+    });
+}
+
+#[test]
+fn test_stop_after_chained_motion() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = BackgroundChassis::new(chassis(&robot, &clock));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let exit = ExitConditions::linear().with_chain(0.3, 0.8);
+        let drive = chassis
+            .run(async move |chassis| chassis.shoot_with(QLength::from_meters(0.8), exit).await);
+        assert!(drive.await.unwrap().is_chained());
+        assert!(chassis.is_idle());
+
+        // The chained motion left the drivetrain running.
+        clock.sleep(Duration::from_millis(100)).await;
+        assert!(robot.linear_velocity() > 0.3);
+
+        chassis.stop().await;
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.linear_velocity().abs() < 0.05);
+    });
+}
@@ -0,0 +1,117 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    Boomerang, DriveDirection, ExitConditions, ExitReason, ManualClock, MoveParams, Pose, QAngle,
+    Vec2,
+};
+
+fn heading_error(robot: &SimRobot, target: f64) -> f64 {
+    let error = (robot.pose().heading().as_degrees() - target).rem_euclid(360.0);
+    error.min(360.0 - error)
+}
+
+#[test]
+fn test_carrot_leads_target() {
+    let target = Pose::new(Vec2::new(1.0, 1.0), QAngle::from_degrees(90.0));
+    let controller = Boomerang::new(target, MoveParams::new().with_lead(0.5));
+
+    // The carrot trails the target along its heading by lead * distance.
+    let carrot = controller.carrot(Pose::new(Vec2::new(1.0, -1.0), QAngle::default()));
+    assert!((carrot.x - 1.0).abs() < 1e-9 && (carrot.y - 0.0).abs() < 1e-9);
+    let carrot = controller.carrot(target);
+    assert!(carrot.distance(target.position()) < 1e-9);
+
+    // Backwards, the carrot sits in front of the target.
+    let backwards = Boomerang::new(
+        target,
+        MoveParams::new().with_lead(0.5).with_forwards(false),
+    );
+    let carrot = backwards.carrot(Pose::new(Vec2::new(1.0, 3.0), QAngle::default()));
+    assert!((carrot.y - 2.0).abs() < 1e-9);
+    assert!((backwards.travel_heading().as_degrees() - 270.0).abs() < 1e-9);
+
+    // Left to choose, the robot backs in when it starts in front of the target.
+    let auto = Boomerang::new(target, MoveParams::new());
+    let ahead = Pose::new(Vec2::new(1.0, 3.0), QAngle::default());
+    let behind = Pose::new(Vec2::new(0.0, -1.0), QAngle::default());
+    assert_eq!(
+        auto.facing(ahead).params().direction,
+        DriveDirection::Backwards
+    );
+    assert_eq!(
+        auto.facing(behind).params().direction,
+        DriveDirection::Forwards
+    );
+}
+
+#[test]
+fn test_move_to_pose_arrives_at_heading() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let target = Pose::new(Vec2::new(1.2, 0.6), QAngle::from_degrees(90.0));
+        let reason = chassis
+            .move_to_pose(target, MoveParams::new())
+            .await
+            .unwrap();
+
+        assert!(!reason.is_timeout(), "{reason:?}");
+        let error = robot.pose().position().distance(target.position());
+        assert!(error < 0.05, "position error = {error}");
+        let heading = heading_error(&robot, 90.0);
+        assert!(heading < 3.0, "heading error = {heading}");
+    });
+}
+
+#[test]
+fn test_move_to_pose_backwards() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let target = Pose::new(Vec2::new(-1.0, -0.5), QAngle::from_degrees(30.0));
+        let params = MoveParams::new().with_forwards(false).with_max_speed(0.6);
+        chassis.move_to_pose(target, params).await.unwrap();
+
+        let error = robot.pose().position().distance(target.position());
+        assert!(error < 0.05, "position error = {error}");
+        let heading = heading_error(&robot, 30.0);
+        assert!(heading < 3.0, "heading error = {heading}");
+    });
+}
+
+#[test]
+fn test_chained_move_to_pose_keeps_min_speed() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let target = Pose::new(Vec2::new(1.0, 0.3), QAngle::from_degrees(0.0));
+        let params = MoveParams::new().with_min_speed(0.5);
+        let exit = ExitConditions::linear().with_chain(0.1, 0.5);
+        let reason = chassis
+            .move_to_pose_with(target, params, exit)
+            .await
+            .unwrap();
+
+        assert_eq!(reason, ExitReason::Chained);
+        assert!(robot.pose().position().distance(target.position()) <= 0.11);
+        let v = robot.linear_velocity();
+        assert!(v > 0.4, "v = {v}");
+    });
+}
@@ -0,0 +1,214 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{TRACK_WIDTH, robot};
+use kernelvex::odom::calibrate::CalibrationError;
+use kernelvex::sim::devices::{SimMotor, SimRobot};
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    Calibrator, Clock, DifferentialDrive, ExpoDrive, ManualClock, MotorGroup, OmniWheel, QLength,
+    Tank, TrackingWheel,
+};
+use std::time::Duration;
+
+/// A drivetrain configured with the wrong track width and wheel size.
+fn drivetrain(robot: &SimRobot) -> DifferentialDrive<SimMotor> {
+    DifferentialDrive::new(
+        MotorGroup::new([robot.left_motor()]),
+        MotorGroup::new([robot.right_motor()]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni4,
+        QLength::from_meters(0.25),
+        0.75,
+    )
+}
+
+fn offset(orientation: TrackingWheelOrientation) -> f64 {
+    match orientation {
+        TrackingWheelOrientation::Vertical(v) | TrackingWheelOrientation::Horizontal(v) => {
+            v.as_meters()
+        }
+    }
+}
+
+#[test]
+fn test_turns_measure_offsets_and_track_width() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    // Encoders follow the true geometry; the wheels are configured with guesses.
+    let true_mounts = [
+        TrackingWheelOrientation::Vertical(QLength::from_meters(-0.12)),
+        TrackingWheelOrientation::Vertical(QLength::from_meters(0.08)),
+        TrackingWheelOrientation::Horizontal(QLength::from_meters(-0.06)),
+    ];
+    let guesses = [
+        TrackingWheelOrientation::Vertical(QLength::from_meters(-0.1)),
+        TrackingWheelOrientation::Vertical(QLength::from_meters(0.1)),
+        TrackingWheelOrientation::Horizontal(QLength::from_meters(0.0)),
+    ];
+    let wheels: Vec<TrackingWheel<_>> = true_mounts
+        .iter()
+        .zip(guesses)
+        .map(|(mount, guess)| {
+            TrackingWheel::new(
+                robot.encoder(OmniWheel::Omni275, *mount, None),
+                OmniWheel::Omni275,
+                guess,
+                None,
+            )
+        })
+        .collect();
+    let refs: Vec<&TrackingWheel<_>> = wheels.iter().collect();
+
+    let result = vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+        Calibrator::with_clock(clock.clone())
+            .turns(&mut dt, &robot.imu(), &refs, 3)
+            .await
+            .unwrap()
+    });
+
+    assert!(result.turned.as_degrees() >= 3.0 * 360.0);
+    for (measured, truth) in result.wheels.iter().zip(true_mounts) {
+        assert!(
+            (offset(*measured) - offset(truth)).abs() < 1e-3,
+            "measured {measured:?}, expected {truth:?}"
+        );
+    }
+    assert!(matches!(
+        result.wheels[2],
+        TrackingWheelOrientation::Horizontal(_)
+    ));
+
+    // The drivetrain's configured wheel is 4.125" instead of 3.25", so its
+    // effective track width scales by the same ratio.
+    let expected = TRACK_WIDTH * 4.125 / 3.25;
+    assert!(
+        (result.track_width.as_meters() - expected).abs() < 1e-3,
+        "track width {}",
+        result.track_width.as_meters()
+    );
+}
+
+#[test]
+fn test_known_distance_measures_wheel_sizes() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    // A 2.75" wheel configured as 3.25".
+    let mount = TrackingWheelOrientation::Vertical(QLength::from_meters(0.0));
+    let wheel = TrackingWheel::new(
+        robot.encoder(OmniWheel::Omni275, mount, None),
+        OmniWheel::Omni325,
+        mount,
+        None,
+    );
+
+    let result = vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+        let calibrator = Calibrator::with_clock(clock.clone());
+
+        let run = calibrator.start_distance(&dt, &[&wheel]).await.unwrap();
+        dt.drive_tank(0.5, 0.5).await.unwrap();
+        clock.sleep(Duration::from_millis(600)).await;
+        dt.drive_tank(0.0, 0.0).await.unwrap();
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let travelled = robot.pose().position().x;
+        assert!(travelled > 0.3, "travelled {travelled}");
+        run.finish(&dt, &[&wheel], QLength::from_meters(travelled))
+            .await
+            .unwrap()
+    });
+
+    assert!((result.wheels[0].size().as_inches() - 2.75).abs() < 1e-3);
+    assert!((result.drive_wheel.size().as_inches() - 3.25).abs() < 1e-3);
+}
+
+#[test]
+fn test_distance_without_motion_fails() {
+    let robot = robot();
+    let dt = drivetrain(&robot);
+    let mount = TrackingWheelOrientation::Vertical(QLength::from_meters(0.0));
+    let wheel = robot.tracking_wheel(OmniWheel::Omni275, mount, None);
+
+    let result = vexide_async::block_on(async {
+        let run = Calibrator::new()
+            .start_distance(&dt, &[&wheel])
+            .await
+            .unwrap();
+        run.finish(&dt, &[&wheel], QLength::from_inches(48.0)).await
+    });
+
+    assert_eq!(result.unwrap_err(), CalibrationError::NoMotion);
+}
+
+#[test]
+fn test_turns_stop_when_imu_fails() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+    let mount = TrackingWheelOrientation::Vertical(QLength::from_meters(0.0));
+    let wheel = robot.tracking_wheel(OmniWheel::Omni275, mount, None);
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The IMU drops out partway through the first turn.
+        let imu = robot.imu();
+        let unplug_clock = clock.clone();
+        let unplug_imu = imu.clone();
+        let _unplug = vexide_async::task::spawn(async move {
+            unplug_clock.sleep(Duration::from_millis(300)).await;
+            unplug_imu.set_connected(false);
+        });
+
+        let result = Calibrator::with_clock(clock.clone())
+            .turns(&mut dt, &imu, &[&wheel], 3)
+            .await;
+        assert_eq!(result.unwrap_err(), CalibrationError::Imu);
+
+        // The drivetrain was stopped, so the robot coasts down instead of
+        // spinning at full speed.
+        clock.sleep(Duration::from_secs(1)).await;
+        let w = robot.angular_velocity();
+        assert!(w.abs() < 0.5, "still turning at {w} rad/s");
+    });
+}
+
+#[test]
+fn test_turns_give_up_when_imu_freezes() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+    let mount = TrackingWheelOrientation::Vertical(QLength::from_meters(0.0));
+    let wheel = robot.tracking_wheel(OmniWheel::Omni275, mount, None);
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The IMU still reads, but never sees the robot turn.
+        let imu = robot.imu();
+        imu.set_frozen(true);
+
+        let start = clock.now();
+        let result = Calibrator::with_clock(clock.clone())
+            .turns(&mut dt, &imu, &[&wheel], 3)
+            .await;
+        assert_eq!(result.unwrap_err(), CalibrationError::Stalled);
+        let elapsed = clock.now() - start;
+        assert!(
+            elapsed < Duration::from_secs(2),
+            "gave up after {elapsed:?}"
+        );
+
+        clock.sleep(Duration::from_secs(1)).await;
+        let w = robot.angular_velocity();
+        assert!(w.abs() < 0.5, "still turning at {w} rad/s");
+    });
+}
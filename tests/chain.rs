@@ -0,0 +1,94 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{Clock, ExitConditions, ExitReason, ExitTracker, ManualClock, Pose, QLength};
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn test_chain_takes_precedence() {
+    let exit = ExitConditions::linear().with_chain(0.05, 0.5);
+    let mut tracker = ExitTracker::new(exit, ms(0));
+
+    assert_eq!(tracker.chained(0.2), None);
+    assert_eq!(tracker.update(0.2, 0.5, ms(0)), None);
+    assert_eq!(
+        tracker.update(-0.04, 0.5, ms(10)),
+        Some(ExitReason::Chained)
+    );
+    assert!(ExitReason::Chained.is_chained());
+    assert_eq!(exit.exit_speed(), 0.5);
+    assert_eq!(ExitConditions::linear().exit_speed(), 0.0);
+}
+
+#[test]
+fn test_chained_drive_keeps_moving() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let chain = ExitConditions::linear().with_chain(0.05, 0.6);
+        let reason = chassis
+            .shoot_with(QLength::from_meters(1.0), chain)
+            .await
+            .unwrap();
+
+        assert_eq!(reason, ExitReason::Chained);
+        let x = robot.pose().position().x;
+        assert!((0.9..1.0).contains(&x), "x = {x}");
+        let v = robot.linear_velocity();
+        assert!(v > 0.4, "v = {v}");
+
+        // The next drive carries on from the current speed.
+        chassis.shoot(QLength::from_meters(0.5)).await.unwrap();
+        let x = robot.pose().position().x;
+        assert!((x - 1.5).abs() < 0.05, "x = {x}");
+        assert_eq!(chassis.get_pose().heading().as_degrees().round(), 0.0);
+    });
+}
+
+#[test]
+fn test_chaining_saves_time() {
+    let drive = async |chain: bool| {
+        let robot = SimRobot::new(config(), Pose::default());
+        let clock = ManualClock::new();
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let mut exit = ExitConditions::linear();
+        if chain {
+            exit = exit.with_chain(0.02, 0.8);
+        }
+        for _ in 0..3 {
+            chassis
+                .shoot_with(QLength::from_meters(0.6), exit)
+                .await
+                .unwrap();
+        }
+        chassis.shoot(QLength::from_meters(0.6)).await.unwrap();
+        (clock.now(), robot.pose().position().x)
+    };
+
+    vexide_async::block_on(async {
+        let (stopped, x_stopped) = drive(false).await;
+        let (chained, x_chained) = drive(true).await;
+
+        assert!((x_stopped - 2.4).abs() < 0.05, "x = {x_stopped}");
+        // Each chained drive ends up to its chain range short of its target.
+        assert!((x_chained - 2.4).abs() < 0.08, "x = {x_chained}");
+        assert!(
+            chained.as_secs_f64() < 0.8 * stopped.as_secs_f64(),
+            "chained {chained:?}, stopped {stopped:?}"
+        );
+    });
+}
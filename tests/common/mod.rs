@@ -0,0 +1,110 @@
+//! Simulated robot fixtures shared by the integration tests.
+
+// Each test binary uses a different subset of these.
+#![allow(dead_code)]
+
+use kernelvex::sim::devices::{SimEncoder, SimImu, SimMotor, SimRobot};
+use kernelvex::sim::plant::PlantConfig;
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    AngularPid, Clock, DifferentialDrive, ExpoDrive, FeedForward, Gyro, ManualClock, MotorGroup,
+    MotorOutput, OdomChassis, OmniWheel, Pid, Pose, QLength, TrackingRig, TrackingWheel,
+    TrapezoidalConstraints,
+};
+use vexide::smart::motor::Gearset;
+
+pub const TRACK_WIDTH: f64 = 0.3;
+
+pub fn config() -> PlantConfig {
+    PlantConfig::new(
+        QLength::from_meters(TRACK_WIDTH),
+        OmniWheel::Omni325,
+        0.75,
+        Gearset::Blue,
+    )
+}
+
+/// A simulated robot starting at the origin.
+pub fn robot() -> SimRobot {
+    SimRobot::new(config(), Pose::default())
+}
+
+pub fn drivetrain(robot: &SimRobot) -> DifferentialDrive<SimMotor> {
+    DifferentialDrive::new(
+        MotorGroup::new([robot.left_motor(), robot.left_motor()]),
+        MotorGroup::new([robot.right_motor(), robot.right_motor()]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_meters(TRACK_WIDTH),
+        0.75,
+    )
+}
+
+pub fn vertical(robot: &SimRobot, offset: f64) -> TrackingWheel<SimEncoder> {
+    robot.tracking_wheel(
+        OmniWheel::Omni275,
+        TrackingWheelOrientation::Vertical(QLength::from_meters(offset)),
+        None,
+    )
+}
+
+pub fn horizontal(robot: &SimRobot, offset: f64) -> TrackingWheel<SimEncoder> {
+    robot.tracking_wheel(
+        OmniWheel::Omni275,
+        TrackingWheelOrientation::Horizontal(QLength::from_meters(offset)),
+        None,
+    )
+}
+
+/// A three-wheel rig with an IMU, starting at the robot's pose.
+pub fn rig(robot: &SimRobot, clock: &ManualClock) -> TrackingRig {
+    rig_at(robot, clock, robot.pose())
+}
+
+/// A three-wheel rig with an IMU that believes it starts at `origin`.
+pub fn rig_at(robot: &SimRobot, clock: &ManualClock, origin: Pose) -> TrackingRig {
+    TrackingRig::with_clock(
+        origin,
+        [horizontal(robot, -0.05)],
+        [vertical(robot, -0.1), vertical(robot, 0.1)],
+        Some(robot.imu()),
+        clock.clone(),
+    )
+}
+
+/// Applies the gains tuned for the simulated plant: feedforward from the
+/// motor curve, light PID on top.
+pub fn tuned<M: MotorOutput + Send, G: Gyro, C: Clock>(
+    chassis: OdomChassis<M, G, C>,
+) -> OdomChassis<M, G, C> {
+    chassis
+        .with_linear_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+        .with_angular_pid(AngularPid::new().set_gains(10.0, 0.0, 1.5))
+        .with_ff(FeedForward::new().set_gains(0.0, 12.0 / config().max_velocity(), 1.0))
+        .with_constraints(TrapezoidalConstraints::new().set_gains(1.0, 2.0))
+}
+
+/// A tuned chassis on two vertical tracking wheels either side of center.
+pub fn chassis(
+    robot: &SimRobot,
+    clock: &ManualClock,
+) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    chassis_with(robot, clock, [-0.1, 0.1])
+}
+
+/// A tuned chassis on vertical tracking wheels at the given offsets.
+pub fn chassis_with<const N: usize>(
+    robot: &SimRobot,
+    clock: &ManualClock,
+    offsets: [f64; N],
+) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        offsets.map(|offset| vertical(robot, offset)),
+        Some(robot.imu()),
+        clock.clone(),
+    );
+
+    tuned(OdomChassis::new(drivetrain(robot), robot.imu(), Some(rig)).with_clock(clock.clone()))
+}
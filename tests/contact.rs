@@ -0,0 +1,159 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, drivetrain, rig, tuned};
+use kernelvex::sim::devices::{SimMotor, SimRobot};
+use kernelvex::{
+    Clock, ContactKind, DifferentialDrive, Drivetrain, ManualClock, OdomChassis, Pose, QLength,
+    Tank, TrackingRig, Vec2,
+};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Drives with tank powers for a while, reporting the drive velocity to the
+/// rig every 10 ms.
+async fn drive(
+    dt: &mut DifferentialDrive<SimMotor>,
+    rig: &TrackingRig,
+    clock: &ManualClock,
+    (left, right): (f64, f64),
+    time: Duration,
+) {
+    dt.drive_tank(left, right).await.unwrap();
+    let end = clock.now() + time;
+    while clock.now() < end {
+        rig.observe_drive(
+            dt.linear_velocity().await.unwrap(),
+            dt.angular_velocity().await.unwrap(),
+        );
+        clock.sleep(Duration::from_millis(10)).await;
+    }
+}
+
+#[test]
+fn test_normal_driving_raises_nothing() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let second = Duration::from_secs(1);
+        drive(&mut dt, &rig, &clock, (0.8, 0.8), second).await;
+        drive(&mut dt, &rig, &clock, (0.3, 0.9), second).await;
+        drive(&mut dt, &rig, &clock, (-0.6, 0.6), second).await;
+        drive(&mut dt, &rig, &clock, (0.0, 0.0), second).await;
+
+        assert!(!rig.contact().any());
+        assert!(rig.take_contact_events().is_empty());
+    });
+}
+
+#[test]
+fn test_wheelspin_against_obstacle() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        drive(
+            &mut dt,
+            &rig,
+            &clock,
+            (0.8, 0.8),
+            Duration::from_millis(500),
+        )
+        .await;
+        robot.set_held(true);
+        let wall = robot.pose();
+        drive(
+            &mut dt,
+            &rig,
+            &clock,
+            (0.8, 0.8),
+            Duration::from_millis(300),
+        )
+        .await;
+
+        let contact = rig.contact();
+        assert!(contact.slipping && !contact.pushed);
+        let kinds: Vec<ContactKind> = rig.take_contact_events().iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, [ContactKind::Collision, ContactKind::Slip]);
+
+        // The tracking wheels did not spin with the drive.
+        assert!(rig.pose().distance(wall).as_meters() < 0.02);
+
+        robot.set_held(false);
+        drive(&mut dt, &rig, &clock, (0.0, 0.0), Duration::from_secs(1)).await;
+        assert!(!rig.contact().any());
+    });
+}
+
+#[test]
+fn test_push_is_flagged() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+        let still = (0.0, 0.0);
+
+        // Shoved sideways: a differential drive cannot do that by itself.
+        robot.set_push(Vec2::new(0.0, 0.4));
+        drive(&mut dt, &rig, &clock, still, Duration::from_millis(300)).await;
+        assert!(rig.contact().pushed);
+        assert!(!rig.contact().slipping);
+        robot.set_push(Vec2::new(0.0, 0.0));
+        drive(&mut dt, &rig, &clock, still, Duration::from_millis(300)).await;
+        assert!(!rig.contact().pushed);
+
+        // Shoved forward while the drive is still.
+        robot.set_push(Vec2::new(0.4, 0.0));
+        drive(&mut dt, &rig, &clock, still, Duration::from_millis(300)).await;
+        assert!(rig.contact().pushed);
+
+        // Starting and stopping a shove instantly also reads as an impact.
+        let pushes: Vec<_> = rig
+            .take_contact_events()
+            .into_iter()
+            .filter(|e| e.kind == ContactKind::Push)
+            .collect();
+        assert_eq!(pushes.len(), 2);
+        assert!(pushes[0].pose.position().y > 0.0);
+
+        // The tracking wheels followed the push.
+        assert!(rig.pose().distance(robot.pose()).as_meters() < 0.01);
+    });
+}
+
+#[test]
+fn test_chassis_reports_drive_velocity() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let rig = Rc::new(rig(&robot, &clock));
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), None)
+                .with_clock(clock.clone())
+                .with_pose_source(Rc::clone(&rig)),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        robot.set_held(true);
+        chassis.shoot(QLength::from_meters(0.5)).await.unwrap();
+
+        assert!(rig.contact().slipping);
+        let events = rig.take_contact_events();
+        assert!(events.iter().any(|e| e.kind == ContactKind::Slip));
+    });
+}
@@ -0,0 +1,438 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, drivetrain, horizontal, tuned, vertical};
+use kernelvex::odom::dist::Wall;
+use kernelvex::sim::devices::{SensorNoise, SimEncoder, SimImu, SimRobot};
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    Clock, DifferentialDrive, DriveError, Ekf, EkfConfig, EkfRig, HeadingSource, ManualClock,
+    Measurement, MotorOutput, OdomChassis, OmniWheel, Pose, PoseSource, QAngle, QLength, Tank,
+    TrackingWheel, TravelSource, Vec2, WheelHealth,
+};
+use nalgebra::Matrix3;
+use std::time::Duration;
+
+fn identity_covariance(std_dev: f64) -> Matrix3<f64> {
+    Matrix3::identity() * (std_dev * std_dev)
+}
+
+fn ekf_rig(robot: &SimRobot, clock: &ManualClock, config: EkfConfig) -> EkfRig {
+    EkfRig::with_clock(
+        robot.pose(),
+        [horizontal(robot, -0.05)],
+        [vertical(robot, -0.1), vertical(robot, 0.1)],
+        None::<&DifferentialDrive>,
+        Some(robot.imu()),
+        config,
+        clock.clone(),
+    )
+}
+
+// ============================================================================
+// Filter Tests
+// ============================================================================
+
+#[test]
+fn test_predict_moves_along_heading() {
+    let start = Pose::new(Vec2::new(1.0, 1.0), QAngle::from_degrees(90.0));
+    let mut ekf = Ekf::new(start, Matrix3::zeros());
+
+    ekf.predict(
+        Vec2::new(0.5, 0.0),
+        QAngle::from_radians(0.0),
+        Matrix3::zeros(),
+    );
+
+    let pose = ekf.pose();
+    assert!((pose.position().x - 1.0).abs() < 1e-12);
+    assert!((pose.position().y - 1.5).abs() < 1e-12);
+    assert!((pose.heading().as_degrees() - 90.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_predict_heading_error_spreads_into_position() {
+    let mut ekf = Ekf::new(Pose::default(), Matrix3::zeros());
+    let noise = Matrix3::from_diagonal(&nalgebra::Vector3::new(0.0, 0.0, 0.01));
+
+    ekf.predict(Vec2::new(1.0, 0.0), QAngle::from_radians(0.0), noise);
+    ekf.predict(
+        Vec2::new(1.0, 0.0),
+        QAngle::from_radians(0.0),
+        Matrix3::zeros(),
+    );
+
+    let p = ekf.covariance();
+    // Driving along x with an uncertain heading spreads uncertainty across y only.
+    assert!(p[(0, 0)].abs() < 1e-12);
+    assert!(p[(1, 1)] > 0.01);
+    assert!((p[(2, 2)] - 0.01).abs() < 1e-12);
+}
+
+#[test]
+fn test_position_correction_weights_by_covariance() {
+    let mut ekf = Ekf::new(Pose::default(), identity_covariance(0.1));
+
+    // Equal uncertainty in estimate and measurement: meet in the middle.
+    assert!(ekf.correct(&Measurement::Position {
+        position: Vec2::new(0.2, 0.0),
+        std_dev: QLength::from_meters(0.1),
+    }));
+
+    assert!((ekf.pose().position().x - 0.1).abs() < 1e-9);
+    assert!((ekf.covariance()[(0, 0)] - 0.005).abs() < 1e-9);
+    // Heading is uncorrelated with position here, so it is untouched.
+    assert!((ekf.covariance()[(2, 2)] - 0.01).abs() < 1e-12);
+}
+
+#[test]
+fn test_heading_correction_wraps() {
+    let start = Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(179.0));
+    let mut ekf = Ekf::new(start, identity_covariance(0.1));
+
+    assert!(ekf.correct(&Measurement::Heading {
+        heading: QAngle::from_degrees(-179.0),
+        std_dev: QAngle::from_radians(0.1),
+    }));
+
+    // The short way from 179° to -179° passes through 180°.
+    assert!((ekf.pose().heading().as_degrees() - 180.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_pose_correction_uses_all_axes() {
+    let mut ekf = Ekf::new(Pose::default(), identity_covariance(1.0));
+    let gps = Pose::new(Vec2::new(0.5, -0.5), QAngle::from_degrees(10.0));
+
+    assert!(ekf.correct(&Measurement::Pose {
+        pose: gps,
+        position_std: QLength::from_meters(1e-3),
+        heading_std: QAngle::from_radians(1e-3),
+    }));
+
+    let pose = ekf.pose();
+    assert!((pose.position().x - 0.5).abs() < 1e-5);
+    assert!((pose.position().y + 0.5).abs() < 1e-5);
+    assert!((pose.heading().as_degrees() - 10.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_outlier_rejected() {
+    let mut ekf = Ekf::new(Pose::default(), identity_covariance(0.01)).with_outlier_gate(9.0);
+
+    // A reading 1 m away when the estimate is certain to within centimetres.
+    let accepted = ekf.correct(&Measurement::Position {
+        position: Vec2::new(1.0, 0.0),
+        std_dev: QLength::from_centimeters(1.0),
+    });
+
+    assert!(!accepted);
+    assert_eq!(ekf.pose().position().x, 0.0);
+    assert_eq!(ekf.covariance(), identity_covariance(0.01));
+}
+
+#[test]
+fn test_wall_distance_corrects_facing_axis() {
+    let wall = Wall::new(Vec2::new(1.8, -1.8), Vec2::new(1.8, 1.8));
+    let mut ekf = Ekf::new(Pose::default(), identity_covariance(0.1));
+
+    // Sensor 10 cm ahead of centre, facing forward. The robot is really at
+    // x = 0.2, so the beam reads 1.5 m instead of the expected 1.7 m.
+    let mount = Pose::new(Vec2::new(0.1, 0.0), QAngle::from_degrees(0.0));
+    assert!(ekf.correct(&Measurement::WallDistance {
+        mount,
+        wall,
+        distance: QLength::from_meters(1.5),
+        std_dev: QLength::from_centimeters(0.1),
+    }));
+
+    let pose = ekf.pose();
+    assert!(
+        (pose.position().x - 0.2).abs() < 1e-3,
+        "x = {}",
+        pose.position().x
+    );
+    assert!(pose.position().y.abs() < 1e-9);
+}
+
+#[test]
+fn test_wall_distance_corrects_heading_when_angled() {
+    let wall = Wall::new(Vec2::new(1.0, -2.0), Vec2::new(1.0, 2.0));
+    let mut ekf = Ekf::new(Pose::default(), identity_covariance(1.0));
+    let truth = QAngle::from_degrees(20.0);
+    let mount = Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(0.0));
+
+    // Position is known well; only the heading explains a longer beam. The
+    // beam carries no first-order heading information when perpendicular to
+    // the wall, so start from a nearby heading.
+    let mut ekf_heading_only = Ekf::new(
+        Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(15.0)),
+        Matrix3::from_diagonal(&nalgebra::Vector3::new(1e-8, 1e-8, 1.0)),
+    );
+    for _ in 0..10 {
+        assert!(ekf_heading_only.correct(&Measurement::WallDistance {
+            mount,
+            wall,
+            distance: QLength::from_meters(1.0 / truth.cos()),
+            std_dev: QLength::from_centimeters(1.0),
+        }));
+    }
+    assert!(
+        (ekf_heading_only.pose().heading().as_degrees() - 20.0).abs() < 0.5,
+        "heading = {}",
+        ekf_heading_only.pose().heading().as_degrees()
+    );
+
+    // A beam that points away from the wall cannot be used.
+    let backwards = Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(180.0));
+    assert!(!ekf.correct(&Measurement::WallDistance {
+        mount: backwards,
+        wall,
+        distance: QLength::from_meters(1.0),
+        std_dev: QLength::from_centimeters(0.1),
+    }));
+}
+
+#[test]
+fn test_wall_ray_cast() {
+    let wall = Wall::new(Vec2::new(0.0, 1.0), Vec2::new(2.0, 1.0));
+
+    let hit = wall
+        .ray_cast(Vec2::new(1.0, 0.0), QAngle::from_degrees(90.0))
+        .unwrap();
+    assert!((hit.as_meters() - 1.0).abs() < 1e-12);
+
+    let diagonal = wall
+        .ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(45.0))
+        .unwrap();
+    assert!((diagonal.as_meters() - std::f64::consts::SQRT_2).abs() < 1e-12);
+
+    // Past the end of the segment, parallel, and facing away.
+    assert!(
+        wall.ray_cast(Vec2::new(3.0, 0.0), QAngle::from_degrees(90.0))
+            .is_none()
+    );
+    assert!(
+        wall.ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(0.0))
+            .is_none()
+    );
+    assert!(
+        wall.ray_cast(Vec2::new(1.0, 0.0), QAngle::from_degrees(-90.0))
+            .is_none()
+    );
+}
+
+// ============================================================================
+// Rig Tests
+// ============================================================================
+
+#[test]
+fn test_ekf_rig_follows_simulated_robot() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = ekf_rig(&robot, &clock, EkfConfig::new());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(10.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(800)).await;
+        left.set_voltage(0.0).unwrap();
+        right.set_voltage(0.0).unwrap();
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let truth = robot.pose();
+        let estimate = rig.pose();
+        assert!(truth.distance(estimate).as_meters() < 0.02);
+        assert!(
+            (truth.heading() - estimate.heading())
+                .remainder(QAngle::TAU)
+                .as_degrees()
+                .abs()
+                < 1.0
+        );
+
+        // Uncertainty grew while driving.
+        let p = rig.covariance();
+        assert!(p[(0, 0)] + p[(1, 1)] > 2.0 * 0.01 * 0.01);
+    });
+}
+
+#[test]
+fn test_ekf_rig_uses_drive_encoders_without_tracking_wheels() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut dt = drivetrain(&robot);
+
+    vexide_async::block_on(async {
+        // No tracking wheels, so their encoder type has to be named.
+        let rig = EkfRig::with_clock::<0, 0, SimEncoder, _, _, _>(
+            robot.pose(),
+            [],
+            [],
+            Some(&dt),
+            None::<SimImu>,
+            EkfConfig::new(),
+            clock.clone(),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        dt.drive_tank(8.0 / 12.0, 4.0 / 12.0).await.unwrap();
+        clock.sleep(Duration::from_millis(600)).await;
+
+        let truth = robot.pose();
+        let estimate = rig.pose();
+        assert!(truth.distance(estimate).as_meters() < 0.02);
+        assert!(estimate.heading().as_degrees() < -1.0);
+        assert_eq!(rig.heading_source(), HeadingSource::Wheels);
+        assert_eq!(rig.travel_source(), TravelSource::Drive);
+    });
+}
+
+#[test]
+fn test_chassis_fails_on_ekf_wheel_dropout() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let orientation = TrackingWheelOrientation::Vertical(QLength::from_meters(0.0));
+    let encoder = robot.encoder(OmniWheel::Omni275, orientation, None);
+    let wheel = TrackingWheel::new(encoder.clone(), OmniWheel::Omni275, orientation, None);
+
+    vexide_async::block_on(async {
+        let rig = EkfRig::with_clock(
+            robot.pose(),
+            [],
+            [wheel],
+            None::<&DifferentialDrive>,
+            Some(robot.imu()),
+            EkfConfig::new(),
+            clock.clone(),
+        );
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), None)
+                .with_clock(clock.clone())
+                .with_pose_source(rig),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The only vertical wheel drops out, and there are no drive encoders
+        // to fall back on.
+        encoder.set_connected(false);
+        let result = chassis.shoot(QLength::from_meters(0.5)).await;
+        let Err(DriveError::TrackingWheel(status)) = result else {
+            panic!("{result:?}");
+        };
+        assert_eq!(status[0].health, WheelHealth::Disconnected);
+
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.linear_velocity().abs() < 0.01);
+    });
+}
+
+#[test]
+fn test_chassis_fails_on_ekf_imu_dropout() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        // A single vertical wheel cannot measure heading without the IMU.
+        let rig = EkfRig::with_clock(
+            robot.pose(),
+            [],
+            [vertical(&robot, 0.0)],
+            None::<&DifferentialDrive>,
+            Some(robot.imu()),
+            EkfConfig::new(),
+            clock.clone(),
+        );
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), None)
+                .with_clock(clock.clone())
+                .with_pose_source(rig),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        robot.imu().set_connected(false);
+        let result = chassis.turn(QAngle::from_degrees(90.0)).await;
+        assert!(matches!(result, Err(DriveError::Imu(_))), "{result:?}");
+    });
+}
+
+#[test]
+fn test_ekf_rig_corrections_remove_slip_drift() {
+    let noise = SensorNoise {
+        slip: 0.2,
+        ..Default::default()
+    };
+
+    let run = |corrected: bool| {
+        let robot = SimRobot::new(config(), Pose::default())
+            .with_noise(noise)
+            .with_seed(3);
+        let clock = ManualClock::new();
+        let mut left = robot.left_motor();
+        let mut right = robot.right_motor();
+
+        vexide_async::block_on(async {
+            // Slipping wheels are much less trustworthy than the default assumes.
+            let rig = ekf_rig(&robot, &clock, EkfConfig::new().with_wheel_noise(0.1));
+            let _physics = robot.spawn_with_clock(clock.clone());
+
+            left.set_voltage(9.0).unwrap();
+            right.set_voltage(7.0).unwrap();
+            for _ in 0..10 {
+                clock.sleep(Duration::from_millis(100)).await;
+                if corrected {
+                    rig.correct(Measurement::Position {
+                        position: robot.pose().position(),
+                        std_dev: QLength::from_centimeters(1.0),
+                    });
+                }
+            }
+
+            robot.pose().distance(rig.pose()).as_meters()
+        })
+    };
+
+    let drifted = run(false);
+    let corrected = run(true);
+    assert!(drifted > 0.05, "slip should cause drift, got {drifted}");
+    assert!(corrected < 0.02, "corrected error {corrected}");
+}
+
+#[test]
+fn test_chassis_accepts_ekf_pose_source() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let rig = ekf_rig(&robot, &clock, EkfConfig::new());
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), None)
+                .with_clock(clock.clone())
+                .with_pose_source(rig),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.shoot(QLength::from_meters(0.5)).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    assert!((robot.pose().position().x - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_pose_source_is_object_safe() {
+    fn heading_of(source: &dyn PoseSource) -> f64 {
+        source.pose().heading().as_degrees()
+    }
+
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let rig = ekf_rig(&robot, &clock, EkfConfig::new());
+    assert_eq!(heading_of(&rig), 0.0);
+}
@@ -0,0 +1,203 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{TRACK_WIDTH, chassis_with, config, rig, tuned};
+use kernelvex::sim::devices::{SimImu, SimMotor, SimRobot};
+use kernelvex::{
+    AngularPid, Clock, DifferentialDrive, DriveError, ExitConditions, ExitReason, ExpoDrive,
+    ManualClock, MotorGroup, MotorOutput, OdomChassis, OmniWheel, Pose, QAngle, QLength,
+    TrapezoidalConstraints,
+};
+use std::time::Duration;
+use vexide::smart::PortError;
+use vexide::smart::motor::BrakeMode;
+
+/// A chassis whose only heading sensor is the IMU: its rig has a single
+/// vertical wheel.
+fn chassis(robot: &SimRobot, clock: &ManualClock) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    chassis_with(robot, clock, [0.0])
+}
+
+/// A simulated motor whose commands fail, without driving, while it is
+/// unplugged.
+struct PluggableMotor {
+    motor: SimMotor,
+    plugged: bool,
+}
+
+impl PluggableMotor {
+    fn check(&self) -> Result<(), PortError> {
+        if self.plugged {
+            Ok(())
+        } else {
+            Err(PortError::Disconnected { port: 1 })
+        }
+    }
+}
+
+impl MotorOutput for PluggableMotor {
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+        self.check()?;
+        self.motor.set_voltage(volts)
+    }
+
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError> {
+        self.check()?;
+        self.motor.brake(mode)
+    }
+
+    fn velocity(&self) -> Result<f64, PortError> {
+        self.motor.velocity()
+    }
+
+    fn position(&self) -> Result<QAngle, PortError> {
+        self.motor.position()
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        self.motor.set_position(position)
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.motor.reset_position()
+    }
+}
+
+#[test]
+fn test_invalid_constraints_are_reported() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    assert!(TrapezoidalConstraints::new().set_gains(1.0, 2.0).is_valid());
+    assert!(!TrapezoidalConstraints::new().is_valid());
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        for constraints in [
+            TrapezoidalConstraints::new(),
+            TrapezoidalConstraints::new().set_gains(1.0, 0.0),
+            TrapezoidalConstraints::new().set_gains(-1.0, 2.0),
+            TrapezoidalConstraints::new().set_gains(f64::NAN, 2.0),
+        ] {
+            let mut chassis = chassis(&robot, &clock).with_constraints(constraints);
+            let result = chassis.shoot(QLength::from_meters(0.5)).await;
+            assert!(
+                matches!(result, Err(DriveError::InvalidConstraints)),
+                "{constraints:?}: {result:?}"
+            );
+        }
+        assert_eq!(robot.pose().position().x, 0.0);
+    });
+}
+
+#[test]
+fn test_imu_loss_fails_turn() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        robot.imu().set_connected(false);
+        let start = clock.now();
+        let result = chassis.turn(QAngle::from_degrees(90.0)).await;
+        let elapsed = clock.now() - start;
+
+        assert!(matches!(result, Err(DriveError::Imu(_))), "{result:?}");
+        assert!(elapsed < Duration::from_millis(200), "{elapsed:?}");
+
+        // The drivetrain is stopped before the error is returned.
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.angular_velocity().abs() < 0.05);
+    });
+}
+
+#[test]
+fn test_brief_imu_dropout_is_tolerated() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // Reconnects 50 ms into the turn, inside the grace period.
+        let imu = robot.imu();
+        imu.set_connected(false);
+        let reconnect_clock = clock.clone();
+        let _reconnect = vexide_async::task::spawn(async move {
+            reconnect_clock.sleep(Duration::from_millis(50)).await;
+            imu.set_connected(true);
+        });
+
+        let reason = chassis.turn(QAngle::from_degrees(90.0)).await.unwrap();
+        assert!(!reason.is_timeout());
+    });
+}
+
+#[test]
+fn test_timeout_can_become_error() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    assert!(matches!(
+        ExitReason::SmallError.ok_or_timeout(),
+        Ok(ExitReason::SmallError)
+    ));
+
+    vexide_async::block_on(async {
+        // Too weak to turn the robot against motor friction.
+        let mut chassis =
+            chassis(&robot, &clock).with_angular_pid(AngularPid::new().set_gains(0.01, 0.0, 0.0));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let exit = ExitConditions::new().with_timeout(Duration::from_millis(300));
+        let result = chassis
+            .turn_with(QAngle::from_degrees(90.0), exit)
+            .await
+            .and_then(ExitReason::ok_or_timeout);
+        assert!(matches!(result, Err(DriveError::Timeout)), "{result:?}");
+    });
+}
+
+#[test]
+fn test_motor_failure_stops_drivetrain() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        // One of the right motors is unplugged; the healthy left motors
+        // still take the commands written before the failure is reported.
+        let motor = |motor, plugged| PluggableMotor { motor, plugged };
+        let dt = DifferentialDrive::new(
+            MotorGroup::new([
+                motor(robot.left_motor(), true),
+                motor(robot.left_motor(), true),
+            ]),
+            MotorGroup::new([
+                motor(robot.right_motor(), true),
+                motor(robot.right_motor(), false),
+            ]),
+            ExpoDrive::new(0.0, 1.0, None),
+            OmniWheel::Omni325,
+            QLength::from_meters(TRACK_WIDTH),
+            0.75,
+        );
+        let mut chassis = tuned(
+            OdomChassis::new(dt, robot.imu(), Some(rig(&robot, &clock))).with_clock(clock.clone()),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let result = chassis.turn(QAngle::from_degrees(90.0)).await;
+        assert!(matches!(result, Err(DriveError::Motor(_))), "{result:?}");
+
+        // The healthy motors are stopped before the error is returned.
+        clock.sleep(Duration::from_secs(1)).await;
+        assert!(robot.linear_velocity().abs() < 0.01);
+        assert!(robot.angular_velocity().abs() < 0.05);
+    });
+}
@@ -0,0 +1,160 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    AngularPid, Clock, ExitConditions, ExitReason, ExitTracker, ManualClock, Pose, QAngle, QLength,
+    Vec2,
+};
+use std::time::Duration;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn test_exit_tracker_conditions() {
+    let exit = ExitConditions::new()
+        .with_small_error(0.01, ms(100))
+        .with_large_error(0.05, ms(300))
+        .with_timeout(ms(1000));
+
+    // The small error timer restarts when the error leaves the tolerance.
+    let mut tracker = ExitTracker::new(exit, ms(0));
+    assert_eq!(tracker.update(0.005, 1.0, ms(0)), None);
+    assert_eq!(tracker.update(0.02, 1.0, ms(80)), None);
+    assert_eq!(tracker.update(0.005, 1.0, ms(120)), None);
+    assert_eq!(
+        tracker.update(-0.005, 1.0, ms(220)),
+        Some(ExitReason::SmallError)
+    );
+
+    // Hovering just outside the small tolerance ends on the large one.
+    let mut tracker = ExitTracker::new(exit, ms(0));
+    for t in (0..300).step_by(10) {
+        assert_eq!(tracker.update(0.03, 1.0, ms(t)), None);
+    }
+    assert_eq!(
+        tracker.update(0.03, 1.0, ms(300)),
+        Some(ExitReason::LargeError)
+    );
+
+    // Far from the target, only the timeout ends the motion.
+    let mut tracker = ExitTracker::new(exit, ms(500));
+    assert_eq!(tracker.update(1.0, 1.0, ms(1400)), None);
+    assert_eq!(tracker.timed_out(ms(1499)), None);
+    let reason = tracker.update(1.0, 1.0, ms(1500)).unwrap();
+    assert!(reason.is_timeout());
+
+    // A stalled robot ends on the velocity condition.
+    let mut tracker = ExitTracker::new(ExitConditions::new().with_velocity(0.01, ms(50)), ms(0));
+    assert_eq!(tracker.update(1.0, 0.001, ms(0)), None);
+    assert_eq!(
+        tracker.update(1.0, 0.001, ms(50)),
+        Some(ExitReason::Velocity)
+    );
+
+    // With a delay, a robot at rest is given time to start moving ...
+    let exit = ExitConditions::new()
+        .with_velocity(0.01, ms(50))
+        .with_velocity_delay(ms(500));
+    let mut tracker = ExitTracker::new(exit, ms(0));
+    for t in (0..500).step_by(10) {
+        assert_eq!(tracker.update(1.0, 0.001, ms(t)), None);
+    }
+    assert_eq!(tracker.update(1.0, 0.001, ms(500)), None);
+    assert_eq!(
+        tracker.update(1.0, 0.001, ms(550)),
+        Some(ExitReason::Velocity)
+    );
+
+    // ... but once it has moved, slowing down ends the motion straight away.
+    let mut tracker = ExitTracker::new(exit, ms(0));
+    assert_eq!(tracker.update(1.0, 0.5, ms(0)), None);
+    assert_eq!(tracker.update(1.0, 0.001, ms(100)), None);
+    assert_eq!(
+        tracker.update(1.0, 0.001, ms(150)),
+        Some(ExitReason::Velocity)
+    );
+
+    // Without conditions nothing ends the motion.
+    let mut tracker = ExitTracker::new(ExitConditions::new(), ms(0));
+    assert_eq!(tracker.update(0.0, 0.0, ms(1_000_000)), None);
+}
+
+#[test]
+fn test_shoot_settles_on_distance() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let reason = chassis.shoot(QLength::from_meters(0.8)).await.unwrap();
+        assert!(
+            matches!(reason, ExitReason::SmallError | ExitReason::LargeError),
+            "{reason:?}"
+        );
+
+        let x = robot.pose().position().x;
+        assert!((x - 0.8).abs() < 0.05, "x = {x}");
+        assert!(robot.linear_velocity().abs() < 0.2);
+    });
+}
+
+#[test]
+fn test_turn_timeout_is_reported() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    // Only settling on the target or the timeout end the turn.
+    let exit = ExitConditions::new()
+        .with_small_error(1.0f64.to_radians(), ms(100))
+        .with_timeout(ms(500));
+
+    vexide_async::block_on(async {
+        // Too weak to turn the robot against motor friction.
+        let mut chassis = chassis(&robot, &clock)
+            .with_angular_pid(AngularPid::new().set_gains(0.01, 0.0, 0.0))
+            .with_angular_exit(exit);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let start = clock.now();
+        let reason = chassis.turn(QAngle::from_degrees(90.0)).await.unwrap();
+        let elapsed = clock.now() - start;
+
+        assert_eq!(reason, ExitReason::Timeout);
+        assert!(elapsed >= ms(500) && elapsed < ms(520), "{elapsed:?}");
+
+        // The robot does not drive towards a pose it failed to face.
+        let reason = chassis
+            .shoot_to_pose(Pose::new(Vec2::new(0.0, 1.0), QAngle::from_degrees(0.0)))
+            .await
+            .unwrap();
+        assert_eq!(reason, ExitReason::Timeout);
+        assert!(robot.pose().position().distance(Vec2::new(0.0, 0.0)) < 0.01);
+    });
+}
+
+#[test]
+fn test_shoot_exits_when_stalled() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        robot.set_held(true);
+        let start = clock.now();
+        let reason = chassis.shoot(QLength::from_meters(0.5)).await.unwrap();
+
+        assert_eq!(reason, ExitReason::Velocity);
+        // The profile takes about 1.2 s; the stall is detected 250 ms later.
+        assert!(clock.now() - start < Duration::from_secs(2));
+    });
+}
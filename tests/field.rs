@@ -0,0 +1,171 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, drivetrain, tuned};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    Alliance, Axis, Clock, FieldFrame, FieldSymmetry, FieldTransform, FieldUnits, ManualClock,
+    OdomChassis, Pose, PurePursuit, QAngle, QLength, QTime, Trajectory, Vec2,
+};
+use std::time::Duration;
+
+fn assert_pose_eq(actual: Pose, x: f64, y: f64, degrees: f64) {
+    let pos = actual.position();
+    assert!((pos.x - x).abs() < 1e-9, "x = {}", pos.x);
+    assert!((pos.y - y).abs() < 1e-9, "y = {}", pos.y);
+    let heading = (actual.heading() - QAngle::from_degrees(degrees))
+        .remainder(QAngle::TAU)
+        .as_degrees();
+    assert!(heading.abs() < 1e-7, "heading off by {heading}°");
+}
+
+fn bezier() -> Trajectory {
+    Trajectory::from_cubic_bezier(
+        Vec2::new(-1.2, -1.2),
+        Vec2::new(-0.6, -1.2),
+        Vec2::new(-0.6, -0.4),
+        Vec2::new(-0.2, -0.4),
+        QTime::from_sec(3.0),
+        100,
+        0.6,
+    )
+}
+
+#[test]
+fn test_pose_mirror_and_rotate() {
+    let pose = Pose::new(Vec2::new(1.0, 0.5), QAngle::from_degrees(30.0));
+
+    assert_pose_eq(pose.mirror(Axis::X), 1.0, -0.5, -30.0);
+    assert_pose_eq(pose.mirror(Axis::Y), -1.0, 0.5, 150.0);
+    assert_pose_eq(pose.rotate_half(), -1.0, -0.5, 210.0);
+
+    // Mirroring twice, or turning twice, gives back the original pose.
+    for twice in [
+        pose.mirror(Axis::X).mirror(Axis::X),
+        pose.mirror(Axis::Y).mirror(Axis::Y),
+        pose.rotate_half().rotate_half(),
+    ] {
+        assert_pose_eq(twice, 1.0, 0.5, 30.0);
+    }
+}
+
+#[test]
+fn test_mirrored_trajectory_turns_the_other_way() {
+    let trajectory = bezier();
+
+    for mirrored in [trajectory.mirror(Axis::X), trajectory.mirror(Axis::Y)] {
+        let points = mirrored.points();
+        assert_eq!(points.len(), trajectory.points().len());
+        for (original, point) in trajectory.points().iter().zip(points) {
+            assert_eq!(point.linear_velocity, original.linear_velocity);
+            assert_eq!(point.angular_velocity, -original.angular_velocity);
+            assert_eq!(point.time.as_sec(), original.time.as_sec());
+        }
+
+        // Headings still follow the path's direction of travel.
+        for pair in points.windows(2) {
+            let step = pair[1].pose.position() - pair[0].pose.position();
+            let direction = QAngle::from_radians(libm::atan2(step.y, step.x));
+            let error = (direction - pair[0].pose.heading())
+                .remainder(QAngle::TAU)
+                .as_degrees();
+            assert!(error.abs() < 5.0, "heading off by {error}°");
+        }
+    }
+
+    let rotated = trajectory.rotate_half();
+    for (original, point) in trajectory.points().iter().zip(rotated.points()) {
+        assert_eq!(point.angular_velocity, original.angular_velocity);
+    }
+}
+
+#[test]
+fn test_field_unit_conversions() {
+    let field = FieldFrame::v5();
+    let half = QLength::from_inches(72.0).as_meters();
+
+    let corner = field.to_centered(Vec2::new(0.0, 0.0), FieldUnits::CornerInches);
+    assert!((corner.x + half).abs() < 1e-9 && (corner.y + half).abs() < 1e-9);
+
+    let centre = field.convert(Vec2::new(0.0, 0.0), FieldUnits::Centered, FieldUnits::Tiles);
+    assert!((centre.x - 3.0).abs() < 1e-9 && (centre.y - 3.0).abs() < 1e-9);
+
+    let inches = field.convert(
+        Vec2::new(1.5, 0.5),
+        FieldUnits::Tiles,
+        FieldUnits::CornerInches,
+    );
+    assert!((inches.x - 36.0).abs() < 1e-9 && (inches.y - 12.0).abs() < 1e-9);
+
+    // Round trips return the original point.
+    let point = Vec2::new(0.3, -1.1);
+    for units in [
+        FieldUnits::Centered,
+        FieldUnits::CornerInches,
+        FieldUnits::Tiles,
+    ] {
+        let back = field.to_centered(field.from_centered(point, units), units);
+        assert!((back.x - point.x).abs() < 1e-9 && (back.y - point.y).abs() < 1e-9);
+    }
+
+    // A smaller practice field with the same tiles.
+    let small = FieldFrame::v5().with_size(QLength::from_inches(96.0), QLength::from_inches(96.0));
+    let centre = small.convert(Vec2::new(0.0, 0.0), FieldUnits::Centered, FieldUnits::Tiles);
+    assert!((centre.x - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_for_alliance_applies_field_symmetry() {
+    let red = Pose::new(Vec2::new(-1.2, -0.6), QAngle::from_degrees(45.0));
+
+    let field = FieldFrame::v5();
+    assert_pose_eq(
+        field.for_alliance(&red, Alliance::Red, Alliance::Red),
+        -1.2,
+        -0.6,
+        45.0,
+    );
+    assert_pose_eq(
+        field.for_alliance(&red, Alliance::Red, Alliance::Blue),
+        1.2,
+        -0.6,
+        135.0,
+    );
+
+    let rotated = field.with_symmetry(FieldSymmetry::Rotate);
+    assert_pose_eq(
+        rotated.for_alliance(&red, Alliance::Blue, Alliance::Red),
+        1.2,
+        0.6,
+        225.0,
+    );
+}
+
+#[test]
+fn test_pursuit_follows_mirrored_path() {
+    let field = FieldFrame::v5();
+    let red = PurePursuit::new(bezier(), 0.25);
+    let blue = field.for_alliance(&red, Alliance::Red, Alliance::Blue);
+    let start = blue.trajectory().points()[0].pose;
+    let end = blue.trajectory().points().last().unwrap().pose;
+    assert!(end.position().x > 0.0, "the blue path is on the other side");
+
+    let robot = SimRobot::new(config(), start);
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), None).with_clock(clock.clone()),
+        );
+        chassis.set_pose(&start);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.pursuit(&blue).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    let error = robot.pose().distance(end).as_meters();
+    assert!(error < 0.1, "ended {error} m from the mirrored path's end");
+}
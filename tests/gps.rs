@@ -0,0 +1,273 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, rig};
+use kernelvex::odom::gps::{GpsError, from_gps_frame};
+use kernelvex::sim::devices::{SensorNoise, SimGps, SimRobot};
+use kernelvex::{
+    Clock, Gps, GpsConfig, GpsReader, GpsRig, ManualClock, MotorOutput, Pose, PoseSource, QAngle,
+    QLength, Vec2,
+};
+use std::cell::Cell;
+use std::time::Duration;
+use vexide::smart::PortError;
+
+/// A GPS that always reports the same reading.
+struct FixedGps {
+    position: Vec2<f64>,
+    heading: QAngle,
+    error: QLength,
+}
+
+impl Gps for FixedGps {
+    fn position(&self) -> Result<Vec2<f64>, PortError> {
+        Ok(self.position)
+    }
+
+    fn heading(&self) -> Result<QAngle, PortError> {
+        Ok(self.heading)
+    }
+
+    fn error(&self) -> Result<QLength, PortError> {
+        Ok(self.error)
+    }
+}
+
+/// A GPS whose readings are only good enough to use once every `every`
+/// reads.
+struct DroppingGps {
+    inner: SimGps,
+    every: u32,
+    reads: Cell<u32>,
+}
+
+impl Gps for DroppingGps {
+    fn position(&self) -> Result<Vec2<f64>, PortError> {
+        self.inner.position()
+    }
+
+    fn heading(&self) -> Result<QAngle, PortError> {
+        self.inner.heading()
+    }
+
+    fn error(&self) -> Result<QLength, PortError> {
+        let reads = self.reads.get() + 1;
+        self.reads.set(reads);
+        if reads.is_multiple_of(self.every) {
+            self.inner.error()
+        } else {
+            Ok(QLength::from_meters(1.0))
+        }
+    }
+}
+
+fn fixed(x: f64, y: f64, heading: f64) -> FixedGps {
+    FixedGps {
+        position: Vec2::new(x, y),
+        heading: QAngle::from_degrees(heading),
+        error: QLength::from_centimeters(1.0),
+    }
+}
+
+fn assert_pose(actual: Pose, x: f64, y: f64, heading: f64) {
+    let dh = (actual.heading() - QAngle::from_degrees(heading))
+        .remainder(QAngle::TAU)
+        .as_degrees();
+    assert!(
+        (actual.position().x - x).abs() < 1e-9
+            && (actual.position().y - y).abs() < 1e-9
+            && dh.abs() < 1e-9,
+        "expected ({x}, {y}, {heading}), got {actual}"
+    );
+}
+
+// ============================================================================
+// Conversion Tests
+// ============================================================================
+
+#[test]
+fn test_gps_heading_converted_to_pose_convention() {
+    // GPS 0° faces +y, 90° faces +x.
+    assert_pose(
+        from_gps_frame(Vec2::new(0.5, -0.5), QAngle::from_degrees(0.0)),
+        0.5,
+        -0.5,
+        90.0,
+    );
+    assert_pose(
+        from_gps_frame(Vec2::new(0.0, 0.0), QAngle::from_degrees(90.0)),
+        0.0,
+        0.0,
+        0.0,
+    );
+    assert_pose(
+        from_gps_frame(Vec2::new(0.0, 0.0), QAngle::from_degrees(270.0)),
+        0.0,
+        0.0,
+        180.0,
+    );
+}
+
+#[test]
+fn test_reader_applies_mount() {
+    // Sensor 10 cm behind centre, facing backward. The sensor faces -x, so the
+    // robot faces +x and its centre is 10 cm further along +x.
+    let mount = Pose::new(Vec2::new(-0.1, 0.0), QAngle::from_degrees(180.0));
+    let reader = GpsReader::new(fixed(1.0, 0.5, 270.0), mount, GpsConfig::new());
+
+    let fix = reader.read().unwrap();
+    assert_pose(fix.pose, 1.1, 0.5, 0.0);
+    assert!((fix.error.as_centimeters() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_reader_applies_origin() {
+    // Odometry frame has its origin in the bottom-left corner, facing +y.
+    let origin = Pose::new(Vec2::new(-1.8, -1.8), QAngle::from_degrees(90.0));
+    let reader = GpsReader::new(
+        fixed(-1.3, -1.6, 0.0),
+        Pose::default(),
+        GpsConfig::new().with_origin(origin),
+    );
+
+    // 0.2 m along the frame's x (field +y), 0.5 m along its y (field -x).
+    assert_pose(reader.read().unwrap().pose, 0.2, -0.5, 0.0);
+}
+
+#[test]
+fn test_reader_rejects_poor_quality() {
+    let mut gps = fixed(0.0, 0.0, 0.0);
+    gps.error = QLength::from_centimeters(20.0);
+    let reader = GpsReader::new(gps, Pose::default(), GpsConfig::new());
+    assert_eq!(reader.read().unwrap_err(), GpsError::PoorQuality);
+
+    let mut gps = fixed(0.0, 0.0, 0.0);
+    gps.error = QLength::from_meters(f64::NAN);
+    let reader = GpsReader::new(gps, Pose::default(), GpsConfig::new());
+    assert_eq!(reader.read().unwrap_err(), GpsError::PoorQuality);
+
+    let mut gps = fixed(0.0, 0.0, 0.0);
+    gps.error = QLength::from_centimeters(20.0);
+    let lenient = GpsConfig::new().with_max_error(QLength::from_centimeters(30.0));
+    assert!(GpsReader::new(gps, Pose::default(), lenient).read().is_ok());
+}
+
+// ============================================================================
+// Rig Tests
+// ============================================================================
+
+#[test]
+fn test_sim_gps_round_trip() {
+    let start = Pose::new(Vec2::new(0.4, -0.7), QAngle::from_degrees(30.0));
+    let robot = SimRobot::new(config(), start);
+    let mount = Pose::new(Vec2::new(0.05, 0.12), QAngle::from_degrees(-90.0));
+
+    let reader = GpsReader::new(robot.gps(mount), mount, GpsConfig::new());
+    assert_pose(reader.read().unwrap().pose, 0.4, -0.7, 30.0);
+}
+
+#[test]
+fn test_gps_rig_replaces_odometry() {
+    let robot = SimRobot::new(config(), Pose::default()).with_noise(SensorNoise {
+        gps_position: 0.005,
+        gps_heading: 0.5,
+        ..Default::default()
+    });
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let reader = GpsReader::new(
+            robot.gps(Pose::default()),
+            Pose::default(),
+            GpsConfig::new(),
+        );
+        let gps = GpsRig::with_clock(reader, None, clock.clone());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        clock.sleep(Duration::from_millis(800)).await;
+
+        let source: &dyn PoseSource = &gps;
+        assert!(robot.pose().distance(source.pose()).as_meters() < 0.03);
+        assert!((source.linear_velocity() - robot.linear_velocity()).abs() < 0.3);
+        assert_eq!(gps.rejected(), 0);
+    });
+}
+
+#[test]
+fn test_gps_rig_velocity_spans_rejected_readings() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let gps = DroppingGps {
+            inner: robot.gps(Pose::default()),
+            every: 5,
+            reads: Cell::new(0),
+        };
+        let reader = GpsReader::new(gps, Pose::default(), GpsConfig::new());
+        let gps = GpsRig::with_clock(reader, None, clock.clone());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        clock.sleep(Duration::from_millis(1000)).await;
+
+        // Four of every five readings are rejected; the velocity is still
+        // measured over the time between the accepted ones.
+        assert!(gps.rejected() > 0);
+        let (v, truth) = (gps.linear_velocity(), robot.linear_velocity());
+        assert!((v - truth).abs() < 0.2, "v = {v}, truth = {truth}");
+    });
+}
+
+#[test]
+fn test_gps_rig_resets_drifting_odometry() {
+    let noise = SensorNoise {
+        slip: 0.2,
+        gps_position: 0.002,
+        ..Default::default()
+    };
+
+    let run = |with_gps: bool| {
+        let robot = SimRobot::new(config(), Pose::default())
+            .with_noise(noise)
+            .with_seed(9);
+        let clock = ManualClock::new();
+        let mut left = robot.left_motor();
+        let mut right = robot.right_motor();
+
+        vexide_async::block_on(async {
+            let rig = rig(&robot, &clock);
+            let source: Box<dyn PoseSource> = if with_gps {
+                let config = GpsConfig::new().with_reset_interval(Duration::from_millis(200));
+                let reader = GpsReader::new(robot.gps(Pose::default()), Pose::default(), config);
+                Box::new(GpsRig::with_clock(reader, Some(rig), clock.clone()))
+            } else {
+                Box::new(rig)
+            };
+            let _physics = robot.spawn_with_clock(clock.clone());
+
+            left.set_voltage(9.0).unwrap();
+            right.set_voltage(7.0).unwrap();
+            clock.sleep(Duration::from_millis(1000)).await;
+            left.set_voltage(0.0).unwrap();
+            right.set_voltage(0.0).unwrap();
+            clock.sleep(Duration::from_millis(500)).await;
+
+            robot.pose().distance(source.pose()).as_meters()
+        })
+    };
+
+    let drifted = run(false);
+    let reset = run(true);
+    assert!(drifted > 0.05, "slip should cause drift, got {drifted}");
+    assert!(reset < 0.01, "reset error {reset}");
+}
@@ -0,0 +1,572 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+use kernelvex::odom::wheel::{OmniWheel, TrackingRig, TrackingWheel};
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    AngularPid, DifferentialDrive, Drivetrain, ExpoDrive, Gyro, ManualClock, MotorGroup,
+    MotorOutput, OdomChassis, Pose, QAngle, QLength, RotaryEncoder, Tank, WheelHealthConfig,
+};
+use std::cell::Cell;
+use std::f64::consts::PI;
+use std::future::poll_fn;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::Duration;
+use vexide::smart::PortError;
+use vexide::smart::imu::InertialError;
+use vexide::smart::motor::BrakeMode;
+use vexide_async::task::spawn;
+
+// ============================================================================
+// Mock Devices
+// ============================================================================
+
+#[derive(Default)]
+struct MotorState {
+    volts: f64,
+    rpm: f64,
+    position: f64,
+    brake: Option<BrakeMode>,
+}
+
+struct MockMotor {
+    state: Arc<Mutex<MotorState>>,
+    connected: bool,
+}
+
+impl MockMotor {
+    fn new() -> (Self, Arc<Mutex<MotorState>>) {
+        let state = Arc::new(Mutex::new(MotorState::default()));
+        (
+            Self {
+                state: Arc::clone(&state),
+                connected: true,
+            },
+            state,
+        )
+    }
+
+    fn check(&self) -> Result<(), PortError> {
+        if self.connected {
+            Ok(())
+        } else {
+            Err(PortError::Disconnected { port: 1 })
+        }
+    }
+}
+
+impl MotorOutput for MockMotor {
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+        self.check()?;
+        self.state.lock().unwrap().volts = volts;
+        Ok(())
+    }
+
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError> {
+        self.check()?;
+        self.state.lock().unwrap().brake = Some(mode);
+        Ok(())
+    }
+
+    fn velocity(&self) -> Result<f64, PortError> {
+        self.check()?;
+        Ok(self.state.lock().unwrap().rpm)
+    }
+
+    fn position(&self) -> Result<QAngle, PortError> {
+        self.check()?;
+        Ok(QAngle::from_radians(self.state.lock().unwrap().position))
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        self.check()?;
+        self.state.lock().unwrap().position = position.as_radians();
+        Ok(())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.set_position(QAngle::from_radians(0.0))
+    }
+}
+
+struct MockEncoder {
+    turns: Rc<Cell<f64>>,
+}
+
+impl RotaryEncoder for MockEncoder {
+    fn position(&self) -> Result<QAngle, PortError> {
+        Ok(QAngle::from_turns(self.turns.get()))
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        self.turns.set(position.as_turns());
+        Ok(())
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.turns.set(0.0);
+        Ok(())
+    }
+}
+
+struct MockGyro {
+    heading: Rc<Cell<f64>>,
+}
+
+impl Gyro for MockGyro {
+    fn heading(&self) -> Result<QAngle, InertialError> {
+        Ok(QAngle::from_degrees(self.heading.get()))
+    }
+
+    fn gyro_rate(&self) -> Result<f64, PortError> {
+        Ok(0.0)
+    }
+}
+
+fn wheel(turns: &Rc<Cell<f64>>, offset: f64) -> TrackingWheel<MockEncoder> {
+    TrackingWheel::new(
+        MockEncoder {
+            turns: Rc::clone(turns),
+        },
+        OmniWheel::Custom(QLength::from_meters(1.0 / PI)),
+        TrackingWheelOrientation::Vertical(QLength::from_meters(offset)),
+        None,
+    )
+}
+
+// ============================================================================
+// MotorGroup Tests
+// ============================================================================
+
+#[test]
+fn test_motor_group_set_voltage_applies_to_all() {
+    let (a, a_state) = MockMotor::new();
+    let (b, b_state) = MockMotor::new();
+    let mut group = MotorGroup::new([a, b]);
+
+    vexide_async::block_on(async {
+        group.set_voltage(6.0).await.unwrap();
+        group.brake(BrakeMode::Hold).await.unwrap();
+    });
+
+    assert_eq!(a_state.lock().unwrap().volts, 6.0);
+    assert_eq!(b_state.lock().unwrap().volts, 6.0);
+    assert_eq!(b_state.lock().unwrap().brake, Some(BrakeMode::Hold));
+}
+
+#[test]
+fn test_motor_group_velocity_is_average() {
+    let (a, a_state) = MockMotor::new();
+    let (b, b_state) = MockMotor::new();
+    a_state.lock().unwrap().rpm = 100.0;
+    b_state.lock().unwrap().rpm = 200.0;
+    let group = MotorGroup::new([a, b]);
+
+    let rpm = vexide_async::block_on(group.velocity()).unwrap();
+    assert_eq!(rpm, 150);
+}
+
+#[test]
+fn test_motor_group_collects_errors() {
+    let (a, a_state) = MockMotor::new();
+    let (mut b, _) = MockMotor::new();
+    b.connected = false;
+    let mut group = MotorGroup::new([a, b]);
+
+    let result = vexide_async::block_on(group.set_voltage(3.0));
+    let errors = result.unwrap_err();
+    assert_eq!(errors.len(), 1);
+    // The healthy motor is still commanded.
+    assert_eq!(a_state.lock().unwrap().volts, 3.0);
+}
+
+#[test]
+fn test_motor_group_position_reset() {
+    let (a, a_state) = MockMotor::new();
+    a_state.lock().unwrap().position = 4.0;
+    let mut group = MotorGroup::new([a]);
+
+    vexide_async::block_on(group.reset_position()).unwrap();
+    assert_eq!(a_state.lock().unwrap().position, 0.0);
+}
+
+// ============================================================================
+// DifferentialDrive Tests
+// ============================================================================
+
+#[test]
+fn test_differential_drive_tank_with_mock_motors() {
+    let (l, l_state) = MockMotor::new();
+    let (r, r_state) = MockMotor::new();
+    let mut dt = DifferentialDrive::new(
+        MotorGroup::new([l]),
+        MotorGroup::new([r]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_inches(12.0),
+        1.0,
+    );
+
+    vexide_async::block_on(dt.drive_tank(0.5, 0.25)).unwrap();
+
+    assert!((l_state.lock().unwrap().volts - 6.0).abs() < 1e-9);
+    assert!((r_state.lock().unwrap().volts - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_differential_drive_zero_velocity_when_stopped() {
+    let (l, _) = MockMotor::new();
+    let (r, _) = MockMotor::new();
+    let dt = DifferentialDrive::new(
+        MotorGroup::new([l]),
+        MotorGroup::new([r]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_inches(12.0),
+        1.0,
+    );
+
+    let v = vexide_async::block_on(dt.linear_velocity()).unwrap();
+    let w = vexide_async::block_on(dt.angular_velocity()).unwrap();
+    assert_eq!(v, 0.0);
+    assert_eq!(w, 0.0);
+}
+
+#[test]
+fn test_differential_drive_tank_reverse() {
+    let (l, l_state) = MockMotor::new();
+    let (r, r_state) = MockMotor::new();
+    let mut dt = DifferentialDrive::new(
+        MotorGroup::new([l]),
+        MotorGroup::new([r]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_inches(12.0),
+        1.0,
+    );
+
+    // Negative inputs are outside the deadband and must not be zeroed.
+    vexide_async::block_on(dt.drive_tank(-0.5, 0.25)).unwrap();
+
+    assert!((l_state.lock().unwrap().volts + 6.0).abs() < 1e-9);
+    assert!((r_state.lock().unwrap().volts - 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_differential_drive_velocity_uses_wheel_radius() {
+    let (l, l_state) = MockMotor::new();
+    let (r, r_state) = MockMotor::new();
+    let dt = DifferentialDrive::new(
+        MotorGroup::new([l]),
+        MotorGroup::new([r]),
+        ExpoDrive::new(0.0, 1.0, None),
+        // A 1/π m wheel rolls one metre per revolution.
+        OmniWheel::Custom(QLength::from_meters(1.0 / PI)),
+        QLength::from_meters(0.5),
+        1.0,
+    );
+
+    l_state.lock().unwrap().rpm = 60.0;
+    r_state.lock().unwrap().rpm = 60.0;
+    let v = vexide_async::block_on(dt.linear_velocity()).unwrap();
+    assert!((v - 1.0).abs() < 1e-9, "v = {v}");
+
+    l_state.lock().unwrap().rpm = -60.0;
+    let w = vexide_async::block_on(dt.angular_velocity()).unwrap();
+    assert!((w - 4.0).abs() < 1e-9, "w = {w}");
+}
+
+// ============================================================================
+// TrackingWheel Tests
+// ============================================================================
+
+#[test]
+fn test_tracking_wheel_distance_one_turn() {
+    let turns = Rc::new(Cell::new(1.0));
+    // Diameter of 1/π gives a circumference of exactly 1 meter.
+    let wheel = wheel(&turns, 0.1);
+    assert!((wheel.distance().unwrap().as_meters() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_tracking_wheel_gearing() {
+    let turns = Rc::new(Cell::new(2.0));
+    let wheel = TrackingWheel::new(
+        MockEncoder {
+            turns: Rc::clone(&turns),
+        },
+        OmniWheel::Custom(QLength::from_meters(1.0 / PI)),
+        TrackingWheelOrientation::Vertical(QLength::from_meters(0.1)),
+        Some(2.0),
+    );
+    assert!((wheel.distance().unwrap().as_meters() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_tracking_wheel_delta_and_reset() {
+    let turns = Rc::new(Cell::new(0.5));
+    let mut wheel = wheel(&turns, -0.1);
+
+    assert!((wheel.delta().unwrap().as_meters() - 0.5).abs() < 1e-9);
+    turns.set(0.75);
+    assert!((wheel.delta().unwrap().as_meters() - 0.25).abs() < 1e-9);
+
+    wheel.reset();
+    assert_eq!(turns.get(), 0.0);
+    assert_eq!(wheel.delta().unwrap().as_meters(), 0.0);
+}
+
+/// Advances `clock` by `duration` a millisecond at a time, letting the
+/// background tasks waiting on it run after each step.
+async fn run_for(clock: &ManualClock, duration: Duration) {
+    for _ in 0..duration.as_millis() {
+        clock.advance(Duration::from_millis(1));
+        let mut yielded = false;
+        poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+}
+
+// ============================================================================
+// TrackingRig Tests
+// ============================================================================
+
+#[test]
+fn test_tracking_rig_straight_line_with_mock_devices() {
+    let left = Rc::new(Cell::new(0.0));
+    let right = Rc::new(Cell::new(0.0));
+    let heading = Rc::new(Cell::new(0.0));
+
+    let clock = ManualClock::new();
+
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        [wheel(&left, -0.1), wheel(&right, 0.1)],
+        Some(MockGyro {
+            heading: Rc::clone(&heading),
+        }),
+        clock.clone(),
+    )
+    // The mock wheels move instantly, faster than any robot could.
+    .with_wheel_health(WheelHealthConfig::new().with_max_speed(f64::INFINITY));
+
+    vexide_async::block_on(async {
+        run_for(&clock, Duration::from_millis(30)).await;
+        left.set(0.5);
+        right.set(0.5);
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    let pose = rig.pose();
+    assert!((pose.position().x - 0.5).abs() < 1e-9);
+    assert!(pose.position().y.abs() < 1e-9);
+}
+
+#[test]
+fn test_tracking_rig_heading_from_parallel_wheels() {
+    let left = Rc::new(Cell::new(0.0));
+    let right = Rc::new(Cell::new(0.0));
+
+    let clock = ManualClock::new();
+
+    // No IMU: heading must come from the two vertical wheels.
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        [wheel(&left, -0.1), wheel(&right, 0.1)],
+        None::<MockGyro>,
+        clock.clone(),
+    )
+    .with_wheel_health(WheelHealthConfig::new().with_max_speed(f64::INFINITY));
+
+    vexide_async::block_on(async {
+        run_for(&clock, Duration::from_millis(30)).await;
+        // Turning 90° counter-clockwise in place moves each wheel 0.1·π/2 m.
+        left.set(-0.05 * PI);
+        right.set(0.05 * PI);
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    assert!((rig.pose().heading().as_degrees() - 90.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_tracking_rig_two_horizontal_wheels() {
+    let front = Rc::new(Cell::new(0.0));
+    let back = Rc::new(Cell::new(0.0));
+    let horizontal = |turns: &Rc<Cell<f64>>, offset| {
+        TrackingWheel::new(
+            MockEncoder {
+                turns: Rc::clone(turns),
+            },
+            OmniWheel::Custom(QLength::from_meters(1.0 / PI)),
+            TrackingWheelOrientation::Horizontal(QLength::from_meters(offset)),
+            None,
+        )
+    };
+
+    let clock = ManualClock::new();
+
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [horizontal(&front, 0.1), horizontal(&back, -0.1)],
+        [],
+        Some(MockGyro {
+            heading: Rc::new(Cell::new(0.0)),
+        }),
+        clock.clone(),
+    )
+    .with_wheel_health(WheelHealthConfig::new().with_max_speed(f64::INFINITY));
+
+    vexide_async::block_on(async {
+        run_for(&clock, Duration::from_millis(30)).await;
+        front.set(0.3);
+        back.set(0.3);
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    let pose = rig.pose();
+    assert!(pose.position().x.abs() < 1e-9);
+    assert!((pose.position().y - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn test_tracking_rig_turn_in_place_with_offset_wheel() {
+    let right = Rc::new(Cell::new(0.0));
+    let heading = Rc::new(Cell::new(0.0));
+
+    let clock = ManualClock::new();
+
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        [wheel(&right, 0.1)],
+        Some(MockGyro {
+            heading: Rc::clone(&heading),
+        }),
+        clock.clone(),
+    )
+    .with_wheel_health(WheelHealthConfig::new().with_max_speed(f64::INFINITY));
+
+    vexide_async::block_on(async {
+        run_for(&clock, Duration::from_millis(30)).await;
+        // A 90° counter-clockwise turn about the centre rolls a wheel 0.1 m to
+        // the right forward by 0.1·π/2 m. The IMU reads clockwise.
+        heading.set(-90.0);
+        right.set(0.05 * PI);
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    let pose = rig.pose();
+    assert!(pose.position().x.abs() < 1e-9, "x = {}", pose.position().x);
+    assert!(pose.position().y.abs() < 1e-9, "y = {}", pose.position().y);
+    assert!((pose.heading().as_degrees() - 90.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_tracking_rig_arc_uses_midpoint_heading() {
+    let centre = Rc::new(Cell::new(0.0));
+    let heading = Rc::new(Cell::new(0.0));
+
+    let clock = ManualClock::new();
+
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        [wheel(&centre, 0.0)],
+        Some(MockGyro {
+            heading: Rc::clone(&heading),
+        }),
+        clock.clone(),
+    )
+    .with_wheel_health(WheelHealthConfig::new().with_max_speed(f64::INFINITY));
+
+    vexide_async::block_on(async {
+        run_for(&clock, Duration::from_millis(30)).await;
+        // A counter-clockwise quarter circle of radius 0.5 m in one update.
+        heading.set(-90.0);
+        centre.set(0.25 * PI);
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    let pose = rig.pose();
+    assert!(
+        (pose.position().x - 0.5).abs() < 1e-9,
+        "x = {}",
+        pose.position().x
+    );
+    assert!(
+        (pose.position().y - 0.5).abs() < 1e-9,
+        "y = {}",
+        pose.position().y
+    );
+}
+
+// ============================================================================
+// OdomChassis Tests
+// ============================================================================
+
+type MotorStates = (Arc<Mutex<MotorState>>, Arc<Mutex<MotorState>>);
+
+fn mock_chassis(
+    heading: &Rc<Cell<f64>>,
+    clock: &ManualClock,
+) -> (OdomChassis<MockMotor, MockGyro, ManualClock>, MotorStates) {
+    let (l, l_state) = MockMotor::new();
+    let (r, r_state) = MockMotor::new();
+    let dt = DifferentialDrive::new(
+        MotorGroup::new([l]),
+        MotorGroup::new([r]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_inches(12.0),
+        1.0,
+    );
+    let imu = MockGyro {
+        heading: Rc::clone(heading),
+    };
+
+    (
+        OdomChassis::new(dt, imu, None).with_clock(clock.clone()),
+        (l_state, r_state),
+    )
+}
+
+#[test]
+fn test_chassis_heading_is_counter_clockwise() {
+    // The IMU reads 90° clockwise, which is -90° in the pose convention.
+    let heading = Rc::new(Cell::new(90.0));
+    let (chassis, _) = mock_chassis(&heading, &ManualClock::new());
+
+    let error = (chassis.heading() - QAngle::from_degrees(-90.0)).remainder(QAngle::TAU);
+    assert!(error.as_degrees().abs() < 1e-9);
+}
+
+#[test]
+fn test_chassis_turn_drives_counter_clockwise() {
+    let heading = Rc::new(Cell::new(0.0));
+    let clock = ManualClock::new();
+    let (chassis, (l_state, r_state)) = mock_chassis(&heading, &clock);
+    let mut chassis = chassis.with_angular_pid(AngularPid::new().set_gains(10.0, 0.0, 0.0));
+
+    vexide_async::block_on(async {
+        // The mock IMU never moves, so the turn runs until the task is dropped.
+        let _turn = spawn(async move { chassis.turn(QAngle::from_degrees(90.0)).await });
+        run_for(&clock, Duration::from_millis(30)).await;
+    });
+
+    // A counter-clockwise turn drives the left side back and the right forward.
+    assert!(l_state.lock().unwrap().volts < 0.0);
+    assert!(r_state.lock().unwrap().volts > 0.0);
+}
@@ -0,0 +1,176 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, drivetrain, tuned};
+use kernelvex::sim::devices::{SimEncoder, SimRobot};
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    Clock, ManualClock, MotorOutput, OdomChassis, OmniWheel, Pose, QAngle, QLength, TrackingRig,
+    TrackingWheel, TravelSource, Vec2, WheelHealth,
+};
+use std::time::Duration;
+
+/// Returns a tracking wheel and a handle to its encoder for injecting faults.
+fn wheel(
+    robot: &SimRobot,
+    orientation: TrackingWheelOrientation,
+) -> (TrackingWheel<SimEncoder>, SimEncoder) {
+    let encoder = robot.encoder(OmniWheel::Omni275, orientation, None);
+    let wheel = TrackingWheel::new(encoder.clone(), OmniWheel::Omni275, orientation, None);
+    (wheel, encoder)
+}
+
+fn vertical(offset: f64) -> TrackingWheelOrientation {
+    TrackingWheelOrientation::Vertical(QLength::from_meters(offset))
+}
+
+/// A rig with two parallel vertical wheels and an IMU.
+fn rig(robot: &SimRobot, clock: &ManualClock) -> (TrackingRig, SimEncoder, SimEncoder) {
+    let (left, left_encoder) = wheel(robot, vertical(-0.1));
+    let (right, right_encoder) = wheel(robot, vertical(0.1));
+    let rig = TrackingRig::with_clock(
+        Pose::default(),
+        [],
+        [left, right],
+        Some(robot.imu()),
+        clock.clone(),
+    );
+    (rig, left_encoder, right_encoder)
+}
+
+fn drive(robot: &SimRobot, left: f64, right: f64) {
+    robot.left_motor().set_voltage(left).unwrap();
+    robot.right_motor().set_voltage(right).unwrap();
+}
+
+#[test]
+fn test_disconnected_wheel_is_left_out() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let (rig, left, _) = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        drive(&robot, 8.0, 6.0);
+        clock.sleep(Duration::from_millis(500)).await;
+        left.set_connected(false);
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let status = rig.wheel_status();
+        assert_eq!(status[0].health, WheelHealth::Disconnected);
+        assert!(!status[0].used);
+        assert!(status[0].failures >= 40, "{} failures", status[0].failures);
+        assert_eq!(status[1].health, WheelHealth::Healthy);
+        assert!(status[1].used);
+        assert_eq!(rig.travel_source(), TravelSource::Wheels);
+
+        let error = rig.pose().distance(robot.pose()).as_meters();
+        assert!(error < 0.02, "{error} m off");
+
+        // The wheel rejoins once it reads again, without the travel it missed.
+        left.set_connected(true);
+        clock.sleep(Duration::from_millis(500)).await;
+        let status = rig.wheel_status();
+        assert_eq!(status[0].health, WheelHealth::Healthy);
+        assert!(status[0].used);
+        let error = rig.pose().distance(robot.pose()).as_meters();
+        assert!(error < 0.02, "{error} m off after reconnecting");
+    });
+}
+
+#[test]
+fn test_frozen_wheel_goes_stale() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let (rig, _, right) = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        drive(&robot, 8.0, 8.0);
+        clock.sleep(Duration::from_millis(300)).await;
+        right.set_frozen(true);
+        for _ in 0..50 {
+            rig.observe_drive(robot.linear_velocity(), robot.angular_velocity());
+            clock.sleep(Duration::from_millis(10)).await;
+        }
+
+        let status = rig.wheel_status();
+        assert_eq!(status[1].health, WheelHealth::Stale);
+        assert!(!status[1].used);
+        assert_eq!(status[0].health, WheelHealth::Healthy);
+
+        // Until it goes stale the frozen wheel drags the average down, but
+        // only by a fraction of the travel during the stale time.
+        let error = rig.pose().distance(robot.pose()).as_meters();
+        assert!(error < 0.25, "{error} m off");
+        let moved = robot.pose().position().x;
+        let tracked_after = rig.pose().position().x;
+        clock.sleep(Duration::from_millis(200)).await;
+        let gained =
+            (robot.pose().position().x - moved) - (rig.pose().position().x - tracked_after);
+        assert!(gained.abs() < 0.02, "still drifting by {gained} m");
+    });
+}
+
+#[test]
+fn test_glitch_is_discarded() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let (rig, left, _) = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        drive(&robot, 6.0, 6.0);
+        clock.sleep(Duration::from_millis(500)).await;
+        left.glitch(20.0);
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let status = rig.wheel_status();
+        assert_eq!(status[0].jumps, 1);
+        assert_eq!(status[0].health, WheelHealth::Healthy);
+        assert_eq!(status[1].jumps, 0);
+
+        let error = rig.pose().distance(robot.pose()).as_meters();
+        assert!(error < 0.03, "{error} m off");
+    });
+}
+
+#[test]
+fn test_chassis_falls_back_to_drive_encoders() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let target = Pose::new(Vec2::new(0.8, 0.0), QAngle::from_degrees(0.0));
+    let (forward, encoder) = wheel(&robot, vertical(0.0));
+
+    vexide_async::block_on(async {
+        let rig = TrackingRig::with_clock(
+            Pose::default(),
+            [],
+            [forward],
+            Some(robot.imu()),
+            clock.clone(),
+        );
+        let mut chassis = tuned(
+            OdomChassis::new(drivetrain(&robot), robot.imu(), Some(rig)).with_clock(clock.clone()),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The only vertical wheel drops out before the motion starts.
+        encoder.set_connected(false);
+        chassis.shoot_to_pose(target).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+
+        let status = chassis.wheel_status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].health, WheelHealth::Disconnected);
+
+        let pose = robot.pose();
+        assert!(pose.distance(target).as_meters() < 0.08, "{pose:?}");
+        assert!(chassis.get_pose().distance(pose).as_meters() < 0.05);
+    });
+}
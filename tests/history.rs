@@ -0,0 +1,145 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, rig};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    Clock, ManualClock, MotorOutput, Pose, PoseHistory, PoseSample, QAngle, QTime, Trajectory,
+    TrajectoryPoint, Vec2,
+};
+use std::time::Duration;
+
+fn sample(x: f64, y: f64, heading: f64, velocity: f64, millis: u64) -> PoseSample {
+    PoseSample {
+        pose: Pose::new(Vec2::new(x, y), QAngle::from_degrees(heading)),
+        linear_velocity: velocity,
+        angular_velocity: 0.0,
+        time: Duration::from_millis(millis),
+    }
+}
+
+// ============================================================================
+// Buffer Tests
+// ============================================================================
+
+#[test]
+fn test_interpolation_matches_trajectory_sample() {
+    let a = sample(0.0, 0.0, 170.0, 0.2, 100);
+    let b = sample(1.0, 0.5, -170.0, 0.6, 200);
+
+    let mut history = PoseHistory::new(10);
+    history.push(a);
+    history.push(b);
+
+    let mut trajectory = Trajectory::new();
+    for s in [a, b] {
+        trajectory.push(TrajectoryPoint::new(
+            s.pose,
+            s.linear_velocity,
+            s.angular_velocity,
+            QTime::from_sec(s.time.as_secs_f64()),
+        ));
+    }
+
+    for millis in [100, 125, 150, 190, 200] {
+        let ours = history.sample_at(Duration::from_millis(millis)).unwrap();
+        let theirs = trajectory
+            .sample(QTime::from_sec(millis as f64 / 1000.0))
+            .unwrap();
+
+        assert!(ours.pose.distance(theirs.pose).as_meters() < 1e-9);
+        assert!(
+            (ours.pose.heading() - theirs.pose.heading())
+                .as_degrees()
+                .abs()
+                < 1e-9
+        );
+        assert!((ours.linear_velocity - theirs.linear_velocity).abs() < 1e-9);
+    }
+
+    // Halfway across ±180° is 180°, not 0°.
+    let mid = history.pose_at(Duration::from_millis(150)).unwrap();
+    assert!((mid.heading().as_degrees().abs() - 180.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_lookup_outside_history_is_none() {
+    let mut history = PoseHistory::new(10);
+    assert!(history.pose_at(Duration::ZERO).is_none());
+
+    history.push(sample(0.0, 0.0, 0.0, 0.0, 100));
+    history.push(sample(1.0, 0.0, 0.0, 0.0, 200));
+
+    assert!(history.pose_at(Duration::from_millis(99)).is_none());
+    assert!(history.pose_at(Duration::from_millis(201)).is_none());
+    let exact = history.pose_at(Duration::from_millis(200)).unwrap();
+    assert!((exact.position().x - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_history_is_bounded() {
+    let mut history = PoseHistory::new(3);
+    for i in 0..5 {
+        history.push(sample(i as f64, 0.0, 0.0, 0.0, i * 10));
+    }
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.oldest().unwrap().time, Duration::from_millis(20));
+    assert_eq!(history.newest().unwrap().time, Duration::from_millis(40));
+
+    // Out-of-order samples are dropped; repeated times replace the newest.
+    history.push(sample(9.0, 0.0, 0.0, 0.0, 30));
+    history.push(sample(5.0, 0.0, 0.0, 0.0, 40));
+    assert_eq!(history.len(), 3);
+    assert!((history.newest().unwrap().pose.position().x - 5.0).abs() < 1e-12);
+
+    history.set_capacity(1);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.oldest().unwrap().time, Duration::from_millis(40));
+
+    let mut disabled = PoseHistory::new(0);
+    disabled.push(sample(0.0, 0.0, 0.0, 0.0, 0));
+    assert!(disabled.is_empty());
+}
+
+// ============================================================================
+// Rig Tests
+// ============================================================================
+
+#[test]
+fn test_rig_recalls_past_poses() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock).with_history(200);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(9.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(300)).await;
+
+        let then = clock.now();
+        let truth_then = robot.pose();
+
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let recalled = rig.pose_at(then).unwrap();
+        assert!(recalled.distance(truth_then).as_meters() < 0.01);
+        assert!(recalled.distance(rig.pose()).as_meters() > 0.3);
+
+        // Between updates the estimate is interpolated.
+        let between = rig.sample_at(then + Duration::from_millis(5)).unwrap();
+        assert!(between.linear_velocity > 0.0);
+
+        // 800 ms of updates at 100 Hz fit in the buffer.
+        let history = rig.history();
+        assert!(history.len() > 75 && history.len() <= 200);
+        assert_eq!(history.oldest().unwrap().time, Duration::ZERO);
+        assert!(rig.pose_at(clock.now() + Duration::from_secs(1)).is_none());
+    });
+}
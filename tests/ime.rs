@@ -0,0 +1,257 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{TRACK_WIDTH, config, drivetrain, tuned};
+use kernelvex::sim::devices::{SimImu, SimMotor, SimRobot};
+use kernelvex::{
+    Clock, DifferentialDrive, DriveError, ExpoDrive, HeadingSource, ImeRig, ManualClock,
+    MotorGroup, MotorOutput, OdomChassis, OmniWheel, Pid, Pose, PoseSource, PurePursuit, QAngle,
+    QLength, QTime, Tank, Trajectory, TravelSource, Vec2,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use vexide::smart::PortError;
+use vexide::smart::motor::BrakeMode;
+
+/// A simulated motor whose encoder reads fail while `connected` is false.
+struct FlakyMotor {
+    motor: SimMotor,
+    connected: Arc<AtomicBool>,
+}
+
+impl FlakyMotor {
+    fn check(&self) -> Result<(), PortError> {
+        if self.connected.load(Ordering::Relaxed) {
+            Ok(())
+        } else {
+            Err(PortError::Disconnected { port: 1 })
+        }
+    }
+}
+
+impl MotorOutput for FlakyMotor {
+    fn set_voltage(&mut self, volts: f64) -> Result<(), PortError> {
+        self.motor.set_voltage(volts)
+    }
+
+    fn brake(&mut self, mode: BrakeMode) -> Result<(), PortError> {
+        self.motor.brake(mode)
+    }
+
+    fn velocity(&self) -> Result<f64, PortError> {
+        self.check()?;
+        self.motor.velocity()
+    }
+
+    fn position(&self) -> Result<QAngle, PortError> {
+        self.check()?;
+        self.motor.position()
+    }
+
+    fn set_position(&mut self, position: QAngle) -> Result<(), PortError> {
+        self.motor.set_position(position)
+    }
+
+    fn reset_position(&mut self) -> Result<(), PortError> {
+        self.motor.reset_position()
+    }
+}
+
+/// A drivetrain whose encoder reads fail while `connected` is false.
+fn flaky_drivetrain(
+    robot: &SimRobot,
+    connected: &Arc<AtomicBool>,
+) -> DifferentialDrive<FlakyMotor> {
+    let motor = |motor| FlakyMotor {
+        motor,
+        connected: Arc::clone(connected),
+    };
+    DifferentialDrive::new(
+        MotorGroup::new([motor(robot.left_motor())]),
+        MotorGroup::new([motor(robot.right_motor())]),
+        ExpoDrive::new(0.0, 1.0, None),
+        OmniWheel::Omni325,
+        QLength::from_meters(TRACK_WIDTH),
+        0.75,
+    )
+}
+
+/// A chassis without a tracking rig, so it falls back to motor encoder
+/// odometry.
+fn chassis(robot: &SimRobot, clock: &ManualClock) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    tuned(OdomChassis::new(drivetrain(robot), robot.imu(), None).with_clock(clock.clone()))
+        .with_left_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+        .with_right_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+}
+
+#[test]
+fn test_ime_rig_follows_arc() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let dt = drivetrain(&robot);
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let with_imu = ImeRig::with_clock(Pose::default(), &dt, Some(robot.imu()), clock.clone());
+        let without_imu = ImeRig::with_clock(Pose::default(), &dt, None::<SimImu>, clock.clone());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(9.0).unwrap();
+        right.set_voltage(5.0).unwrap();
+        clock.sleep(Duration::from_secs(1)).await;
+
+        assert_eq!(with_imu.heading_source(), HeadingSource::Imu);
+        assert_eq!(without_imu.heading_source(), HeadingSource::Wheels);
+        assert!(robot.pose().position().x > 0.4);
+
+        for rig in [&with_imu, &without_imu] {
+            // The rig's latest update is up to one tick behind the robot.
+            let error = rig.pose().distance(robot.pose()).as_meters();
+            assert!(error < 0.02, "{error} m off");
+            let heading = (rig.pose().heading() - robot.pose().heading())
+                .remainder(QAngle::TAU)
+                .as_degrees();
+            assert!(heading.abs() < 2.0, "{heading}° off");
+        }
+        assert!(with_imu.linear_velocity() > 0.5);
+        assert!(with_imu.angular_velocity() < 0.0);
+    });
+}
+
+#[test]
+fn test_chassis_drives_to_pose_without_tracking_rig() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let target = Pose::new(Vec2::new(0.6, 0.4), QAngle::from_degrees(0.0));
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.shoot_to_pose(target).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+
+        let pose = robot.pose();
+        assert!(pose.distance(target).as_meters() < 0.05);
+        assert!(chassis.get_pose().distance(pose).as_meters() < 0.02);
+
+        // Resetting moves the estimate; motion after it is tracked from there.
+        chassis.set_pose(&Pose::new(Vec2::new(1.0, 1.0), QAngle::from_degrees(90.0)));
+        chassis.turn_to_pose(Pose::default()).await.unwrap();
+        let facing = chassis.heading().remainder(QAngle::TAU).as_degrees();
+        assert!((facing + 135.0).abs() < 5.0, "facing {facing}°");
+    });
+}
+
+#[test]
+fn test_pursuit_without_tracking_rig() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let trajectory = Trajectory::from_cubic_bezier(
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.6, 0.0),
+        Vec2::new(0.6, 0.8),
+        Vec2::new(1.2, 0.8),
+        QTime::from_sec(3.0),
+        100,
+        0.6,
+    );
+    let end = trajectory.points().last().unwrap().pose;
+    let path = PurePursuit::new(trajectory, 0.25);
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.pursuit(&path).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    let error = robot.pose().distance(end).as_meters();
+    assert!(error < 0.1, "ended {error} m from the path's end");
+}
+
+#[test]
+fn test_ime_rig_reports_unreadable_encoders() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let connected = Arc::new(AtomicBool::new(true));
+    let mut dt = flaky_drivetrain(&robot, &connected);
+
+    vexide_async::block_on(async {
+        let rig = ImeRig::with_clock(Pose::default(), &dt, Some(robot.imu()), clock.clone());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        dt.drive_tank(0.6, 0.6).await.unwrap();
+        clock.sleep(Duration::from_millis(500)).await;
+        assert_eq!(rig.travel_source(), TravelSource::Drive);
+        assert!(rig.linear_velocity() > 0.3);
+
+        // The robot keeps driving while the encoders cannot be read.
+        connected.store(false, Ordering::Relaxed);
+        clock.sleep(Duration::from_millis(200)).await;
+        let source: &dyn PoseSource = &rig;
+        assert_eq!(source.travel_source(), Some(TravelSource::Unavailable));
+        assert_eq!(rig.linear_velocity(), 0.0);
+        assert_eq!(rig.angular_velocity(), 0.0);
+
+        // Once they recover, the motion during the dropout is caught up.
+        connected.store(true, Ordering::Relaxed);
+        clock.sleep(Duration::from_millis(50)).await;
+        assert_eq!(rig.travel_source(), TravelSource::Drive);
+        let error = rig.pose().distance(robot.pose()).as_meters();
+        assert!(error < 0.02, "{error} m off");
+    });
+}
+
+#[test]
+fn test_chassis_reports_unreadable_drive_encoders() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let connected = Arc::new(AtomicBool::new(true));
+
+    vexide_async::block_on(async {
+        let dt = flaky_drivetrain(&robot, &connected);
+        let mut chassis = tuned(OdomChassis::new(dt, robot.imu(), None).with_clock(clock.clone()));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // Without tracking wheels there are no wheel statuses to report.
+        connected.store(false, Ordering::Relaxed);
+        let target = Pose::new(Vec2::new(0.6, 0.0), QAngle::from_degrees(0.0));
+        let result = chassis.shoot_to_pose(target).await;
+        assert!(
+            matches!(result, Err(DriveError::DriveEncoders)),
+            "{result:?}"
+        );
+    });
+}
+
+#[test]
+fn test_ime_rig_period() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let dt = drivetrain(&robot);
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = ImeRig::with_clock(Pose::default(), &dt, Some(robot.imu()), clock.clone())
+            .with_period(Duration::from_millis(50));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        clock.sleep(Duration::from_millis(510)).await;
+
+        // Updates land every 50 ms, so the estimate holds between them.
+        let before = rig.pose().position().x;
+        clock.sleep(Duration::from_millis(30)).await;
+        assert_eq!(rig.pose().position().x, before);
+        clock.sleep(Duration::from_millis(20)).await;
+        assert!(rig.pose().position().x > before);
+    });
+}
@@ -0,0 +1,196 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{robot, vertical};
+use kernelvex::sim::devices::{SensorNoise, SimImu, SimRobot};
+use kernelvex::{
+    Clock, Gyro, HeadingSource, ImuFusion, ImuFusionConfig, ManualClock, MotorOutput, Pose,
+    TrackingRig,
+};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Difference between a clockwise sensor heading and the robot's true
+/// heading, in degrees.
+fn heading_error<G: Gyro>(imu: &G, robot: &SimRobot) -> f64 {
+    let cw = imu.heading().unwrap().as_degrees();
+    let truth = -robot.pose().heading().as_degrees();
+    (cw - truth + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Reads the fused heading every 10 ms for the given time.
+async fn track<G: Gyro>(imu: &G, clock: &ManualClock, time: Duration) {
+    let end = clock.now() + time;
+    while clock.now() < end {
+        clock.sleep(Duration::from_millis(10)).await;
+        let _ = imu.heading();
+    }
+}
+
+#[test]
+fn test_fusion_corrects_scale_and_rejects_outliers() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    // One sensor reads 357° per turn, one is accurate and one is stuck.
+    let short = robot.imu();
+    short.set_scale(357.0 / 360.0);
+    let stuck = robot.add_imu();
+    stuck.set_scale(0.0);
+    let fusion = ImuFusion::with_clock(
+        [(short, 360.0 / 357.0), (robot.add_imu(), 1.0), (stuck, 1.0)],
+        ImuFusionConfig::new(),
+        clock.clone(),
+    );
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+        left.set_voltage(-8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        track(&fusion, &clock, Duration::from_secs(3)).await;
+    });
+
+    let error = heading_error(&fusion, &robot);
+    assert!(error.abs() < 1.0, "fused heading is {error}° off");
+
+    let status = fusion.status();
+    assert!(status[0].used && status[1].used);
+    assert!(!status[2].used);
+    assert!(status[2].rejections > 100);
+    assert!(status.iter().all(|s| s.connected && s.failures == 0));
+}
+
+#[test]
+fn test_fusion_learns_bias_while_still() {
+    let noise = SensorNoise {
+        imu_drift: 0.5,
+        ..SensorNoise::default()
+    };
+    let robot = robot().with_noise(noise);
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    let raw = robot.imu();
+    let fusion = ImuFusion::with_clock([(robot.imu(), 1.0)], ImuFusionConfig::new(), clock.clone());
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+        track(&fusion, &clock, Duration::from_secs(6)).await;
+
+        // The raw sensor has drifted 3°; the fused heading stopped drifting
+        // once the robot was seen to be still.
+        assert!((heading_error(&raw, &robot) + 3.0).abs() < 0.05);
+        assert!(heading_error(&fusion, &robot).abs() < 0.3);
+        assert!((fusion.bias() - 0.5).abs() < 0.05, "bias {}", fusion.bias());
+        assert!(fusion.gyro_rate().unwrap().abs() < 0.05);
+
+        // While turning, the learned bias is removed.
+        let before = heading_error(&fusion, &robot);
+        left.set_voltage(-6.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        track(&fusion, &clock, Duration::from_secs(2)).await;
+        let drift = heading_error(&fusion, &robot) - before;
+        assert!(drift.abs() < 0.1, "drifted {drift}° while turning");
+    });
+}
+
+#[test]
+fn test_fusion_reports_dropout() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    let first = robot.imu();
+    let second = robot.add_imu();
+    let fusion = ImuFusion::with_clock(
+        [(first.clone(), 1.0), (second.clone(), 1.0)],
+        ImuFusionConfig::new(),
+        clock.clone(),
+    );
+
+    vexide_async::block_on(async {
+        let _physics = robot.spawn_with_clock(clock.clone());
+        left.set_voltage(-8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        track(&fusion, &clock, Duration::from_millis(500)).await;
+
+        // One sensor drops out; the other carries on.
+        second.set_connected(false);
+        track(&fusion, &clock, Duration::from_millis(500)).await;
+        assert_eq!(fusion.connected(), 1);
+        assert!(heading_error(&fusion, &robot).abs() < 0.1);
+        assert!(fusion.gyro_rate().is_ok());
+
+        // With none left, reads fail.
+        first.set_connected(false);
+        assert!(fusion.heading().is_err());
+        assert!(fusion.gyro_rate().is_err());
+
+        first.set_connected(true);
+        second.set_connected(true);
+        track(&fusion, &clock, Duration::from_millis(100)).await;
+    });
+
+    let status = fusion.status();
+    assert!(status.iter().all(|s| s.connected && s.used));
+    assert_eq!(status[0].failures, 1);
+    assert!(status[1].failures >= 50);
+}
+
+#[test]
+fn test_rig_recovers_from_imu_dropout() {
+    let robot = robot();
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    let imu: SimImu = robot.imu();
+
+    vexide_async::block_on(async {
+        let fusion = Rc::new(ImuFusion::with_clock(
+            [(imu.clone(), 1.0)],
+            ImuFusionConfig::new(),
+            clock.clone(),
+        ));
+        let with_wheels = TrackingRig::with_clock(
+            Pose::default(),
+            [],
+            [vertical(&robot, -0.1), vertical(&robot, 0.1)],
+            Some(imu.clone()),
+            clock.clone(),
+        );
+        let imu_only = TrackingRig::with_clock(
+            Pose::default(),
+            [],
+            [vertical(&robot, 0.0)],
+            Some(Rc::clone(&fusion)),
+            clock.clone(),
+        );
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(9.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(300)).await;
+        assert_eq!(with_wheels.heading_source(), HeadingSource::Imu);
+
+        imu.set_connected(false);
+        clock.sleep(Duration::from_millis(300)).await;
+        assert_eq!(with_wheels.heading_source(), HeadingSource::Wheels);
+        assert_eq!(imu_only.heading_source(), HeadingSource::Unavailable);
+        assert!(with_wheels.imu_failures() >= 25);
+        assert_eq!(fusion.connected(), 0);
+        assert!(with_wheels.pose().distance(robot.pose()).as_meters() < 0.01);
+
+        imu.set_connected(true);
+        let stalled = imu_only.pose();
+        clock.sleep(Duration::from_millis(300)).await;
+        assert_eq!(with_wheels.heading_source(), HeadingSource::Imu);
+        assert_eq!(imu_only.heading_source(), HeadingSource::Imu);
+        assert_eq!(fusion.connected(), 1);
+        assert!(imu_only.pose().distance(stalled).as_meters() > 0.1);
+        assert!(with_wheels.pose().distance(robot.pose()).as_meters() < 0.01);
+    });
+}
@@ -0,0 +1,302 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, rig};
+use kernelvex::odom::dist::{Field, MountedSensor, Wall};
+use kernelvex::sim::devices::{SensorNoise, SimDistanceSensor, SimRobot};
+use kernelvex::{
+    Clock, ManualClock, Mcl, MclConfig, MotorOutput, ParticleFilter, Pose, PoseSource, QAngle,
+    QLength, RangeFinder, Vec2,
+};
+use std::time::Duration;
+
+/// Sensors facing forward, left, backward and diagonally, 15 cm from centre.
+fn mounts() -> [Pose; 4] {
+    [
+        Pose::new(Vec2::new(0.15, 0.0), QAngle::from_degrees(0.0)),
+        Pose::new(Vec2::new(0.0, 0.15), QAngle::from_degrees(90.0)),
+        Pose::new(Vec2::new(-0.15, 0.0), QAngle::from_degrees(180.0)),
+        Pose::new(Vec2::new(0.1, -0.1), QAngle::from_degrees(-45.0)),
+    ]
+}
+
+fn readings(field: &Field, truth: Pose) -> Vec<(Pose, QLength)> {
+    mounts()
+        .into_iter()
+        .map(|mount| (mount, field.expected_reading(truth, mount).unwrap()))
+        .collect()
+}
+
+fn heading_error(a: Pose, b: Pose) -> f64 {
+    (a.heading() - b.heading())
+        .remainder(QAngle::TAU)
+        .as_degrees()
+        .abs()
+}
+
+// ============================================================================
+// Field Tests
+// ============================================================================
+
+#[test]
+fn test_field_ray_cast_hits_nearest_wall() {
+    let field = Field::v5().with_rectangle(Vec2::new(0.5, -0.1), Vec2::new(0.7, 0.1));
+    let half = Field::V5_SIZE.as_meters() / 2.0;
+
+    assert_eq!(field.walls().len(), 8);
+
+    // The obstacle blocks the beam toward +x.
+    let forward = field
+        .ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(0.0))
+        .unwrap();
+    assert!((forward.as_meters() - 0.5).abs() < 1e-12);
+
+    // Nothing in the way toward +y.
+    let left = field
+        .ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(90.0))
+        .unwrap();
+    assert!((left.as_meters() - half).abs() < 1e-12);
+
+    // An open map has nothing to hit.
+    let open = Field::new().with_wall(Wall::new(Vec2::new(1.0, 1.0), Vec2::new(2.0, 1.0)));
+    assert!(
+        open.ray_cast(Vec2::new(0.0, 0.0), QAngle::from_degrees(180.0))
+            .is_none()
+    );
+}
+
+#[test]
+fn test_expected_reading_uses_mount() {
+    let field = Field::perimeter(QLength::from_meters(4.0), QLength::from_meters(4.0));
+    let robot = Pose::new(Vec2::new(0.5, 0.0), QAngle::from_degrees(90.0));
+
+    // A sensor 10 cm in front of centre facing right sees the wall at x = 2
+    // when the robot faces +y.
+    let mount = Pose::new(Vec2::new(0.1, 0.0), QAngle::from_degrees(-90.0));
+    let reading = field.expected_reading(robot, mount).unwrap();
+    assert!((reading.as_meters() - 1.5).abs() < 1e-12);
+
+    // Forward, the sensor is 10 cm closer to the wall at y = 2.
+    let front = Pose::new(Vec2::new(0.1, 0.0), QAngle::from_degrees(0.0));
+    let reading = field.expected_reading(robot, front).unwrap();
+    assert!((reading.as_meters() - 1.9).abs() < 1e-12);
+}
+
+// ============================================================================
+// Filter Tests
+// ============================================================================
+
+#[test]
+fn test_filter_converges_from_wrong_guess() {
+    let field = Field::v5();
+    let truth = Pose::new(Vec2::new(0.3, 0.2), QAngle::from_degrees(10.0));
+    let guess = Pose::new(Vec2::new(0.38, 0.14), QAngle::from_degrees(14.0));
+    let config = MclConfig::new()
+        .with_particles(500)
+        .with_initial_std_dev(QLength::from_centimeters(10.0), QAngle::from_degrees(5.0));
+
+    let mut filter = ParticleFilter::new(guess, config);
+    let initial_spread = filter.spread();
+
+    for _ in 0..30 {
+        filter.predict(Vec2::new(0.0, 0.0), QAngle::from_radians(0.0));
+        assert!(filter.update(&readings(&field, truth), &field));
+    }
+
+    let estimate = filter.estimate();
+    assert!(
+        truth.distance(estimate).as_meters() < 0.02,
+        "estimate {estimate}"
+    );
+    assert!(heading_error(truth, estimate) < 1.5, "estimate {estimate}");
+    assert!(filter.spread().as_meters() < initial_spread.as_meters() / 4.0);
+}
+
+#[test]
+fn test_filter_follows_motion() {
+    let field = Field::v5();
+    let mut truth = Pose::new(Vec2::new(-0.5, 0.0), QAngle::from_degrees(0.0));
+    let config = MclConfig::new().with_motion_noise(0.2, 0.05);
+    let mut filter = ParticleFilter::new(truth, config);
+
+    // Drive forward 5 cm per step while the odometry under-reports by 10%.
+    for _ in 0..20 {
+        truth = Pose::new(truth.position() + Vec2::new(0.05, 0.0), truth.heading());
+        filter.predict(Vec2::new(0.045, 0.0), QAngle::from_radians(0.0));
+        filter.update(&readings(&field, truth), &field);
+    }
+
+    let error = truth.distance(filter.estimate()).as_meters();
+    assert!(error < 0.02, "error {error}");
+}
+
+#[test]
+fn test_filter_ignores_out_of_range_readings() {
+    let field = Field::v5();
+    let start = Pose::default();
+    let mut filter = ParticleFilter::new(start, MclConfig::new());
+    let before = filter.estimate();
+
+    let far = [(mounts()[0], QLength::from_meters(5.0))];
+    assert!(!filter.update(&far, &field));
+    assert!(!filter.update(&[], &field));
+    assert_eq!(filter.estimate().position(), before.position());
+}
+
+#[test]
+fn test_resample_keeps_normalised_weights() {
+    let field = Field::v5();
+    let config = MclConfig::new()
+        .with_particles(100)
+        .with_initial_std_dev(QLength::from_centimeters(20.0), QAngle::from_degrees(5.0));
+    let mut filter = ParticleFilter::new(Pose::default(), config);
+
+    filter.update(&readings(&field, Pose::default()), &field);
+    filter.resample();
+
+    assert_eq!(filter.particles().len(), 100);
+    let total: f64 = filter.particles().iter().map(|p| p.weight).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    assert!((filter.effective_sample_size() - 100.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_estimate_heading_wraps() {
+    let config = MclConfig::new()
+        .with_initial_std_dev(QLength::from_centimeters(1.0), QAngle::from_degrees(3.0));
+    let filter = ParticleFilter::new(
+        Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(180.0)),
+        config,
+    );
+
+    // Particles straddle ±180°; a naive mean would land near 0°.
+    let heading = filter.estimate().heading().as_degrees().abs();
+    assert!((heading - 180.0).abs() < 1.0, "heading {heading}");
+}
+
+// ============================================================================
+// Simulation Tests
+// ============================================================================
+
+#[test]
+fn test_sim_distance_sensor_reads_field() {
+    let robot = SimRobot::new(
+        config(),
+        Pose::new(Vec2::new(0.7, 0.0), QAngle::from_degrees(0.0)),
+    );
+    let field = Field::perimeter(QLength::from_meters(3.0), QLength::from_meters(3.0));
+
+    let front = robot.distance_sensor(mounts()[0], field.clone());
+    let reading = front.distance().unwrap().unwrap();
+    assert!((reading.as_meters() - 0.65).abs() < 1e-9);
+
+    // The back wall is 2.05 m from the sensor, beyond its range.
+    let back = robot.distance_sensor(mounts()[2], field);
+    assert!(SimDistanceSensor::MAX_RANGE.as_meters() < 2.05);
+    assert!(back.distance().unwrap().is_none());
+}
+
+#[test]
+fn test_mcl_corrects_odometry_drift() {
+    let noise = SensorNoise {
+        slip: 0.2,
+        distance: 0.01,
+        ..Default::default()
+    };
+    let robot = SimRobot::new(
+        config(),
+        Pose::new(Vec2::new(-0.6, -0.3), QAngle::from_degrees(0.0)),
+    )
+    .with_noise(noise)
+    .with_seed(5);
+    let clock = ManualClock::new();
+    let field = Field::v5();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let sensors = mounts()
+            .map(|mount| MountedSensor::new(robot.distance_sensor(mount, field.clone()), mount));
+        let mcl = Mcl::with_clock(rig, sensors, field.clone(), MclConfig::new(), clock.clone());
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(1200)).await;
+        left.set_voltage(0.0).unwrap();
+        right.set_voltage(0.0).unwrap();
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let truth = robot.pose();
+        let odometry_error = truth.distance(mcl.odometry().pose()).as_meters();
+        let localized: &dyn PoseSource = &mcl;
+        let mcl_error = truth.distance(localized.pose()).as_meters();
+
+        assert!(
+            odometry_error > 0.05,
+            "slip should cause drift, got {odometry_error}"
+        );
+        assert!(mcl_error < 0.02, "localized error {mcl_error}");
+        assert!(heading_error(truth, localized.pose()) < 2.0);
+        assert!(mcl.spread().as_meters() < 0.05);
+    });
+}
+
+/// Odometry for a robot that never moves.
+struct Stationary(Pose);
+
+impl PoseSource for Stationary {
+    fn pose(&self) -> Pose {
+        self.0
+    }
+
+    fn linear_velocity(&self) -> f64 {
+        0.0
+    }
+
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+
+    fn set_pose(&self, _pose: Pose) {}
+}
+
+#[test]
+fn test_mcl_skips_repeated_readings() {
+    let origin = Pose::new(Vec2::new(-0.6, -0.3), QAngle::from_degrees(0.0));
+    let robot = SimRobot::new(config(), origin);
+    let field = Field::v5();
+
+    // Noise-free sensors on a parked robot keep returning the same readings.
+    let spread = |config: MclConfig| {
+        let clock = ManualClock::new();
+        vexide_async::block_on(async {
+            let sensors = mounts().map(|mount| {
+                MountedSensor::new(robot.distance_sensor(mount, field.clone()), mount)
+            });
+            let mcl = Mcl::with_clock(
+                Stationary(origin),
+                sensors,
+                field.clone(),
+                config,
+                clock.clone(),
+            );
+            let _physics = robot.spawn_with_clock(clock.clone());
+            clock.sleep(Duration::from_secs(1)).await;
+            mcl.spread().as_meters()
+        })
+    };
+
+    let once = spread(MclConfig::new());
+    let every_loop = spread(
+        MclConfig::new()
+            .with_update_threshold(QLength::from_meters(0.0), QAngle::from_degrees(0.0)),
+    );
+    // One measurement barely narrows the 2 cm initial cloud; counting it every
+    // loop collapses the cloud as if it were a hundred independent readings.
+    assert!(once > 0.01, "spread {once}");
+    assert!(once > 2.0 * every_loop, "{once} vs {every_loop}");
+}
@@ -0,0 +1,144 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, chassis_with, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{
+    AngularPid, Clock, DriveDirection, DriveError, ManualClock, MoveParams, Pose, QAngle, Vec2,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+fn heading_error(robot: &SimRobot, target: f64) -> f64 {
+    let error = (robot.pose().heading().as_degrees() - target).rem_euclid(360.0);
+    error.min(360.0 - error)
+}
+
+#[test]
+fn test_move_to_point_turns_while_driving() {
+    let point = Vec2::new(1.0, 0.8);
+
+    // Turning and driving together beats turning in place and then driving.
+    let drive = async |simultaneous: bool| {
+        let robot = SimRobot::new(config(), Pose::default());
+        let clock = ManualClock::new();
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        if simultaneous {
+            chassis.move_to_point(point, MoveParams::new()).await
+        } else {
+            chassis
+                .shoot_to_pose(Pose::new(point, QAngle::default()))
+                .await
+        }
+        .unwrap();
+        (clock.now(), robot.pose().position().distance(point))
+    };
+
+    vexide_async::block_on(async {
+        let (moved, error) = drive(true).await;
+        let (shot, _) = drive(false).await;
+
+        assert!(error < 0.05, "error = {error}");
+        assert!(
+            moved < shot,
+            "move_to_point {moved:?}, shoot_to_pose {shot:?}"
+        );
+    });
+}
+
+#[test]
+fn test_move_to_point_reverses_to_points_behind() {
+    let point = Vec2::new(-0.8, 0.1);
+
+    for (direction, heading) in [
+        (DriveDirection::Auto, 0.0),
+        (DriveDirection::Backwards, 0.0),
+        (DriveDirection::Forwards, 180.0),
+    ] {
+        let robot = SimRobot::new(config(), Pose::default());
+        let clock = ManualClock::new();
+
+        vexide_async::block_on(async {
+            let mut chassis = chassis(&robot, &clock);
+            let _physics = robot.spawn_with_clock(clock.clone());
+
+            let params = MoveParams::new().with_direction(direction);
+            chassis.move_to_point(point, params).await.unwrap();
+
+            let error = robot.pose().position().distance(point);
+            assert!(error < 0.05, "{direction:?}: error = {error}");
+            let heading_error = heading_error(&robot, heading);
+            assert!(
+                heading_error < 10.0,
+                "{direction:?}: heading error = {heading_error}"
+            );
+        });
+    }
+}
+
+#[test]
+fn test_forced_direction_turns_before_driving_away() {
+    // Each point lies on the wrong side for the forced direction.
+    for (direction, point) in [
+        (DriveDirection::Forwards, Vec2::new(-0.8, 0.1)),
+        (DriveDirection::Backwards, Vec2::new(0.8, 0.1)),
+    ] {
+        let robot = SimRobot::new(config(), Pose::default());
+        let clock = ManualClock::new();
+
+        vexide_async::block_on(async {
+            // A gentle turn leaves time to drive the wrong way.
+            let mut chassis = chassis(&robot, &clock)
+                .with_angular_pid(AngularPid::new().set_gains(3.0, 0.0, 0.5));
+            let _physics = robot.spawn_with_clock(clock.clone());
+
+            let start = robot.pose().position().distance(point);
+            let farthest = Rc::new(Cell::new(start));
+            let (monitor_robot, monitor_clock) = (robot.clone(), clock.clone());
+            let monitor_farthest = Rc::clone(&farthest);
+            let _monitor = vexide_async::task::spawn(async move {
+                loop {
+                    let distance = monitor_robot.pose().position().distance(point);
+                    monitor_farthest.set(monitor_farthest.get().max(distance));
+                    monitor_clock.sleep(Duration::from_millis(10)).await;
+                }
+            });
+
+            let params = MoveParams::new().with_direction(direction);
+            chassis.move_to_point(point, params).await.unwrap();
+
+            let error = robot.pose().position().distance(point);
+            assert!(error < 0.05, "{direction:?}: error = {error}");
+            let growth = farthest.get() - start;
+            assert!(growth < 0.01, "{direction:?}: moved {growth} m away");
+        });
+    }
+}
+
+#[test]
+fn test_move_to_point_needs_pose_source() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        // With a single vertical wheel the IMU is the only heading sensor.
+        let mut chassis = chassis_with(&robot, &clock, [0.0]);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        robot.imu().set_connected(false);
+        clock.sleep(Duration::from_millis(50)).await;
+        let start = clock.now();
+        let result = chassis
+            .move_to_point(Vec2::new(1.0, 0.0), MoveParams::new())
+            .await;
+
+        assert!(matches!(result, Err(DriveError::Imu(_))), "{result:?}");
+        assert_eq!(clock.now(), start);
+        assert_eq!(robot.pose().position().x, 0.0);
+    });
+}
@@ -1,4 +1,5 @@
-use kernelvex::{AngularPid, Pid, QAngle};
+use core::time::Duration;
+use kernelvex::{AngularPid, Clock, ManualClock, Pid, QAngle, QTime};
 
 #[test]
 fn test_pid_zero_error_output() {
@@ -35,18 +36,90 @@ fn test_pid_output_limits() {
 
 #[test]
 fn test_pid_integral_accumulates() {
-    let mut pid = Pid::new().set_gains(0.0, 1.0, 0.0);
-    let first = pid.calculate(1.0, 0.0);
-    let second = pid.calculate(1.0, 0.0);
-    assert!(second > first);
+    let clock = ManualClock::new();
+    let mut pid = Pid::new()
+        .set_gains(0.0, 1.0, 0.0)
+        .with_clock(clock.clone());
+
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(pid.calculate(2.0, 0.0), 1.0);
+
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(pid.calculate(2.0, 0.0), 1.5);
 }
 
 #[test]
 fn test_pid_derivative_response() {
-    let mut pid = Pid::new().set_gains(0.0, 0.0, 1.0);
+    let clock = ManualClock::new();
+    let mut pid = Pid::new()
+        .set_gains(0.0, 0.0, 1.0)
+        .with_clock(clock.clone());
+
+    clock.advance(Duration::from_millis(100));
     let _ = pid.calculate(0.0, 0.0);
+
+    clock.advance(Duration::from_millis(250));
+    assert_eq!(pid.calculate(1.0, 0.0), 4.0);
+}
+
+#[test]
+fn test_pid_manual_clock_zero_dt_uses_minimum() {
+    let clock = ManualClock::new();
+    let mut pid = Pid::new()
+        .set_gains(0.0, 0.0, 1.0)
+        .with_clock(clock.clone());
+
+    // No time has passed, so the 1 ms floor applies.
     let output = pid.calculate(1.0, 0.0);
-    assert!(output > 0.0);
+    assert!((output - 1000.0).abs() < 1e-9, "got {}", output);
+}
+
+#[test]
+fn test_pid_reset_restarts_clock_interval() {
+    let clock = ManualClock::new();
+    let mut pid = Pid::new()
+        .set_gains(0.0, 1.0, 0.0)
+        .with_clock(clock.clone());
+
+    clock.advance(Duration::from_secs(10));
+    pid.reset();
+
+    clock.advance(Duration::from_millis(500));
+    assert_eq!(pid.calculate(1.0, 0.0), 0.5);
+}
+
+#[test]
+fn test_pid_calculate_with_dt() {
+    let mut pid = Pid::new().set_gains(1.0, 1.0, 1.0);
+
+    // P = 2, I = 2 * 0.5, D = 2 / 0.5
+    let output = pid.calculate_with_dt(2.0, 0.0, QTime::from_sec(0.5));
+    assert_eq!(output, 7.0);
+}
+
+#[test]
+fn test_with_clock_keeps_limits() {
+    let clock = ManualClock::new();
+    let mut pid = Pid::new()
+        .set_gains(10.0, 0.0, 0.0)
+        .with_output_limits(-5.0, 5.0)
+        .with_clock(clock.clone());
+
+    assert_eq!(pid.calculate(1.0, 0.0), 5.0);
+}
+
+#[test]
+fn test_manual_clock_shared_between_clones() {
+    let clock = ManualClock::new();
+    let other = clock.clone();
+
+    other.advance(Duration::from_millis(30));
+    clock.advance(Duration::from_millis(12));
+    assert_eq!(clock.now(), Duration::from_millis(42));
+    assert_eq!(other.now(), Duration::from_millis(42));
+
+    clock.set(Duration::from_secs(1));
+    assert_eq!(other.now(), Duration::from_secs(1));
 }
 
 // =============================================================================
@@ -144,36 +217,51 @@ fn test_angular_pid_wraparound_negative() {
 
 #[test]
 fn test_angular_pid_integral_accumulates() {
-    let mut pid = AngularPid::new().set_gains(0.0, 1.0, 0.0);
-    let first = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
-    let second = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
-    assert!(
-        second > first,
-        "Integral should accumulate: first={}, second={}",
-        first,
-        second
-    );
+    let clock = ManualClock::new();
+    let mut pid = AngularPid::new()
+        .set_gains(0.0, 1.0, 0.0)
+        .with_clock(clock.clone());
+
+    clock.advance(Duration::from_millis(500));
+    let first = pid.calculate(QAngle::from_radians(1.0), QAngle::from_radians(0.0));
+    clock.advance(Duration::from_millis(500));
+    let second = pid.calculate(QAngle::from_radians(1.0), QAngle::from_radians(0.0));
+
+    assert!((first - 0.5).abs() < 1e-12, "first = {}", first);
+    assert!((second - 1.0).abs() < 1e-12, "second = {}", second);
 }
 
 #[test]
 fn test_angular_pid_reset_clears_state() {
-    let mut pid = AngularPid::new().set_gains(0.0, 1.0, 0.0);
+    let clock = ManualClock::new();
+    let mut pid = AngularPid::new()
+        .set_gains(0.0, 1.0, 0.0)
+        .with_clock(clock.clone());
 
     // Accumulate some integral
-    let _ = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
-    let _ = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
-    let before_reset = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
+    for _ in 0..3 {
+        clock.advance(Duration::from_millis(500));
+        let _ = pid.calculate(QAngle::from_radians(1.0), QAngle::from_radians(0.0));
+    }
 
     pid.reset();
 
     // After reset, integral should start fresh
-    let after_reset = pid.calculate(QAngle::from_degrees(10.0), QAngle::from_degrees(0.0));
+    clock.advance(Duration::from_millis(500));
+    let after_reset = pid.calculate(QAngle::from_radians(1.0), QAngle::from_radians(0.0));
+    assert!((after_reset - 0.5).abs() < 1e-12, "after reset = {}", after_reset);
+}
 
-    // before_reset had accumulated integral, after_reset should be smaller
-    assert!(
-        after_reset < before_reset,
-        "Reset should clear state: before={}, after={}",
-        before_reset,
-        after_reset
+#[test]
+fn test_angular_pid_calculate_with_dt_wraps() {
+    let mut pid = AngularPid::new().set_gains(0.0, 0.0, 1.0);
+
+    // Short path from 350° to 10° is +20°, reached over 0.1 s.
+    let output = pid.calculate_with_dt(
+        QAngle::from_degrees(10.0),
+        QAngle::from_degrees(350.0),
+        QTime::from_sec(0.1),
     );
+    let expected = 20.0_f64.to_radians() / 0.1;
+    assert!((output - expected).abs() < 1e-9, "got {}", output);
 }
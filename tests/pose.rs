@@ -1,4 +1,4 @@
-use kernelvex::odom::pose::Pose;
+use kernelvex::odom::pose::{Pose, Twist};
 use kernelvex::util::si::QAngle;
 use std::f64::consts::PI;
 use kernelvex::Vec2;
@@ -381,6 +381,7 @@ fn test_pose_move_local_translated() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::float_equality_without_abs)]
 fn test_pose_move_global() {
     let p1 = Pose::new(Vec2::<f64>::new(1.0, 2.0), QAngle::from_degrees(45.0));
     let p2 = Pose::new(Vec2::<f64>::new(3.0, 4.0), QAngle::from_degrees(90.0));
@@ -488,3 +489,93 @@ fn test_pose_clone() {
     assert_eq!(pos2.y, pos1.y);
     assert!((p1.heading().as_degrees() - p2.heading().as_degrees()).abs() < f64::EPSILON);
 }
+
+// ============================================================================
+// SE(2) Tests
+// ============================================================================
+
+fn assert_pose_eq(actual: Pose, x: f64, y: f64, degrees: f64) {
+    let pos = actual.position();
+    assert!((pos.x - x).abs() < 1e-9, "x = {}", pos.x);
+    assert!((pos.y - y).abs() < 1e-9, "y = {}", pos.y);
+    let heading = (actual.heading() - QAngle::from_degrees(degrees))
+        .remainder(QAngle::TAU)
+        .as_degrees();
+    assert!(heading.abs() < 1e-7, "heading off by {heading}°");
+}
+
+#[test]
+fn test_pose_inverse() {
+    let pose = Pose::new(Vec2::<f64>::new(2.0, 1.0), QAngle::from_degrees(90.0));
+    // The origin seen from (2, 1) facing +y is 1 m behind and 2 m to the left.
+    assert_pose_eq(pose.inverse(), -1.0, 2.0, -90.0);
+    assert_pose_eq(pose * pose.inverse(), 0.0, 0.0, 0.0);
+    assert_pose_eq(pose.inverse() * pose, 0.0, 0.0, 0.0);
+}
+
+#[test]
+fn test_pose_relative_to_and_transform_by() {
+    let origin = Pose::new(Vec2::<f64>::new(1.0, 1.0), QAngle::from_degrees(90.0));
+    let pose = Pose::new(Vec2::<f64>::new(0.0, 3.0), QAngle::from_degrees(180.0));
+
+    let local = pose.relative_to(origin);
+    assert_pose_eq(local, 2.0, 1.0, 90.0);
+    assert_pose_eq(origin.transform_by(local), 0.0, 3.0, 180.0);
+
+    // Moving 1 m forward and turning left from a pose facing +y.
+    let step = Pose::new(Vec2::<f64>::new(1.0, 0.0), QAngle::from_degrees(90.0));
+    assert_pose_eq(origin.transform_by(step), 1.0, 2.0, 180.0);
+}
+
+#[test]
+fn test_pose_exp_closed_form() {
+    let start = Pose::new(Vec2::<f64>::new(1.0, 0.0), QAngle::from_degrees(90.0));
+
+    // Straight line: 2 m forward and 1 m left in the robot's frame.
+    assert_pose_eq(start.exp(Twist::new(2.0, 1.0, 0.0)), 0.0, 2.0, 90.0);
+
+    // Half circle of radius 1 to the left ends 2 m to the left, facing back.
+    assert_pose_eq(start.exp(Twist::new(PI, 0.0, PI)), -1.0, 0.0, 270.0);
+
+    // Turning in place does not move.
+    assert_pose_eq(start.exp(Twist::new(0.0, 0.0, 1.0)), 1.0, 0.0, 90.0 + 1f64.to_degrees());
+
+    // A tiny rotation stays continuous with the straight line.
+    let near = Pose::default().exp(Twist::new(1.0, 0.0, 1e-12));
+    assert!((near.position().x - 1.0).abs() < 1e-9);
+    assert!(near.position().y.abs() < 1e-9);
+}
+
+#[test]
+fn test_pose_log_inverts_exp() {
+    let start = Pose::new(Vec2::<f64>::new(-0.5, 2.0), QAngle::from_degrees(-30.0));
+    let twists = [
+        Twist::new(1.0, 0.0, 0.0),
+        Twist::new(1.5, -0.3, 0.8),
+        Twist::new(0.2, 0.4, -2.5),
+        Twist::new(0.0, 0.0, 1.0),
+    ];
+
+    for twist in twists {
+        let end = start.exp(twist);
+        let log = start.log(end);
+        assert!((log.dx - twist.dx).abs() < 1e-9, "{log:?} vs {twist:?}");
+        assert!((log.dy - twist.dy).abs() < 1e-9, "{log:?} vs {twist:?}");
+        assert!((log.dtheta - twist.dtheta).abs() < 1e-9, "{log:?} vs {twist:?}");
+    }
+
+    // A quarter circle of radius 2.
+    let end = Pose::new(Vec2::<f64>::new(2.0, 2.0), QAngle::from_degrees(90.0));
+    let log = Pose::default().log(end);
+    assert!((log.dx - PI).abs() < 1e-9);
+    assert!(log.dy.abs() < 1e-9);
+    assert!((log.dtheta - PI / 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_twist_scales() {
+    let twist = Twist::new(1.0, -2.0, 0.5) * 0.1;
+    assert!((twist.dx - 0.1).abs() < 1e-12);
+    assert!((twist.dy + 0.2).abs() < 1e-12);
+    assert!((twist.dtheta - 0.05).abs() < 1e-12);
+}
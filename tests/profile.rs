@@ -230,3 +230,74 @@ fn test_profile_time_is_monotonic() {
         "Time should be monotonically increasing"
     );
 }
+
+#[test]
+fn test_profile_between_starts_and_ends_moving() {
+    let constraints = TrapezoidalConstraints {
+        max_velocity: 1.0,
+        max_acceleration: 2.0,
+    };
+
+    let distance = QLength::from_meters(1.0);
+    let profile = constraints.generate_profile_between(distance, 0.5, 0.8);
+
+    let first = profile.first().unwrap();
+    let last = profile.last().unwrap();
+    assert!((first.velocity - 0.5).abs() < EPS);
+    assert!((last.velocity - 0.8).abs() < 1e-3);
+    assert!((last.position.as_meters() - distance.as_meters()).abs() < 1e-3);
+
+    let positions: Vec<f64> = profile.iter().map(|s| s.position.as_meters()).collect();
+    assert!(is_non_decreasing(&positions));
+    assert!(profile.iter().all(|s| {
+        s.velocity <= constraints.max_velocity + EPS
+            && s.acceleration.abs() <= constraints.max_acceleration + EPS
+    }));
+
+    // Zero start and end velocities give the ordinary profile.
+    let ordinary = constraints.generate_profile(distance);
+    let between = constraints.generate_profile_between(distance, 0.0, 0.0);
+    for (a, b) in ordinary.iter().zip(&between) {
+        assert!((a.position.as_meters() - b.position.as_meters()).abs() < EPS);
+        assert!((a.velocity - b.velocity).abs() < EPS);
+    }
+}
+
+#[test]
+fn test_profile_between_unreachable_end_velocity() {
+    let constraints = TrapezoidalConstraints {
+        max_velocity: 2.0,
+        max_acceleration: 1.0,
+    };
+
+    // Too short to reach 2 m/s from rest: accelerate the whole way.
+    let profile = constraints.generate_profile_between(QLength::from_meters(0.5), 0.0, 2.0);
+    let last = profile.last().unwrap();
+    assert!((last.velocity - 1.0).abs() < 1e-3, "got {}", last.velocity);
+    assert!((last.position.as_meters() - 0.5).abs() < 1e-3);
+    let ramp = &profile[..profile.len() - 1];
+    assert!(ramp.iter().all(|s| (s.acceleration - 1.0).abs() < EPS));
+
+    // Too short to stop from 2 m/s: decelerate the whole way.
+    let profile = constraints.generate_profile_between(QLength::from_meters(0.5), 2.0, 0.0);
+    let last = profile.last().unwrap();
+    assert!((last.velocity - 3.0f64.sqrt()).abs() < 1e-3, "got {}", last.velocity);
+    assert!((last.position.as_meters() - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn test_profile_backwards_is_mirrored() {
+    let constraints = TrapezoidalConstraints {
+        max_velocity: 2.0,
+        max_acceleration: 1.0,
+    };
+
+    let forward = constraints.generate_profile_between(QLength::from_meters(1.5), 0.5, 0.0);
+    let backward = constraints.generate_profile_between(QLength::from_meters(-1.5), -0.5, 0.0);
+    for (f, b) in forward.iter().zip(&backward) {
+        assert!(!b.velocity.is_nan());
+        assert!((f.position.as_meters() + b.position.as_meters()).abs() < EPS);
+        assert!((f.velocity + b.velocity).abs() < EPS);
+        assert!((f.time.as_sec() - b.time.as_sec()).abs() < EPS);
+    }
+}
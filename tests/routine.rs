@@ -0,0 +1,248 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::config;
+use kernelvex::sim::devices::{SimImu, SimMotor, SimRobot};
+use kernelvex::{
+    BackgroundChassis, Clock, ErrorPolicy, ExitConditions, ExitReason, ManualClock, Pose, QAngle,
+    QLength, Routine, Step, StepError, StepOutcome,
+};
+use std::cell::RefCell;
+use std::time::Duration;
+
+fn chassis(
+    robot: &SimRobot,
+    clock: &ManualClock,
+) -> BackgroundChassis<SimMotor, SimImu, ManualClock> {
+    BackgroundChassis::new(common::chassis(robot, clock))
+}
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn test_routine_runs_steps_in_order() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let log = RefCell::new(Vec::new());
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let report = Routine::new()
+            .then(Step::shoot(QLength::from_meters(0.5)))
+            .then(Step::turn(QAngle::from_degrees(90.0)))
+            .then(Step::wait(ms(200)))
+            .then(Step::action("clamp", async {
+                log.borrow_mut().push(clock.now());
+                Ok::<(), ()>(())
+            }))
+            .run(&mut chassis)
+            .await;
+
+        assert!(report.succeeded() && !report.aborted, "{report:?}");
+        let names: Vec<_> = report.steps.iter().map(|step| step.name.as_str()).collect();
+        assert_eq!(names, ["shoot", "turn", "wait", "clamp"]);
+        assert!(matches!(
+            report.steps[1].outcome,
+            StepOutcome::Completed(Some(ExitReason::SmallError | ExitReason::LargeError))
+        ));
+        assert!(report.steps[2].elapsed >= ms(200) && report.steps[2].elapsed < ms(220));
+
+        // Steps run back to back and add up to the routine's duration.
+        for pair in report.steps.windows(2) {
+            assert!(pair[1].started >= pair[0].started + pair[0].elapsed);
+        }
+        let total: Duration = report.steps.iter().map(|step| step.elapsed).sum();
+        assert!(report.elapsed >= total && report.elapsed - total < ms(50));
+
+        assert_eq!(log.borrow().len(), 1);
+        let heading = robot.pose().heading().as_degrees();
+        assert!((heading - 90.0).abs() < 2.0, "heading = {heading}");
+    });
+}
+
+#[test]
+fn test_failed_step_aborts_routine() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let report = Routine::new()
+            .then(Step::action("jammed", async { Err("intake stalled") }))
+            .then(Step::shoot(QLength::from_meters(0.5)))
+            .then(Step::wait(ms(100)))
+            .run(&mut chassis)
+            .await;
+
+        assert!(report.aborted && !report.succeeded());
+        assert!(matches!(
+            &report.steps[0].outcome,
+            StepOutcome::Failed(StepError::Action(err)) if err.contains("intake stalled")
+        ));
+        assert!(matches!(report.steps[1].outcome, StepOutcome::Skipped));
+        assert!(matches!(report.steps[2].outcome, StepOutcome::Skipped));
+        assert_eq!(robot.pose().position().x, 0.0);
+
+        // With the continue policy the routine carries on past the failure.
+        let report = Routine::new()
+            .then(
+                Step::action("jammed", async { Err("intake stalled") })
+                    .with_policy(ErrorPolicy::Continue),
+            )
+            .then(Step::shoot(QLength::from_meters(0.5)))
+            .run(&mut chassis)
+            .await;
+
+        assert!(!report.aborted && !report.succeeded());
+        assert!(report.steps[1].outcome.is_ok());
+        assert!((robot.pose().position().x - 0.5).abs() < 0.05);
+    });
+}
+
+#[test]
+fn test_step_timeout_stops_motion() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let report = Routine::new()
+            .then(
+                Step::shoot(QLength::from_meters(2.0))
+                    .with_timeout(ms(500))
+                    .with_policy(ErrorPolicy::Continue),
+            )
+            .then(Step::wait(ms(1000)))
+            .run(&mut chassis)
+            .await;
+
+        let shoot = &report.steps[0];
+        assert!(matches!(shoot.outcome, StepOutcome::TimedOut));
+        assert!(shoot.elapsed >= ms(500) && shoot.elapsed < ms(530));
+        assert!(report.steps[1].outcome.is_ok());
+
+        // The drivetrain was stopped, well short of the target.
+        assert!(robot.linear_velocity().abs() < 0.05);
+        assert!(robot.pose().position().x < 0.5);
+    });
+}
+
+#[test]
+fn test_parallel_group_runs_mechanism_during_drive() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let intake_at = RefCell::new(None);
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let report = Routine::new()
+            .then(Step::parallel([
+                Routine::new().then(Step::shoot(QLength::from_meters(0.8))),
+                Routine::new()
+                    .then(Step::wait(ms(300)))
+                    .then(Step::action("intake", async {
+                        *intake_at.borrow_mut() = Some(robot.pose().position().x);
+                        Ok::<(), ()>(())
+                    })),
+            ]))
+            .run(&mut chassis)
+            .await;
+
+        assert!(report.succeeded(), "{report:?}");
+        let group = &report.steps[0];
+        assert_eq!(group.children.len(), 2);
+        assert_eq!(group.children[1].steps[1].started, ms(300));
+        assert_eq!(group.elapsed, group.children[0].elapsed);
+
+        // The intake ran while the robot was still driving.
+        let x = intake_at.borrow().unwrap();
+        assert!(x > 0.0 && x < 0.4, "intake ran at x = {x}");
+        assert!((robot.pose().position().x - 0.8).abs() < 0.05);
+    });
+}
+
+#[test]
+fn test_group_timeout_keeps_finished_steps() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let report = Routine::new()
+            .then(
+                Step::parallel([
+                    Routine::new().then(Step::wait(ms(200))),
+                    Routine::new()
+                        .then(Step::wait(ms(100)))
+                        .then(Step::shoot(QLength::from_meters(2.0)))
+                        .then(Step::wait(ms(100))),
+                ])
+                .with_timeout(ms(500)),
+            )
+            .run(&mut chassis)
+            .await;
+
+        let group = &report.steps[0];
+        assert!(matches!(group.outcome, StepOutcome::TimedOut));
+        assert_eq!(group.children.len(), 2);
+
+        // The routine that finished keeps its report.
+        let waited = &group.children[0];
+        assert!(waited.succeeded() && !waited.aborted);
+        assert_eq!(waited.steps[0].elapsed, ms(200));
+
+        // The one cut short lists what it finished, what was running and
+        // what it never reached.
+        let driven = &group.children[1];
+        assert!(driven.aborted);
+        assert!(driven.steps[0].outcome.is_ok());
+        assert!(matches!(driven.steps[1].outcome, StepOutcome::TimedOut));
+        assert_eq!(driven.steps[1].started, ms(100));
+        assert_eq!(driven.steps[1].elapsed, ms(400));
+        assert!(matches!(driven.steps[2].outcome, StepOutcome::Skipped));
+
+        // The motion had stopped the drivetrain before the routine moved on.
+        assert!(chassis.is_idle());
+    });
+}
+
+#[test]
+fn test_motion_exit_timeout_is_reported() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // The exit timeout ends the turn long before the step's own.
+        let exit = ExitConditions::new().with_timeout(ms(200));
+        let report = Routine::new()
+            .then(Step::motion("turn", move |chassis| {
+                chassis.run(async move |chassis| {
+                    chassis.turn_with(QAngle::from_degrees(90.0), exit).await
+                })
+            }))
+            .then(Step::wait(ms(100)))
+            .run(&mut chassis)
+            .await;
+
+        assert!(matches!(report.steps[0].outcome, StepOutcome::TimedOut));
+        assert!(report.aborted);
+        assert!(matches!(report.steps[1].outcome, StepOutcome::Skipped));
+    });
+}
@@ -0,0 +1,508 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{TRACK_WIDTH, config, drivetrain, rig, tuned};
+use kernelvex::sim::devices::{SensorNoise, SimImu, SimMotor, SimRobot};
+use kernelvex::sim::plant::{MotorCurve, PlantConfig};
+use kernelvex::util::utils::TrackingWheelOrientation;
+use kernelvex::{
+    Clock, Drivetrain, Gyro, ManualClock, MotorOutput, OdomChassis, OmniWheel, Pid, Pose,
+    PurePursuit, QAngle, QLength, QTime, RamseteController, RotaryEncoder, Trajectory, Vec2,
+};
+use std::time::Duration;
+use vexide::smart::motor::{BrakeMode, Gearset};
+
+/// A chassis tuned for the simulated plant, with drive-side PIDs and a
+/// RAMSETE controller for trajectory following. The chassis and its tracking
+/// rig run on simulated time.
+fn chassis(robot: &SimRobot, clock: &ManualClock) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    let rig = rig(robot, clock);
+
+    tuned(OdomChassis::new(drivetrain(robot), robot.imu(), Some(rig)).with_clock(clock.clone()))
+        .with_left_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+        .with_right_pid(Pid::new().set_gains(4.0, 0.0, 0.0))
+        .with_ramsete(RamseteController::new().set(2.0, 0.7))
+}
+
+/// Builds a Bézier trajectory whose constant velocity matches its path length.
+fn bezier(points: [Vec2<f64>; 4], seconds: f64) -> Trajectory {
+    let build = |velocity| {
+        Trajectory::from_cubic_bezier(
+            points[0],
+            points[1],
+            points[2],
+            points[3],
+            QTime::from_sec(seconds),
+            100,
+            velocity,
+        )
+    };
+    let length: f64 = build(0.0)
+        .points()
+        .windows(2)
+        .map(|w| w[0].pose.distance(w[1].pose).as_meters())
+        .sum();
+
+    build(length / seconds)
+}
+
+fn angle_error(a: QAngle, b: QAngle) -> f64 {
+    (a - b).remainder(QAngle::TAU).as_degrees().abs()
+}
+
+// ============================================================================
+// Plant Tests
+// ============================================================================
+
+#[test]
+fn test_motor_curve_v5() {
+    let blue = MotorCurve::v5(Gearset::Blue);
+    assert_eq!(blue.free_rpm, 600.0);
+    assert!((blue.torque(12.0, 0.0) - 0.35).abs() < 1e-12);
+    assert!(blue.torque(12.0, 600.0).abs() < 1e-12);
+    assert!((blue.torque(6.0, 300.0)).abs() < 1e-12);
+    // Voltage is clamped to the nominal 12 V.
+    assert_eq!(blue.torque(20.0, 0.0), blue.torque(12.0, 0.0));
+}
+
+#[test]
+fn test_plant_reaches_top_speed() {
+    let robot = SimRobot::new(config().with_damping(0.0, 0.0), Pose::default());
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    left.set_voltage(12.0).unwrap();
+    right.set_voltage(12.0).unwrap();
+
+    robot.step(Duration::from_secs(5));
+
+    let v = robot.linear_velocity();
+    assert!((v - config().max_velocity()).abs() < 0.01, "v = {v}");
+    assert!(robot.angular_velocity().abs() < 1e-9);
+    assert!(robot.pose().position().y.abs() < 1e-9);
+    assert!(robot.pose().position().x > 5.0);
+}
+
+#[test]
+fn test_plant_turns_in_place() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    left.set_voltage(-6.0).unwrap();
+    right.set_voltage(6.0).unwrap();
+
+    robot.step(Duration::from_millis(500));
+
+    assert!(
+        robot.angular_velocity() > 0.0,
+        "right side forward turns CCW"
+    );
+    assert!(robot.linear_velocity().abs() < 1e-9);
+    assert!(robot.pose().position().x.abs() < 1e-9);
+    assert!(robot.pose().heading().as_radians() > 0.0);
+}
+
+#[test]
+fn test_brake_stops_sooner_than_coast() {
+    let coast = |mode| {
+        let robot = SimRobot::new(config(), Pose::default());
+        let mut left = robot.left_motor();
+        let mut right = robot.right_motor();
+        left.set_voltage(12.0).unwrap();
+        right.set_voltage(12.0).unwrap();
+        robot.step(Duration::from_secs(1));
+        left.brake(mode).unwrap();
+        right.brake(mode).unwrap();
+        robot.step(Duration::from_millis(300));
+        robot.linear_velocity()
+    };
+
+    let coasting = coast(BrakeMode::Coast);
+    let braking = coast(BrakeMode::Brake);
+    assert!(braking < coasting);
+    assert!(braking >= 0.0);
+}
+
+#[test]
+fn test_plant_config_from_drivetrain() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let dt = drivetrain(&robot);
+    let mirrored = PlantConfig::from_drivetrain(&dt, Gearset::Blue);
+
+    assert_eq!(mirrored.track_width.as_meters(), TRACK_WIDTH);
+    assert_eq!(mirrored.wheel, OmniWheel::Omni325);
+    assert_eq!(mirrored.ratio, config().ratio);
+}
+
+// ============================================================================
+// Device Tests
+// ============================================================================
+
+#[test]
+fn test_sensors_follow_true_motion() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    let forward = robot.encoder(
+        OmniWheel::Omni275,
+        TrackingWheelOrientation::Vertical(QLength::from_meters(0.0)),
+        None,
+    );
+    let sideways = robot.encoder(
+        OmniWheel::Omni275,
+        TrackingWheelOrientation::Horizontal(QLength::from_meters(0.0)),
+        None,
+    );
+    let imu = robot.imu();
+
+    left.set_voltage(8.0).unwrap();
+    right.set_voltage(8.0).unwrap();
+    robot.step(Duration::from_secs(1));
+
+    let travelled = robot.pose().position().x;
+    let measured = forward.position().unwrap().as_turns()
+        * OmniWheel::Omni275.size().as_meters()
+        * std::f64::consts::PI;
+    assert!((measured - travelled).abs() < 1e-9);
+    assert!(sideways.position().unwrap().as_turns().abs() < 1e-12);
+    assert!(imu.heading().unwrap().as_degrees().abs() < 1e-9);
+
+    // Motor encoders report shaft rotation through the external ratio.
+    let wheel_turns = travelled / (OmniWheel::Omni325.size().as_meters() * std::f64::consts::PI);
+    let motor_turns = left.position().unwrap().as_turns();
+    assert!((motor_turns * config().ratio - wheel_turns).abs() < 1e-9);
+}
+
+#[test]
+fn test_imu_reports_clockwise_heading() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    let imu = robot.imu();
+
+    left.set_voltage(-4.0).unwrap();
+    right.set_voltage(4.0).unwrap();
+    robot.step(Duration::from_millis(300));
+
+    let ccw = robot.pose().heading().as_degrees();
+    let cw = imu.heading().unwrap().as_degrees();
+    assert!(ccw > 0.0);
+    assert!((cw - (360.0 - ccw)).abs() < 1e-9);
+    assert!(imu.gyro_rate().unwrap() > 0.0);
+}
+
+#[test]
+fn test_imu_starts_at_zero() {
+    let start = Pose::new(Vec2::new(1.0, 1.0), QAngle::from_degrees(90.0));
+    let robot = SimRobot::new(config(), start);
+
+    assert!(robot.imu().heading().unwrap().as_degrees().abs() < 1e-9);
+}
+
+#[test]
+fn test_noise_is_seeded() {
+    let noisy = SensorNoise {
+        encoder: 0.05,
+        slip: 0.1,
+        imu_heading: 1.0,
+        ..Default::default()
+    };
+
+    let run = |seed| {
+        let robot = SimRobot::new(config(), Pose::default())
+            .with_noise(noisy)
+            .with_seed(seed);
+        let mut left = robot.left_motor();
+        let mut right = robot.right_motor();
+        let encoder = robot.encoder(
+            OmniWheel::Omni275,
+            TrackingWheelOrientation::Vertical(QLength::from_meters(0.0)),
+            None,
+        );
+        left.set_voltage(12.0).unwrap();
+        right.set_voltage(12.0).unwrap();
+        robot.step(Duration::from_secs(1));
+        (
+            encoder.position().unwrap().as_turns(),
+            robot.pose().position().x,
+        )
+    };
+
+    let (a, truth) = run(7);
+    let (b, _) = run(7);
+    let (c, _) = run(8);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+
+    // Slip only ever loses travel.
+    let ideal = truth / (OmniWheel::Omni275.size().as_meters() * std::f64::consts::PI);
+    assert!(a < ideal);
+}
+
+#[test]
+fn test_imu_drift() {
+    let robot = SimRobot::new(config(), Pose::default()).with_noise(SensorNoise {
+        imu_drift: 0.5,
+        ..Default::default()
+    });
+    let imu = robot.imu();
+
+    robot.step(Duration::from_secs(10));
+
+    // 0.5 deg/s counter-clockwise bias over 10 s reads as 5 degrees clockwise of truth.
+    let heading = imu.heading().unwrap().as_degrees();
+    assert!((heading - 355.0).abs() < 1e-6, "heading = {heading}");
+}
+
+#[test]
+fn test_drivetrain_ime_velocity_matches_plant() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let dt = drivetrain(&robot);
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+    left.set_voltage(12.0).unwrap();
+    right.set_voltage(6.0).unwrap();
+    robot.step(Duration::from_secs(2));
+
+    let v = vexide_async::block_on(dt.linear_velocity()).unwrap();
+    let w = vexide_async::block_on(dt.angular_velocity()).unwrap();
+
+    // MotorGroup truncates RPM to an integer, so allow for that resolution.
+    assert!((v - robot.linear_velocity()).abs() < 0.02, "v = {v}");
+    assert!((w - robot.angular_velocity()).abs() < 0.1, "w = {w}");
+}
+
+// ============================================================================
+// End-to-End Chassis Tests
+// ============================================================================
+
+#[test]
+fn test_tracking_rig_follows_simulated_robot() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(10.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(800)).await;
+        left.brake(BrakeMode::Brake).unwrap();
+        right.brake(BrakeMode::Brake).unwrap();
+        clock.sleep(Duration::from_millis(300)).await;
+
+        let truth = robot.pose();
+        let estimate = rig.pose();
+        assert!(truth.distance(estimate).as_meters() < 0.02);
+        assert!(angle_error(truth.heading(), estimate.heading()) < 1.0);
+    });
+}
+
+#[test]
+fn test_tracking_rig_ignores_initial_imu_heading() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    // The IMU was zeroed facing elsewhere; the rig still starts at its origin.
+    robot.imu().set_heading(QAngle::from_degrees(90.0));
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        clock.sleep(Duration::from_millis(50)).await;
+        assert!(angle_error(rig.pose().heading(), QAngle::from_degrees(0.0)) < 1e-6);
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        clock.sleep(Duration::from_millis(500)).await;
+
+        let truth = robot.pose();
+        assert!(truth.distance(rig.pose()).as_meters() < 0.01);
+    });
+}
+
+#[test]
+fn test_tracking_rig_reset_while_moving() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(9.0).unwrap();
+        right.set_voltage(6.0).unwrap();
+        clock.sleep(Duration::from_millis(400)).await;
+
+        // Re-seed into a frame shifted and rotated from the robot's own.
+        let offset = Pose::new(Vec2::new(1.0, -0.5), QAngle::from_degrees(60.0));
+        let reset = robot.pose();
+        let shifted = |pose: Pose| {
+            let theta = offset.heading();
+            let p = pose.position();
+            Pose::new(
+                offset.position()
+                    + Vec2::new(
+                        p.x * theta.cos() - p.y * theta.sin(),
+                        p.x * theta.sin() + p.y * theta.cos(),
+                    ),
+                pose.heading() + theta,
+            )
+        };
+        rig.set_pose(shifted(reset));
+        assert!(rig.pose().distance(shifted(reset)).as_meters() < 1e-12);
+
+        clock.sleep(Duration::from_millis(400)).await;
+
+        let expected = shifted(robot.pose());
+        let estimate = rig.pose();
+        assert!(expected.distance(estimate).as_meters() < 0.01);
+        assert!(angle_error(expected.heading(), estimate.heading()) < 0.5);
+    });
+}
+
+#[test]
+fn test_tracking_rig_partial_resets() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        rig.set_position(Vec2::new(0.4, 0.2));
+        rig.set_heading(QAngle::from_degrees(-45.0));
+        clock.sleep(Duration::from_millis(30)).await;
+
+        let pose = rig.pose();
+        assert!((pose.position().x - 0.4).abs() < 1e-9);
+        assert!((pose.position().y - 0.2).abs() < 1e-9);
+        assert!(angle_error(pose.heading(), QAngle::from_degrees(-45.0)) < 1e-9);
+
+        rig.set_position(Vec2::new(-0.1, 0.0));
+        assert!(angle_error(rig.pose().heading(), QAngle::from_degrees(-45.0)) < 1e-9);
+
+        rig.tare();
+        clock.sleep(Duration::from_millis(30)).await;
+        assert!(rig.pose().distance(Pose::default()).as_meters() < 1e-9);
+        assert!(rig.pose().heading().as_degrees().abs() < 1e-9);
+    });
+}
+
+#[test]
+fn test_shoot_end_to_end() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.shoot(QLength::from_meters(1.0)).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    let pose = robot.pose();
+    assert!(
+        (pose.position().x - 1.0).abs() < 0.05,
+        "x = {}",
+        pose.position().x
+    );
+    assert!(pose.position().y.abs() < 0.05);
+}
+
+#[test]
+fn test_turn_end_to_end() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.turn(QAngle::from_degrees(90.0)).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    let heading = robot.pose().heading();
+    assert!(
+        angle_error(heading, QAngle::from_degrees(90.0)) < 5.0,
+        "heading = {}",
+        heading.as_degrees()
+    );
+}
+
+#[test]
+fn test_trajectory_end_to_end() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let trajectory = bezier(
+        [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.5, 0.0),
+            Vec2::new(0.5, 1.0),
+            Vec2::new(1.0, 1.0),
+        ],
+        3.0,
+    );
+    let end = trajectory.points().last().unwrap().pose;
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.trajectory(&trajectory).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    // The Bézier trajectory ends at cruise speed, so allow for stopping distance.
+    let pose = robot.pose();
+    assert!(
+        pose.distance(end).as_meters() < 0.15,
+        "ended at ({}, {})",
+        pose.position().x,
+        pose.position().y
+    );
+}
+
+#[test]
+fn test_pursuit_end_to_end() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let trajectory = bezier(
+        [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.6, 0.0),
+            Vec2::new(0.6, 0.8),
+            Vec2::new(1.2, 0.8),
+        ],
+        3.0,
+    );
+    let end = trajectory.points().last().unwrap().pose;
+    let path = PurePursuit::new(trajectory, 0.25);
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        chassis.pursuit(&path).await.unwrap();
+        clock.sleep(Duration::from_millis(200)).await;
+    });
+
+    let pose = robot.pose();
+    assert!(
+        pose.distance(end).as_meters() < 0.1,
+        "ended at ({}, {})",
+        pose.position().x,
+        pose.position().y
+    );
+}
@@ -0,0 +1,123 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{chassis, config};
+use kernelvex::sim::devices::SimRobot;
+use kernelvex::{ManualClock, Pose, QAngle, QLength, Side, TurnDirection, Vec2};
+
+fn heading_error(robot: &SimRobot, target: f64) -> f64 {
+    let error = (robot.pose().heading().as_degrees() - target).rem_euclid(360.0);
+    error.min(360.0 - error)
+}
+
+#[test]
+fn test_swing_pivots_on_locked_side() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        let reason = chassis
+            .swing(
+                QAngle::from_degrees(90.0),
+                Side::Left,
+                TurnDirection::Shortest,
+            )
+            .await
+            .unwrap();
+
+        assert!(!reason.is_timeout(), "{reason:?}");
+        let heading = heading_error(&robot, 90.0);
+        assert!(heading < 2.0, "heading error = {heading}");
+
+        // The left wheels stayed at (0, 0.15), so the centre swung forwards
+        // around them.
+        let error = robot.pose().position().distance(Vec2::new(0.15, 0.15));
+        assert!(error < 0.03, "position error = {error}");
+    });
+}
+
+#[test]
+fn test_arc_follows_circle() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let mut chassis = chassis(&robot, &clock);
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        // A quarter circle forwards and left around (0, 0.5).
+        let radius = QLength::from_meters(0.5);
+        chassis
+            .arc(
+                QAngle::from_degrees(90.0),
+                radius,
+                Side::Left,
+                TurnDirection::Shortest,
+            )
+            .await
+            .unwrap();
+
+        let heading = heading_error(&robot, 90.0);
+        assert!(heading < 2.0, "heading error = {heading}");
+        let error = robot.pose().position().distance(Vec2::new(0.5, 0.5));
+        assert!(error < 0.05, "position error = {error}");
+
+        // Clockwise around a centre on the right drives forwards too, back
+        // to the starting heading.
+        chassis
+            .arc(
+                QAngle::from_degrees(0.0),
+                radius,
+                Side::Right,
+                TurnDirection::Clockwise,
+            )
+            .await
+            .unwrap();
+
+        let heading = heading_error(&robot, 0.0);
+        assert!(heading < 2.0, "heading error = {heading}");
+        let error = robot.pose().position().distance(Vec2::new(1.0, 1.0));
+        assert!(error < 0.08, "position error = {error}");
+    });
+}
+
+#[test]
+fn test_swing_direction_takes_long_way() {
+    let swing = |direction| {
+        let robot = SimRobot::new(config(), Pose::default());
+        let clock = ManualClock::new();
+
+        vexide_async::block_on(async {
+            let mut chassis = chassis(&robot, &clock);
+            let _physics = robot.spawn_with_clock(clock.clone());
+
+            chassis
+                .swing(QAngle::from_degrees(90.0), Side::Right, direction)
+                .await
+                .unwrap();
+
+            let heading = heading_error(&robot, 90.0);
+            assert!(heading < 2.0, "heading error = {heading}");
+            robot.pose()
+        })
+    };
+
+    // The short way to 90 degrees backs up a quarter turn around the right
+    // wheels at (0, -0.15).
+    let short = swing(TurnDirection::Shortest);
+    let error = short.position().distance(Vec2::new(-0.15, -0.15));
+    assert!(error < 0.03, "position error = {error}");
+    assert!((short.heading().as_degrees() - 90.0).abs() < 2.0);
+    let ccw = swing(TurnDirection::CounterClockwise);
+    assert!((ccw.heading().as_degrees() - 90.0).abs() < 2.0);
+
+    // Clockwise drives forwards three quarters of a turn instead; the
+    // simulated heading is not wrapped, so it shows the way the robot went.
+    let long = swing(TurnDirection::Clockwise).heading().as_degrees();
+    assert!((long + 270.0).abs() < 2.0, "heading = {long}");
+}
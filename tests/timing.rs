@@ -0,0 +1,138 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{rig, robot};
+use kernelvex::sim::devices::SensorNoise;
+use kernelvex::{Clock, LoopTiming, ManualClock, MotorOutput, VelocityEstimator, VelocityFilter};
+use std::time::Duration;
+
+fn std_dev(samples: &[f64]) -> f64 {
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    variance.sqrt()
+}
+
+#[test]
+fn test_velocity_filters() {
+    // Raw estimates alternate around 1 m/s, as encoder quantization causes.
+    let raw = |i: usize| if i.is_multiple_of(2) { 0.8 } else { 1.2 };
+    let run = |filter| {
+        let mut estimator = VelocityEstimator::new(filter);
+        (0..50)
+            .map(|i| estimator.update(raw(i), 0.01))
+            .collect::<Vec<_>>()
+    };
+
+    let difference = run(VelocityFilter::Difference);
+    assert_eq!(&difference[48..], [0.8, 1.2]);
+
+    let average = run(VelocityFilter::MovingAverage(4));
+    assert!(average[10..].iter().all(|v| (v - 1.0).abs() < 1e-9));
+
+    let low_pass = run(VelocityFilter::LowPass(Duration::from_millis(50)));
+    assert!(low_pass[0] < 0.2, "low-pass starts from rest");
+    assert!(low_pass[40..].iter().all(|v| (v - 1.0).abs() < 0.05));
+
+    let regression = run(VelocityFilter::Regression(8));
+    assert!(regression[10..].iter().all(|v| (v - 1.0).abs() < 0.05));
+
+    // Intervals of zero length are ignored.
+    let mut estimator = VelocityEstimator::new(VelocityFilter::Difference);
+    estimator.update(2.0, 0.01);
+    assert_eq!(estimator.update(5.0, 0.0), 2.0);
+}
+
+#[test]
+fn test_loop_timing_statistics() {
+    let mut timing = LoopTiming::new(Duration::from_millis(10));
+    assert_eq!(timing.mean_period(), Duration::ZERO);
+
+    for ms in [10, 11, 9, 30, 10] {
+        timing.record(Duration::from_millis(ms));
+    }
+
+    assert_eq!(timing.updates(), 5);
+    assert_eq!(timing.mean_period(), Duration::from_millis(14));
+    assert_eq!(timing.max_period(), Duration::from_millis(30));
+    assert_eq!(timing.max_jitter(), Duration::from_millis(20));
+    assert_eq!(timing.missed_deadlines(), 1);
+}
+
+#[test]
+fn test_rig_period_and_missed_deadlines() {
+    let robot = robot();
+    let clock = ManualClock::new();
+
+    vexide_async::block_on(async {
+        let rig = rig(&robot, &clock).with_period(Duration::from_millis(20));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        clock.sleep(Duration::from_secs(1)).await;
+        let timing = rig.timing();
+        assert_eq!(timing.period(), Duration::from_millis(20));
+        assert!(
+            (49..=50).contains(&timing.updates()),
+            "{}",
+            timing.updates()
+        );
+        assert_eq!(timing.mean_period(), Duration::from_millis(20));
+        assert_eq!(timing.missed_deadlines(), 0);
+        assert_eq!(rig.history().len() as u32, timing.updates() + 1);
+
+        // Another task holds the CPU for 70 ms.
+        clock.advance(Duration::from_millis(70));
+        clock.sleep(Duration::from_millis(100)).await;
+        let timing = rig.timing();
+        assert_eq!(timing.missed_deadlines(), 1);
+        assert!(timing.max_jitter() >= Duration::from_millis(50));
+
+        rig.reset_timing();
+        assert_eq!(rig.timing().updates(), 0);
+    });
+}
+
+#[test]
+fn test_rig_velocity_filter_reduces_noise() {
+    let noise = SensorNoise {
+        encoder: 0.3,
+        ..SensorNoise::default()
+    };
+    let robot = robot().with_noise(noise);
+    let clock = ManualClock::new();
+    let mut left = robot.left_motor();
+    let mut right = robot.right_motor();
+
+    vexide_async::block_on(async {
+        let raw = rig(&robot, &clock);
+        let smooth = rig(&robot, &clock).with_velocity_filter(VelocityFilter::MovingAverage(10));
+        let _physics = robot.spawn_with_clock(clock.clone());
+
+        left.set_voltage(8.0).unwrap();
+        right.set_voltage(8.0).unwrap();
+        clock.sleep(Duration::from_secs(3)).await;
+
+        let mut raw_samples = Vec::new();
+        let mut smooth_samples = Vec::new();
+        for _ in 0..100 {
+            clock.sleep(Duration::from_millis(10)).await;
+            raw_samples.push(raw.linear_velocity());
+            smooth_samples.push(smooth.linear_velocity());
+        }
+
+        let mean = |samples: &[f64]| samples.iter().sum::<f64>() / samples.len() as f64;
+        let (raw_mean, smooth_mean) = (mean(&raw_samples), mean(&smooth_samples));
+        assert!(raw_mean > 1.0);
+        assert!(
+            (smooth_mean - raw_mean).abs() < 0.02,
+            "{smooth_mean} vs {raw_mean}"
+        );
+        assert!(
+            std_dev(&smooth_samples) < std_dev(&raw_samples) / 2.0,
+            "{} vs {}",
+            std_dev(&smooth_samples),
+            std_dev(&raw_samples)
+        );
+    });
+}
@@ -0,0 +1,185 @@
+// Links the mock SDK so the async executor can run on the host.
+extern crate vexide;
+
+mod common;
+
+use common::{config, drivetrain, rig_at};
+use kernelvex::odom::dist::{Field, MountedSensor, Wall, WallReading, WallResetError, relocalize};
+use kernelvex::sim::devices::{SimImu, SimMotor, SimRobot};
+use kernelvex::{ManualClock, OdomChassis, Pose, QAngle, QLength, Vec2};
+
+const HALF: f64 = 1.8288;
+
+fn west() -> Wall {
+    Wall::new(Vec2::new(-HALF, -HALF), Vec2::new(-HALF, HALF))
+}
+
+fn south() -> Wall {
+    Wall::new(Vec2::new(-HALF, -HALF), Vec2::new(HALF, -HALF))
+}
+
+fn back() -> Pose {
+    Pose::new(Vec2::new(-0.15, 0.05), QAngle::from_degrees(180.0))
+}
+
+fn right_front() -> Pose {
+    Pose::new(Vec2::new(0.1, -0.12), QAngle::from_degrees(-90.0))
+}
+
+fn right_rear() -> Pose {
+    Pose::new(Vec2::new(-0.1, -0.12), QAngle::from_degrees(-90.0))
+}
+
+/// Builds the reading a sensor would see from `truth`.
+fn reading(truth: Pose, mount: Pose, wall: Wall) -> WallReading {
+    let field = Field::new().with_wall(wall);
+    WallReading {
+        mount,
+        wall,
+        distance: field.expected_reading(truth, mount).unwrap(),
+    }
+}
+
+fn assert_close(actual: Pose, expected: Pose) {
+    let dh = (actual.heading() - expected.heading())
+        .remainder(QAngle::TAU)
+        .as_degrees();
+    assert!(
+        actual.distance(expected).as_meters() < 1e-9 && dh.abs() < 1e-9,
+        "expected {expected}, got {actual}"
+    );
+}
+
+// ============================================================================
+// Relocalization Tests
+// ============================================================================
+
+#[test]
+fn test_single_wall_corrects_one_coordinate() {
+    let truth = Pose::new(Vec2::new(-1.2, -0.4), QAngle::from_degrees(0.0));
+    let estimate = Pose::new(Vec2::new(-1.1, -0.3), QAngle::from_degrees(0.0));
+
+    let pose = relocalize(estimate, &[reading(truth, back(), west())], false).unwrap();
+
+    // x comes from the west wall; y is unconstrained and kept.
+    assert_close(
+        pose,
+        Pose::new(Vec2::new(-1.2, -0.3), QAngle::from_degrees(0.0)),
+    );
+}
+
+#[test]
+fn test_perpendicular_walls_correct_both_coordinates() {
+    let truth = Pose::new(Vec2::new(-1.5, -1.4), QAngle::from_degrees(5.0));
+    let estimate = Pose::new(Vec2::new(-1.3, -1.2), QAngle::from_degrees(5.0));
+
+    let readings = [
+        reading(truth, back(), west()),
+        reading(truth, right_front(), south()),
+    ];
+    assert_close(relocalize(estimate, &readings, false).unwrap(), truth);
+}
+
+#[test]
+fn test_parallel_pair_corrects_heading() {
+    let truth = Pose::new(Vec2::new(-1.5, -1.4), QAngle::from_degrees(4.0));
+    let estimate = Pose::new(Vec2::new(-1.4, -1.3), QAngle::from_degrees(-2.0));
+
+    let readings = [
+        reading(truth, right_front(), south()),
+        reading(truth, right_rear(), south()),
+        reading(truth, back(), west()),
+    ];
+    assert_close(relocalize(estimate, &readings, true).unwrap(), truth);
+
+    // Without the heading correction, the wrong heading skews the result.
+    let skewed = relocalize(estimate, &readings, false).unwrap();
+    assert!(skewed.distance(truth).as_meters() > 1e-3);
+}
+
+#[test]
+fn test_relocalize_errors() {
+    let truth = Pose::new(Vec2::new(-1.2, -0.4), QAngle::from_degrees(0.0));
+
+    assert_eq!(
+        relocalize(truth, &[], false).unwrap_err(),
+        WallResetError::NoReadings
+    );
+
+    // One sensor cannot measure heading.
+    assert_eq!(
+        relocalize(truth, &[reading(truth, back(), west())], true).unwrap_err(),
+        WallResetError::NoHeadingPair
+    );
+
+    // A beam running along the wall it is paired with.
+    let along = WallReading {
+        mount: Pose::new(Vec2::new(0.0, 0.0), QAngle::from_degrees(90.0)),
+        wall: west(),
+        distance: QLength::from_meters(1.0),
+    };
+    assert_eq!(
+        relocalize(truth, &[reading(truth, back(), west()), along], false).unwrap_err(),
+        WallResetError::GrazingBeam(1)
+    );
+}
+
+// ============================================================================
+// Chassis Tests
+// ============================================================================
+
+fn chassis(
+    robot: &SimRobot,
+    clock: &ManualClock,
+    start: Pose,
+) -> OdomChassis<SimMotor, SimImu, ManualClock> {
+    let rig = rig_at(robot, clock, start);
+    OdomChassis::new(drivetrain(robot), robot.imu(), Some(rig)).with_clock(clock.clone())
+}
+
+#[test]
+fn test_chassis_set_pose_moves_tracking_rig() {
+    let robot = SimRobot::new(config(), Pose::default());
+    let clock = ManualClock::new();
+    let mut chassis = chassis(&robot, &clock, Pose::default());
+
+    let target = Pose::new(Vec2::new(0.5, -0.25), QAngle::from_degrees(45.0));
+    chassis.set_pose(&target);
+
+    assert_close(chassis.get_pose(), target);
+    assert!((chassis.heading().as_degrees() - 45.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_chassis_wall_reset_with_simulated_sensors() {
+    let truth = Pose::new(Vec2::new(-1.5, -1.4), QAngle::from_degrees(3.0));
+    let robot = SimRobot::new(config(), truth);
+    let clock = ManualClock::new();
+
+    // Odometry believes it started 8 cm and 3 degrees away from the truth.
+    let guess = Pose::new(Vec2::new(-1.45, -1.34), QAngle::from_degrees(0.0));
+    let mut chassis = chassis(&robot, &clock, guess);
+
+    let field = Field::v5();
+    let sensor = |mount| MountedSensor::new(robot.distance_sensor(mount, field.clone()), mount);
+    let (front, rear, behind) = (sensor(right_front()), sensor(right_rear()), sensor(back()));
+
+    let pose = chassis
+        .wall_reset(
+            &[(&front, south()), (&rear, south()), (&behind, west())],
+            true,
+        )
+        .unwrap();
+
+    assert!(pose.distance(truth).as_meters() < 1e-3, "pose {pose}");
+    assert!((pose.heading() - truth.heading()).as_degrees().abs() < 0.1);
+    assert!(chassis.get_pose().distance(truth).as_meters() < 1e-3);
+
+    // A sensor facing away from every wall it could see leaves the pose alone.
+    let blind = MountedSensor::new(robot.distance_sensor(back(), Field::new()), back());
+    assert_eq!(
+        chassis.wall_reset(&[(&blind, west())], false).unwrap_err(),
+        WallResetError::NoDetection(0)
+    );
+    assert!(chassis.get_pose().distance(truth).as_meters() < 1e-3);
+}